@@ -0,0 +1,107 @@
+//! BattleMogs play loop: every bot hatches two eggs and breeds them.
+
+use crate::{Bot, Interrupt};
+
+use game_battle_mogs::config::GameEventType;
+use sage_client::{
+	battle_mogs::{BattleMogsAction, BattleMogsAssetFor, BattleMogsEvent, BattleMogsId},
+	SageEvent, TransitionOutcome,
+};
+use std::time::Instant;
+
+/// Registers the bot as a player, then plays `rounds` rounds of breeding.
+///
+/// A rejected round is skipped, only failing to register stops the bot.
+pub async fn play(bot: &Bot, rounds: u32) -> Result<(), Interrupt> {
+	setup(bot).await?;
+
+	for _ in 0..rounds {
+		match play_round(bot).await {
+			Ok(()) | Err(Interrupt::Rejected) => {},
+			Err(interrupt) => eprintln!("Bot {} skipped a round: {interrupt}", bot.index),
+		}
+	}
+
+	Ok(())
+}
+
+async fn play_round(bot: &Bot) -> Result<(), Interrupt> {
+	let mogwai_1 = create_mogwai(bot).await?;
+	let mogwai_2 = create_mogwai(bot).await?;
+
+	let bred = breed(bot, mogwai_1, mogwai_2).await;
+
+	// Remove the mogwais even if breeding failed, so that the bot stays below the limit.
+	let offspring = bred.as_ref().ok().copied().flatten();
+	let mut removed = Ok(());
+	for mogwai in [Some(mogwai_1.0), Some(mogwai_2.0), offspring].into_iter().flatten() {
+		let outcome = transition(bot, BattleMogsAction::Remove { mogwai }).await;
+		removed = removed.and(outcome.map(|_| ()));
+	}
+
+	bred.map(|_| ()).and(removed)
+}
+
+/// Hatches both mogwais and breeds them, returning the offspring.
+async fn breed(
+	bot: &Bot,
+	mogwai_1: (BattleMogsId, u32),
+	mogwai_2: (BattleMogsId, u32),
+) -> Result<Option<BattleMogsId>, Interrupt> {
+	let hatch_after = u32::from(GameEventType::time_till(GameEventType::Hatch));
+	bot.wait_for_block(mogwai_1.1.max(mogwai_2.1) + hatch_after).await?;
+	for (mogwai, _) in [mogwai_1, mogwai_2] {
+		transition(bot, BattleMogsAction::Hatch { mogwai }).await?;
+	}
+
+	let outcome =
+		transition(bot, BattleMogsAction::Breed { mogwai_1: mogwai_1.0, mogwai_2: mogwai_2.0 })
+			.await?;
+	Ok(outcome.sage_events().into_iter().find_map(|event| match event {
+		SageEvent::BattleMogs {
+			event: BattleMogsEvent::MogwaiBred { offspring_id, .. }, ..
+		} => Some(offspring_id),
+		_ => None,
+	}))
+}
+
+/// Creates an egg, returning its id and genesis.
+async fn create_mogwai(bot: &Bot) -> Result<(BattleMogsId, u32), Interrupt> {
+	transition(bot, BattleMogsAction::CreateMogwai).await?;
+	// Asset ids are increasing, so the new egg is the latest mogwai of the bot.
+	assets(bot)
+		.await?
+		.into_iter()
+		.filter(|asset| asset.is_mogwai())
+		.max_by_key(|asset| asset.id)
+		.map(|asset| (asset.id, asset.genesis))
+		.ok_or(Interrupt::MissingAsset("mogwai"))
+}
+
+/// Registers the bot, reusing the player of earlier runs, and removes the mogwais those runs
+/// left behind.
+async fn setup(bot: &Bot) -> Result<(), Interrupt> {
+	let assets = assets(bot).await?;
+	if !assets.iter().any(|asset| asset.is_player()) {
+		transition(bot, BattleMogsAction::RegisterPlayer).await?;
+	}
+
+	for mogwai in assets.iter().filter(|asset| asset.is_mogwai()) {
+		transition(bot, BattleMogsAction::Remove { mogwai: mogwai.id }).await?;
+	}
+
+	Ok(())
+}
+
+async fn transition(bot: &Bot, action: BattleMogsAction) -> Result<TransitionOutcome, Interrupt> {
+	let started = Instant::now();
+	let outcome = bot
+		.client
+		.battle_mogs_transition(&bot.signer, action.clone(), vec![], None)
+		.await;
+	bot.record(action, started, outcome)
+}
+
+async fn assets(bot: &Bot) -> Result<Vec<BattleMogsAssetFor>, Interrupt> {
+	Ok(bot.client.battle_mogs_assets_of(&bot.account, None).await?)
+}
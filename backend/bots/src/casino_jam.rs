@@ -0,0 +1,135 @@
+//! CasinoJam play loop: every bot gambles on a seat of its own bandit.
+
+use crate::{Bot, Interrupt};
+
+use sage_client::{
+	casino_jam::{
+		AssetId, AssetType, AssetVariant, CasinoAction, CasinoJamAsset, MachineType,
+		MultiplierType, PlayerType, RentDuration, ReservationDuration, TokenType, VariantType,
+	},
+	TransitionOutcome,
+};
+use std::time::Instant;
+
+/// The assets a bot plays with.
+struct Table {
+	human: AssetId,
+	tracker: AssetId,
+	seat: AssetId,
+	bandit: AssetId,
+}
+
+/// Sets up the bot's assets, then plays `rounds` rounds of `gambles` gambles each.
+///
+/// A rejected round is skipped, only failing to set up the assets stops the bot.
+pub async fn play(bot: &Bot, rounds: u32, gambles: u32) -> Result<(), Interrupt> {
+	let table = setup(bot).await?;
+
+	for _ in 0..rounds {
+		match play_round(bot, &table, gambles).await {
+			Ok(()) | Err(Interrupt::Rejected) => {},
+			Err(interrupt) => eprintln!("Bot {} skipped a round: {interrupt}", bot.index),
+		}
+	}
+
+	Ok(())
+}
+
+async fn play_round(bot: &Bot, table: &Table, gambles: u32) -> Result<(), Interrupt> {
+	transition(bot, CasinoAction::Deposit(AssetType::Player, TokenType::T1000), vec![table.human])
+		.await?;
+	transition(
+		bot,
+		CasinoAction::Reserve(ReservationDuration::Mins5),
+		vec![table.human, table.seat],
+	)
+	.await?;
+
+	let mut gambled = Ok(());
+	for _ in 0..gambles {
+		gambled = transition(
+			bot,
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![table.human, table.tracker, table.seat, table.bandit],
+		)
+		.await
+		.map(|_| ());
+		if gambled.is_err() {
+			break;
+		}
+	}
+
+	// Always release the seat, so that the next round can reserve it again.
+	transition(bot, CasinoAction::Release, vec![table.human, table.seat]).await?;
+	gambled
+}
+
+/// Creates the bandit, seat and player of the bot, reusing the ones of earlier runs.
+async fn setup(bot: &Bot) -> Result<Table, Interrupt> {
+	let bandit_type = VariantType::Machine(MachineType::Bandit);
+	let bandit = match find(&assets(bot).await?, bandit_type) {
+		Some(bandit) => bandit,
+		None => {
+			transition(bot, CasinoAction::Create(AssetType::Machine(MachineType::Bandit)), vec![])
+				.await?;
+			let bandit =
+				find(&assets(bot).await?, bandit_type).ok_or(Interrupt::MissingAsset("bandit"))?;
+			transition(
+				bot,
+				CasinoAction::Deposit(AssetType::Machine(MachineType::Bandit), TokenType::T100000),
+				vec![bandit],
+			)
+			.await?;
+			bandit
+		},
+	};
+
+	let seat = match find(&assets(bot).await?, VariantType::Seat) {
+		Some(seat) => seat,
+		None => {
+			transition(bot, CasinoAction::Rent(RentDuration::Day1), vec![bandit]).await?;
+			find(&assets(bot).await?, VariantType::Seat).ok_or(Interrupt::MissingAsset("seat"))?
+		},
+	};
+
+	let human_type = VariantType::Player(PlayerType::Human);
+	if find(&assets(bot).await?, human_type).is_none() {
+		transition(bot, CasinoAction::Create(AssetType::Player), vec![]).await?;
+	}
+	let assets = assets(bot).await?;
+	let human = find(&assets, human_type).ok_or(Interrupt::MissingAsset("human"))?;
+	let tracker = find(&assets, VariantType::Player(PlayerType::Tracker))
+		.ok_or(Interrupt::MissingAsset("tracker"))?;
+
+	// A seat reserved by an interrupted earlier run has to be released first.
+	let reserved = assets.iter().any(|asset| match &asset.variant {
+		AssetVariant::Seat(variant) => asset.id == seat && variant.player_id.is_some(),
+		_ => false,
+	});
+	if reserved {
+		transition(bot, CasinoAction::Release, vec![human, seat]).await?;
+	}
+
+	Ok(Table { human, tracker, seat, bandit })
+}
+
+async fn transition(
+	bot: &Bot,
+	action: CasinoAction,
+	asset_ids: Vec<AssetId>,
+) -> Result<TransitionOutcome, Interrupt> {
+	let started = Instant::now();
+	let outcome = bot.client.casino_jam_transition(&bot.signer, action, asset_ids, None).await;
+	bot.record(action, started, outcome)
+}
+
+async fn assets(bot: &Bot) -> Result<Vec<CasinoJamAsset>, Interrupt> {
+	Ok(bot.client.casino_jam_assets_of(&bot.account, None).await?)
+}
+
+fn find(assets: &[CasinoJamAsset], variant_type: VariantType) -> Option<AssetId> {
+	assets
+		.iter()
+		.find(|asset| asset.variant.is_variant(variant_type))
+		.map(|asset| asset.id)
+}
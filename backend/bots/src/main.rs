@@ -0,0 +1,264 @@
+//! Load-testing bots for the SAGE games of a playground node.
+//!
+//! Every bot plays with its own dev account, derived as `//SageBot//<index>` and funded by
+//! `--funder` before playing. CasinoJam bots run their own bandit with a rented seat, and
+//! deposit, reserve, gamble and release on it. BattleMogs bots create, hatch and breed
+//! mogwais. When all bots are done, a JSON report with the throughput, the errors and the
+//! resulting economic state is printed.
+
+mod battle_mogs;
+mod casino_jam;
+mod report;
+
+use report::{Economy, Stats};
+
+use clap::Parser;
+use futures::future::{join_all, try_join_all};
+use sage_client::{
+	sp_core::{sr25519, Pair},
+	AccountId, Balance, Error, GameError, Hash, RuntimeCall, SageClient, SageGame,
+	TransitionOutcome, UNIT,
+};
+use serde::Serialize;
+use sp_runtime::DispatchError;
+use std::{
+	sync::Arc,
+	time::{Duration, Instant},
+};
+
+/// How often bots waiting for a block poll the best block.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+enum Games {
+	CasinoJam,
+	BattleMogs,
+	/// Half of the bots play each game.
+	All,
+}
+
+#[derive(Debug, Parser)]
+#[command(about)]
+struct Cli {
+	/// WebSocket endpoint of the node.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	url: String,
+
+	/// The games the bots play.
+	#[arg(long, value_enum, default_value_t = Games::All)]
+	games: Games,
+
+	/// Number of bots playing concurrently.
+	#[arg(long, default_value_t = 10)]
+	bots: u32,
+
+	/// Number of play loops every bot runs.
+	#[arg(long, default_value_t = 5)]
+	rounds: u32,
+
+	/// Number of gambles per CasinoJam round.
+	#[arg(long, default_value_t = 3)]
+	gambles: u32,
+
+	/// Seed of the account funding the bots.
+	#[arg(long, default_value = "//Alice")]
+	funder: String,
+
+	/// Whole tokens transferred to every bot before playing, `0` to skip funding.
+	#[arg(long, default_value_t = 100)]
+	endowment: Balance,
+
+	/// Pretty print the JSON report.
+	#[arg(long)]
+	pretty: bool,
+}
+
+/// A dev account playing one of the games.
+pub struct Bot {
+	pub index: u32,
+	pub game: SageGame,
+	pub signer: sr25519::Pair,
+	pub account: AccountId,
+	pub client: Arc<SageClient>,
+	pub stats: Arc<Stats>,
+}
+
+/// Why a bot stopped its current play loop.
+#[derive(Debug)]
+pub enum Interrupt {
+	/// The chain rejected a transition, which has been recorded in the stats.
+	Rejected,
+	/// A transition didn't make it into a block, or the node couldn't be queried.
+	Client(Error),
+	/// An asset the bot just created couldn't be found.
+	MissingAsset(&'static str),
+}
+
+impl std::fmt::Display for Interrupt {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Interrupt::Rejected => write!(f, "transition rejected"),
+			Interrupt::Client(error) => write!(f, "{error}"),
+			Interrupt::MissingAsset(asset) => write!(f, "missing {asset}"),
+		}
+	}
+}
+
+impl From<Error> for Interrupt {
+	fn from(error: Error) -> Self {
+		Interrupt::Client(error)
+	}
+}
+
+impl Bot {
+	fn new(index: u32, game: SageGame, client: Arc<SageClient>, stats: Arc<Stats>) -> Self {
+		let signer = sr25519::Pair::from_string(&format!("//SageBot//{index}"), None)
+			.expect("derivation path is valid; qed");
+		let account = signer.public().into();
+		Self { index, game, signer, account, client, stats }
+	}
+
+	/// Records the outcome of a transition, interrupting the play loop if it failed.
+	fn record(
+		&self,
+		action: impl Serialize,
+		started: Instant,
+		outcome: Result<TransitionOutcome, Error>,
+	) -> Result<TransitionOutcome, Interrupt> {
+		let action = variant_name(action);
+		match outcome {
+			Ok(outcome) => {
+				let error = outcome.dispatch_error.as_ref().map(error_label);
+				self.stats
+					.record(self.game, &action, started.elapsed(), &outcome, error.clone());
+				match error {
+					None => Ok(outcome),
+					Some(_) => Err(Interrupt::Rejected),
+				}
+			},
+			Err(error) => {
+				self.stats.record_client_error(&error);
+				Err(Interrupt::Client(error))
+			},
+		}
+	}
+
+	/// Waits until the best block is at least `number`.
+	async fn wait_for_block(&self, number: u32) -> Result<(), Interrupt> {
+		while self.client.best_header().await?.number < number {
+			tokio::time::sleep(POLL_INTERVAL).await;
+		}
+		Ok(())
+	}
+}
+
+/// Names the variant of a serialized action, e.g. `Gamble` or `CreateMogwai`.
+fn variant_name(action: impl Serialize) -> String {
+	match serde_json::to_value(action) {
+		Ok(serde_json::Value::String(name)) => name,
+		Ok(serde_json::Value::Object(variant)) if variant.len() == 1 =>
+			variant.into_iter().next().map(|(name, _)| name).unwrap_or_default(),
+		_ => "Unknown".into(),
+	}
+}
+
+/// Names a dispatch error, using the error codes of the games where possible.
+fn error_label(error: &DispatchError) -> String {
+	match GameError::from_dispatch_error(error) {
+		Some(error) => error.to_string(),
+		None => format!("{error:?}"),
+	}
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+	let cli = Cli::parse();
+	let client = Arc::new(SageClient::connect(&cli.url).await?);
+	let stats = Arc::new(Stats::default());
+
+	let bots = (0..cli.bots)
+		.map(|index| {
+			let game = match cli.games {
+				Games::CasinoJam => SageGame::CasinoJam,
+				Games::BattleMogs => SageGame::BattleMogs,
+				Games::All if index % 2 == 0 => SageGame::CasinoJam,
+				Games::All => SageGame::BattleMogs,
+			};
+			Bot::new(index, game, client.clone(), stats.clone())
+		})
+		.collect::<Vec<_>>();
+
+	if cli.endowment > 0 {
+		let funder = sr25519::Pair::from_string(&cli.funder, None)
+			.map_err(|e| format!("Invalid funder seed: {e:?}"))?;
+		eprintln!("Funding {} bots with {} tokens each", bots.len(), cli.endowment);
+		fund(&client, &funder, &bots, cli.endowment.saturating_mul(UNIT)).await?;
+	}
+	let balance_before = total_balance(&client, &bots).await?;
+
+	let (rounds, gambles) = (cli.rounds, cli.gambles);
+	eprintln!("Playing {rounds} rounds with {} bots", bots.len());
+	let started = Instant::now();
+	let results = join_all(bots.iter().map(|bot| async move {
+		match bot.game {
+			SageGame::CasinoJam => casino_jam::play(bot, rounds, gambles).await,
+			SageGame::BattleMogs => battle_mogs::play(bot, rounds).await,
+		}
+	}))
+	.await;
+	let duration = started.elapsed();
+
+	for (bot, result) in bots.iter().zip(results) {
+		if let Err(interrupt) = result {
+			eprintln!("Bot {} stopped during setup: {interrupt}", bot.index);
+		}
+	}
+
+	let metrics = client.game_metrics(None).await?;
+	let economy = Economy::new(balance_before, total_balance(&client, &bots).await?, metrics);
+	let report = stats.report(bots.len() as u32, duration, economy);
+	let json = if cli.pretty {
+		serde_json::to_string_pretty(&report)
+	} else {
+		serde_json::to_string(&report)
+	}?;
+	println!("{json}");
+
+	Ok(())
+}
+
+/// Transfers `amount` from `funder` to every bot, submitting all transfers at once.
+async fn fund(
+	client: &SageClient,
+	funder: &sr25519::Pair,
+	bots: &[Bot],
+	amount: Balance,
+) -> Result<(), Box<dyn std::error::Error>> {
+	let mut context = client.signing_context(&funder.public().into()).await?;
+	let transfers = bots
+		.iter()
+		.map(|bot| {
+			let call = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+				dest: bot.account.clone().into(),
+				value: amount,
+			});
+			let transfer = sage_client::sign(funder, call, &context);
+			context.nonce += 1;
+			transfer
+		})
+		.collect::<Vec<_>>();
+
+	let outcomes =
+		try_join_all(transfers.iter().map(|transfer| client.submit_and_watch(transfer))).await?;
+	match outcomes.into_iter().find_map(|outcome| outcome.dispatch_error) {
+		Some(error) => Err(format!("Funding the bots failed: {error:?}").into()),
+		None => Ok(()),
+	}
+}
+
+async fn total_balance(client: &SageClient, bots: &[Bot]) -> Result<Balance, Error> {
+	let accounts =
+		try_join_all(bots.iter().map(|bot| client.account_info(&bot.account, None::<Hash>)))
+			.await?;
+	Ok(accounts.iter().map(|account| account.data.free).sum())
+}
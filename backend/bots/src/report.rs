@@ -0,0 +1,171 @@
+//! Statistics collected while the bots play, and the report printed at the end.
+
+use sage_client::{Balance, Error, GameMetrics, Hash, SageGame, TransitionOutcome};
+use serde::Serialize;
+use std::{
+	collections::{BTreeMap, HashMap},
+	sync::Mutex,
+	time::Duration,
+};
+
+/// Outcomes of the transitions of all bots.
+#[derive(Default)]
+pub struct Stats(Mutex<StatsInner>);
+
+#[derive(Default)]
+struct StatsInner {
+	actions: BTreeMap<(&'static str, String), ActionStats>,
+	errors: BTreeMap<String, u32>,
+	client_errors: BTreeMap<String, u32>,
+	blocks: HashMap<Hash, u32>,
+}
+
+#[derive(Default)]
+struct ActionStats {
+	executed: u32,
+	failed: u32,
+	latency: Duration,
+}
+
+impl Stats {
+	/// Records a transition included in a block, which failed with `error` if any.
+	pub fn record(
+		&self,
+		game: SageGame,
+		action: &str,
+		latency: Duration,
+		outcome: &TransitionOutcome,
+		error: Option<String>,
+	) {
+		let mut inner = self.0.lock().expect("no bot panics while holding the lock; qed");
+		let stats = inner.actions.entry((game_label(game), action.to_owned())).or_default();
+		stats.latency += latency;
+		match error {
+			None => stats.executed += 1,
+			Some(error) => {
+				stats.failed += 1;
+				*inner.errors.entry(error).or_default() += 1;
+			},
+		}
+		*inner.blocks.entry(outcome.block_hash).or_default() += 1;
+	}
+
+	/// Records a transition that didn't make it into a block.
+	pub fn record_client_error(&self, error: &Error) {
+		let label = match error {
+			Error::Rpc(_) => "rpc",
+			Error::Codec(_) => "codec",
+			Error::UnexpectedResponse(_) => "unexpected response",
+			Error::NotIncluded(_) => "not included",
+		};
+		let mut inner = self.0.lock().expect("no bot panics while holding the lock; qed");
+		*inner.client_errors.entry(label.into()).or_default() += 1;
+	}
+
+	pub fn report(&self, bots: u32, duration: Duration, economy: Economy) -> Report {
+		let inner = self.0.lock().expect("no bot panics while holding the lock; qed");
+		let actions = inner
+			.actions
+			.iter()
+			.map(|((game, action), stats)| ActionReport {
+				game: *game,
+				action: action.clone(),
+				executed: stats.executed,
+				failed: stats.failed,
+				avg_latency_ms: stats.latency.as_millis() as u64 /
+					u64::from(stats.executed + stats.failed).max(1),
+			})
+			.collect::<Vec<_>>();
+		let transitions = inner.blocks.values().sum::<u32>();
+
+		Report {
+			bots,
+			duration_secs: duration.as_secs_f64(),
+			transitions,
+			executed: actions.iter().map(|action| action.executed).sum(),
+			failed: actions.iter().map(|action| action.failed).sum(),
+			tps: f64::from(transitions) / duration.as_secs_f64().max(f64::EPSILON),
+			blocks: inner.blocks.len() as u32,
+			max_transitions_per_block: inner.blocks.values().copied().max().unwrap_or_default(),
+			actions,
+			errors: inner.errors.clone(),
+			client_errors: inner.client_errors.clone(),
+			economy,
+		}
+	}
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+	bots: u32,
+	duration_secs: f64,
+	/// Transitions included in a block, whether they succeeded or not.
+	transitions: u32,
+	executed: u32,
+	failed: u32,
+	/// Included transitions per second.
+	tps: f64,
+	/// Number of blocks including at least one transition of the bots.
+	blocks: u32,
+	max_transitions_per_block: u32,
+	actions: Vec<ActionReport>,
+	/// Failed transitions per game error.
+	errors: BTreeMap<String, u32>,
+	/// Transitions that didn't make it into a block, per kind of client error.
+	client_errors: BTreeMap<String, u32>,
+	economy: Economy,
+}
+
+#[derive(Debug, Serialize)]
+struct ActionReport {
+	game: &'static str,
+	action: String,
+	executed: u32,
+	failed: u32,
+	avg_latency_ms: u64,
+}
+
+/// Balances of the bots and state of the games after playing.
+///
+/// Balances are serialized as strings, since they don't fit into a JSON number.
+#[derive(Debug, Serialize)]
+pub struct Economy {
+	/// Free balance of all bots before playing, after funding them.
+	bot_balance_before: String,
+	bot_balance_after: String,
+	machine_funds: String,
+	seat_funds: String,
+	total_jackpot: u64,
+	/// Number of assets per `game/variant`, of all players.
+	assets: BTreeMap<String, u32>,
+}
+
+impl Economy {
+	pub fn new(
+		bot_balance_before: Balance,
+		bot_balance_after: Balance,
+		metrics: GameMetrics,
+	) -> Self {
+		Self {
+			bot_balance_before: bot_balance_before.to_string(),
+			bot_balance_after: bot_balance_after.to_string(),
+			machine_funds: metrics.machine_funds.to_string(),
+			seat_funds: metrics.seat_funds.to_string(),
+			total_jackpot: metrics.jackpots.iter().map(|(_, jackpot)| u64::from(*jackpot)).sum(),
+			assets: metrics
+				.assets
+				.into_iter()
+				.map(|(game, variant, count)| {
+					(format!("{}/{}", game_label(game), String::from_utf8_lossy(&variant)), count)
+				})
+				.collect(),
+		}
+	}
+}
+
+fn game_label(game: SageGame) -> &'static str {
+	match game {
+		SageGame::CasinoJam => "casino_jam",
+		SageGame::BattleMogs => "battle_mogs",
+	}
+}
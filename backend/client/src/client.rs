@@ -0,0 +1,378 @@
+use crate::{
+	events::{dispatch_error, extrinsic_index},
+	extrinsic::{sign, SigningContext},
+	Error, EventRecord, GameError, PaymentAsset, SageEvent,
+};
+use game_battle_mogs::{prelude::BattleMogsId, BattleMogsAction};
+use game_casino_jam::prelude::{AssetId as CasinoJamId, CasinoAction};
+use sage_playground_runtime::{
+	configs::sage::{
+		battle_mogs::{BattleMogsAssetFor, SageBattleMogsInstance},
+		casino_jam::{CasinoJamAsset, SageCasinoJamInstance},
+		metrics::GameMetrics,
+	},
+	AccountId, Balance, Hash, Header, Nonce, Runtime, RuntimeCall, UncheckedExtrinsic,
+};
+
+use frame_support::storage::StoragePrefixedMap;
+use futures::{Stream, StreamExt};
+use jsonrpsee::{
+	core::client::{ClientT, SubscriptionClientT},
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use parity_scale_codec::{Decode, Encode};
+use sc_transaction_pool_api::TransactionStatus;
+use sp_core::{
+	sr25519,
+	storage::{StorageChangeSet, StorageData, StorageKey},
+	Bytes, Pair,
+};
+use sp_runtime::{traits::Header as _, DispatchError};
+use sp_version::RuntimeVersion;
+
+/// The result of a transition, or any other call, that made it into a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransitionOutcome {
+	pub block_hash: Hash,
+	pub extrinsic_index: u32,
+	/// The events deposited by the extrinsic.
+	pub events: Vec<EventRecord>,
+	/// Why the extrinsic failed, `None` if the transition was executed.
+	pub dispatch_error: Option<DispatchError>,
+}
+
+impl TransitionOutcome {
+	/// Whether the transition was executed.
+	pub fn is_ok(&self) -> bool {
+		self.dispatch_error.is_none()
+	}
+
+	/// The error the game rejected the transition with.
+	pub fn game_error(&self) -> Option<GameError> {
+		self.dispatch_error.as_ref().and_then(GameError::from_dispatch_error)
+	}
+
+	/// The game events deposited by the transition.
+	pub fn sage_events(&self) -> Vec<SageEvent> {
+		self.events.iter().filter_map(SageEvent::from_record).collect()
+	}
+}
+
+/// Client of a SAGE playground node, connected over a WebSocket.
+pub struct SageClient {
+	rpc: WsClient,
+	genesis_hash: Hash,
+}
+
+impl SageClient {
+	/// Connects to the node listening at `url`, e.g. `ws://127.0.0.1:9944`.
+	pub async fn connect(url: &str) -> Result<Self, Error> {
+		let rpc = WsClientBuilder::default().build(url).await?;
+		let genesis_hash = rpc
+			.request::<Option<Hash>, _>("chain_getBlockHash", rpc_params![0])
+			.await?
+			.ok_or_else(|| Error::UnexpectedResponse("missing genesis hash".into()))?;
+
+		Ok(Self { rpc, genesis_hash })
+	}
+
+	/// The underlying RPC client, for calls not covered by this client.
+	pub fn rpc(&self) -> &WsClient {
+		&self.rpc
+	}
+
+	/// Executes a CasinoJam transition signed by `signer` and waits for it to be included in a
+	/// block.
+	pub async fn casino_jam_transition(
+		&self,
+		signer: &sr25519::Pair,
+		action: CasinoAction,
+		asset_ids: Vec<CasinoJamId>,
+		payment_asset: Option<PaymentAsset>,
+	) -> Result<TransitionOutcome, Error> {
+		let call =
+			pallet_sage::Call::<Runtime, SageCasinoJamInstance>::new_call_variant_state_transition(
+				action,
+				asset_ids,
+				payment_asset,
+			);
+		self.execute(signer, RuntimeCall::SageCasinoJam(call)).await
+	}
+
+	/// Executes a BattleMogs transition signed by `signer` and waits for it to be included in a
+	/// block.
+	pub async fn battle_mogs_transition(
+		&self,
+		signer: &sr25519::Pair,
+		action: BattleMogsAction,
+		asset_ids: Vec<BattleMogsId>,
+		payment_asset: Option<PaymentAsset>,
+	) -> Result<TransitionOutcome, Error> {
+		let call =
+			pallet_sage::Call::<Runtime, SageBattleMogsInstance>::new_call_variant_state_transition(
+				action,
+				asset_ids,
+				payment_asset,
+			);
+		self.execute(signer, RuntimeCall::SageBattleMogs(call)).await
+	}
+
+	/// The header of the best block.
+	pub async fn best_header(&self) -> Result<Header, Error> {
+		self.rpc
+			.request::<Option<Header>, _>("chain_getHeader", rpc_params![])
+			.await?
+			.ok_or_else(|| Error::UnexpectedResponse("missing best header".into()))
+	}
+
+	/// The context to sign the next extrinsic of `account` in, against the current best block.
+	///
+	/// Incrementing the `nonce` of the context allows signing several extrinsics of the same
+	/// account before submitting them.
+	pub async fn signing_context(&self, account: &AccountId) -> Result<SigningContext, Error> {
+		let nonce: Nonce =
+			self.rpc.request("system_accountNextIndex", rpc_params![account]).await?;
+		let best = self.best_header().await?;
+		let version: RuntimeVersion =
+			self.rpc.request("state_getRuntimeVersion", rpc_params![]).await?;
+
+		Ok(SigningContext {
+			genesis_hash: self.genesis_hash,
+			best_hash: best.hash(),
+			best_number: best.number,
+			spec_version: version.spec_version,
+			transaction_version: version.transaction_version,
+			nonce,
+		})
+	}
+
+	/// Signs `call` with the next nonce of `signer` against the current best block.
+	pub async fn sign(
+		&self,
+		signer: &sr25519::Pair,
+		call: RuntimeCall,
+	) -> Result<UncheckedExtrinsic, Error> {
+		let context = self.signing_context(&signer.public().into()).await?;
+		Ok(sign(signer, call, &context))
+	}
+
+	/// Signs and submits `call`, then waits for it to be included in a block.
+	pub async fn execute(
+		&self,
+		signer: &sr25519::Pair,
+		call: RuntimeCall,
+	) -> Result<TransitionOutcome, Error> {
+		let extrinsic = self.sign(signer, call).await?;
+		self.submit_and_watch(&extrinsic).await
+	}
+
+	/// Submits a signed `extrinsic` and waits for it to be included in a block.
+	pub async fn submit_and_watch(
+		&self,
+		extrinsic: &UncheckedExtrinsic,
+	) -> Result<TransitionOutcome, Error> {
+		let mut status = self
+			.rpc
+			.subscribe::<TransactionStatus<Hash, Hash>, _>(
+				"author_submitAndWatchExtrinsic",
+				rpc_params![Bytes(extrinsic.encode())],
+				"author_unwatchExtrinsic",
+			)
+			.await?;
+
+		while let Some(status) = status.next().await {
+			match status? {
+				TransactionStatus::InBlock((block_hash, index)) => {
+					let index = index as u32;
+					let events = self
+						.events(Some(block_hash))
+						.await?
+						.into_iter()
+						.filter(|record| extrinsic_index(record) == Some(index))
+						.collect::<Vec<_>>();
+					return Ok(TransitionOutcome {
+						block_hash,
+						extrinsic_index: index,
+						dispatch_error: dispatch_error(&events, index),
+						events,
+					});
+				},
+				TransactionStatus::Future |
+				TransactionStatus::Ready |
+				TransactionStatus::Broadcast(_) => continue,
+				other => return Err(Error::NotIncluded(format!("{other:?}"))),
+			}
+		}
+
+		Err(Error::NotIncluded("subscription closed".into()))
+	}
+
+	/// Reads the CasinoJam asset `id` and its owner, at the best block if `at` is `None`.
+	pub async fn casino_jam_asset(
+		&self,
+		id: CasinoJamId,
+		at: Option<Hash>,
+	) -> Result<Option<(AccountId, CasinoJamAsset)>, Error> {
+		let key = pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::hashed_key_for(id);
+		self.storage(key, at).await
+	}
+
+	/// Reads the BattleMogs asset `id` and its owner, at the best block if `at` is `None`.
+	pub async fn battle_mogs_asset(
+		&self,
+		id: BattleMogsId,
+		at: Option<Hash>,
+	) -> Result<Option<(AccountId, BattleMogsAssetFor)>, Error> {
+		let key = pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::hashed_key_for(id);
+		self.storage(key, at).await
+	}
+
+	/// The CasinoJam assets owned by `owner`, at the best block if `at` is `None`.
+	///
+	/// Iterates all assets of the game, which gets slow on chains with many assets.
+	pub async fn casino_jam_assets_of(
+		&self,
+		owner: &AccountId,
+		at: Option<Hash>,
+	) -> Result<Vec<CasinoJamAsset>, Error> {
+		let prefix = pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::final_prefix();
+		self.assets_of(prefix, owner, at).await
+	}
+
+	/// The BattleMogs assets owned by `owner`, at the best block if `at` is `None`.
+	///
+	/// Iterates all assets of the game, which gets slow on chains with many assets.
+	pub async fn battle_mogs_assets_of(
+		&self,
+		owner: &AccountId,
+		at: Option<Hash>,
+	) -> Result<Vec<BattleMogsAssetFor>, Error> {
+		let prefix = pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::final_prefix();
+		self.assets_of(prefix, owner, at).await
+	}
+
+	async fn assets_of<A: Decode>(
+		&self,
+		prefix: [u8; 32],
+		owner: &AccountId,
+		at: Option<Hash>,
+	) -> Result<Vec<A>, Error> {
+		// Pin the block, so that all pages are read from the same state.
+		let at = match at {
+			Some(at) => at,
+			None => self.best_header().await?.hash(),
+		};
+
+		let mut assets = Vec::new();
+		let mut start_key = None::<StorageKey>;
+		loop {
+			let keys: Vec<StorageKey> = self
+				.rpc
+				.request(
+					"state_getKeysPaged",
+					rpc_params![StorageKey(prefix.to_vec()), KEYS_PER_PAGE, start_key, at],
+				)
+				.await?;
+			if keys.is_empty() {
+				break;
+			}
+
+			let values: Vec<StorageChangeSet<Hash>> =
+				self.rpc.request("state_queryStorageAt", rpc_params![&keys, at]).await?;
+			for (_, data) in values.into_iter().flat_map(|values| values.changes) {
+				let Some(StorageData(data)) = data else { continue };
+				let (asset_owner, asset) = <(AccountId, A)>::decode(&mut &data[..])?;
+				if &asset_owner == owner {
+					assets.push(asset);
+				}
+			}
+
+			if keys.len() < KEYS_PER_PAGE as usize {
+				break;
+			}
+			start_key = keys.last().cloned();
+		}
+
+		Ok(assets)
+	}
+
+	/// The nonce and balances of `account`, at the best block if `at` is `None`.
+	pub async fn account_info(
+		&self,
+		account: &AccountId,
+		at: Option<Hash>,
+	) -> Result<AccountInfo, Error> {
+		let key = frame_system::Account::<Runtime>::hashed_key_for(account);
+		Ok(self.storage(key, at).await?.unwrap_or_default())
+	}
+
+	/// Asset counts, locked funds and jackpots of the games, at the best block if `at` is
+	/// `None`.
+	pub async fn game_metrics(&self, at: Option<Hash>) -> Result<GameMetrics, Error> {
+		let result: Bytes = self
+			.rpc
+			.request(
+				"state_call",
+				rpc_params!["SageMetricsApi_game_metrics", Bytes(Vec::new()), at],
+			)
+			.await?;
+		Ok(GameMetrics::decode(&mut &result[..])?)
+	}
+
+	/// The events deposited in the block `at`, or in the best block if `at` is `None`.
+	pub async fn events(&self, at: Option<Hash>) -> Result<Vec<EventRecord>, Error> {
+		Ok(self.storage(events_key(), at).await?.unwrap_or_default())
+	}
+
+	/// Follows the events of every new best block.
+	pub async fn subscribe_events(
+		&self,
+	) -> Result<impl Stream<Item = Result<(Hash, Vec<EventRecord>), Error>>, Error> {
+		let changes = self
+			.rpc
+			.subscribe::<StorageChangeSet<Hash>, _>(
+				"state_subscribeStorage",
+				rpc_params![vec![StorageKey(events_key())]],
+				"state_unsubscribeStorage",
+			)
+			.await?;
+
+		Ok(changes.map(|changes| {
+			let changes = changes?;
+			let events = match changes.changes.into_iter().next().and_then(|(_, data)| data) {
+				Some(StorageData(data)) => Vec::<EventRecord>::decode(&mut &data[..])?,
+				None => Vec::new(),
+			};
+			Ok((changes.block, events))
+		}))
+	}
+
+	/// Follows the game events of every new best block.
+	pub async fn subscribe_sage_events(
+		&self,
+	) -> Result<impl Stream<Item = Result<(Hash, Vec<SageEvent>), Error>>, Error> {
+		Ok(self.subscribe_events().await?.map(|block| {
+			block.map(|(hash, records)| {
+				(hash, records.iter().filter_map(SageEvent::from_record).collect())
+			})
+		}))
+	}
+
+	async fn storage<T: Decode>(&self, key: Vec<u8>, at: Option<Hash>) -> Result<Option<T>, Error> {
+		let data: Option<StorageData> =
+			self.rpc.request("state_getStorage", rpc_params![StorageKey(key), at]).await?;
+		data.map(|StorageData(data)| T::decode(&mut &data[..]).map_err(Error::from))
+			.transpose()
+	}
+}
+
+/// The nonce and balances of an account.
+pub type AccountInfo = frame_system::AccountInfo<Nonce, pallet_balances::AccountData<Balance>>;
+
+/// The number of storage keys requested at once when iterating assets.
+const KEYS_PER_PAGE: u32 = 1_000;
+
+fn events_key() -> Vec<u8> {
+	frame_support::storage::storage_prefix(b"System", b"Events").to_vec()
+}
@@ -0,0 +1,53 @@
+use std::fmt;
+
+/// Errors of the [`SageClient`](crate::SageClient).
+#[derive(Debug)]
+pub enum Error {
+	/// The RPC request failed or the connection was lost.
+	Rpc(jsonrpsee::core::ClientError),
+	/// The node returned data that doesn't match the types of the playground runtime.
+	Codec(parity_scale_codec::Error),
+	/// The node answered with an unexpected value, e.g. a missing genesis hash.
+	UnexpectedResponse(String),
+	/// The transaction was dropped, invalidated or retracted before being included in a block.
+	NotIncluded(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Error::Rpc(error) => write!(f, "RPC error: {error}"),
+			Error::Codec(error) => write!(f, "Undecodable response: {error}"),
+			Error::UnexpectedResponse(message) => write!(f, "Unexpected response: {message}"),
+			Error::NotIncluded(status) => write!(f, "Transaction not included: {status}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {
+	fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+		match self {
+			Error::Rpc(error) => Some(error),
+			Error::Codec(error) => Some(error),
+			_ => None,
+		}
+	}
+}
+
+impl From<jsonrpsee::core::ClientError> for Error {
+	fn from(error: jsonrpsee::core::ClientError) -> Self {
+		Error::Rpc(error)
+	}
+}
+
+impl From<serde_json::Error> for Error {
+	fn from(error: serde_json::Error) -> Self {
+		Error::UnexpectedResponse(error.to_string())
+	}
+}
+
+impl From<parity_scale_codec::Error> for Error {
+	fn from(error: parity_scale_codec::Error) -> Self {
+		Error::Codec(error)
+	}
+}
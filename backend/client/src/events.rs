@@ -0,0 +1,81 @@
+use crate::GameError;
+use game_battle_mogs::{prelude::BattleMogsEvent, BattleMogsAction};
+use game_casino_jam::prelude::{CasinoAction, CasinoJamEvent};
+use sage_playground_runtime::{AccountId, Hash, RuntimeEvent};
+use sp_runtime::DispatchError;
+
+pub type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
+
+/// A runtime event concerning the SAGE games.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SageEvent {
+	CasinoJamTransition {
+		account: AccountId,
+		action: CasinoAction,
+	},
+	BattleMogsTransition {
+		account: AccountId,
+		action: BattleMogsAction,
+	},
+	CasinoJam {
+		account: AccountId,
+		event: CasinoJamEvent,
+	},
+	BattleMogs {
+		account: AccountId,
+		event: BattleMogsEvent,
+	},
+	/// An extrinsic failed because a game rejected the transition.
+	TransitionFailed {
+		extrinsic_index: u32,
+		error: GameError,
+	},
+}
+
+impl SageEvent {
+	/// Decodes the game event of `record`, `None` for events of other pallets.
+	pub fn from_record(record: &EventRecord) -> Option<Self> {
+		let event = match &record.event {
+			RuntimeEvent::SageCasinoJam(pallet_sage::Event::TransitionExecuted { account, id }) =>
+				SageEvent::CasinoJamTransition { account: account.clone(), action: *id },
+			RuntimeEvent::SageBattleMogs(pallet_sage::Event::TransitionExecuted {
+				account,
+				id,
+			}) => SageEvent::BattleMogsTransition { account: account.clone(), action: id.clone() },
+			RuntimeEvent::SageCasinoJamGameEvents(pallet_sage_game_events::Event::GameEvent {
+				account,
+				event,
+			}) => SageEvent::CasinoJam { account: account.clone(), event: event.clone() },
+			RuntimeEvent::SageBattleMogsGameEvents(pallet_sage_game_events::Event::GameEvent {
+				account,
+				event,
+			}) => SageEvent::BattleMogs { account: account.clone(), event: event.clone() },
+			RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+				dispatch_error, ..
+			}) => SageEvent::TransitionFailed {
+				extrinsic_index: extrinsic_index(record)?,
+				error: GameError::from_dispatch_error(dispatch_error)?,
+			},
+			_ => return None,
+		};
+		Some(event)
+	}
+}
+
+/// Index of the extrinsic that deposited `record`, `None` for events of the block hooks.
+pub(crate) fn extrinsic_index(record: &EventRecord) -> Option<u32> {
+	match record.phase {
+		frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+		_ => None,
+	}
+}
+
+/// The dispatch error of the extrinsic at `index`, if it failed.
+pub(crate) fn dispatch_error(records: &[EventRecord], index: u32) -> Option<DispatchError> {
+	records.iter().find_map(|record| match &record.event {
+		RuntimeEvent::System(frame_system::Event::ExtrinsicFailed { dispatch_error, .. })
+			if extrinsic_index(record) == Some(index) =>
+			Some(*dispatch_error),
+		_ => None,
+	})
+}
@@ -0,0 +1,70 @@
+use sage_playground_runtime::{
+	configs::BlockHashCount, BlockNumber, Hash, Nonce, Runtime, RuntimeCall, Signature,
+	SignedExtra, SignedPayload, UncheckedExtrinsic,
+};
+
+use parity_scale_codec::Encode;
+use sp_core::{sr25519, Pair};
+use sp_runtime::generic::Era;
+
+/// Chain state a signed extrinsic commits to.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SigningContext {
+	pub genesis_hash: Hash,
+	/// The block the extrinsic's mortality starts at.
+	pub best_hash: Hash,
+	pub best_number: BlockNumber,
+	pub spec_version: u32,
+	pub transaction_version: u32,
+	pub nonce: Nonce,
+}
+
+/// Signs `call` with `signer`, paying no tip.
+///
+/// The extrinsic is valid for as long as the chain remembers the hash of the block it was
+/// signed at.
+pub fn sign(
+	signer: &sr25519::Pair,
+	call: RuntimeCall,
+	context: &SigningContext,
+) -> UncheckedExtrinsic {
+	let period = BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2);
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::mortal(
+			period.into(),
+			context.best_number.into(),
+		)),
+		frame_system::CheckNonce::<Runtime>::from(context.nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		frame_metadata_hash_extension::CheckMetadataHash::<Runtime>::new(false),
+	);
+
+	let payload = SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		(
+			(),
+			context.spec_version,
+			context.transaction_version,
+			context.genesis_hash,
+			context.best_hash,
+			(),
+			(),
+			(),
+			None,
+		),
+	);
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+
+	UncheckedExtrinsic::new_signed(
+		call,
+		sp_runtime::AccountId32::from(signer.public()).into(),
+		Signature::Sr25519(signature),
+		extra,
+	)
+}
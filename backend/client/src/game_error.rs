@@ -0,0 +1,289 @@
+use sage_playground_runtime::{
+	configs::{
+		sage::{battle_mogs::SageBattleMogsInstance, casino_jam::SageCasinoJamInstance},
+		SageGame,
+	},
+	Runtime, SageBattleMogs, SageCasinoJam,
+};
+
+use frame_support::traits::PalletInfoAccess;
+use scale_info::{TypeDef, TypeInfo};
+use sp_runtime::{DispatchError, ModuleError};
+use std::fmt;
+
+/// Declares an error enum mirroring the `u8` codes of a game's `error` module.
+macro_rules! game_errors {
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident from $($module:ident)::+ {
+			$($(#[$variant_meta:meta])* $variant:ident = $code:ident,)*
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+		pub enum $name {
+			$($(#[$variant_meta])* $variant,)*
+		}
+
+		impl $name {
+			/// All errors of the game.
+			pub const ALL: &'static [Self] = &[$(Self::$variant),*];
+
+			/// The error returned by the game as `code`, if it is a known one.
+			pub fn from_code(code: u8) -> Option<Self> {
+				use $($module)::+::*;
+				match code {
+					$($code => Some(Self::$variant),)*
+					_ => None,
+				}
+			}
+
+			/// The code the game returns for the error.
+			pub fn code(&self) -> u8 {
+				use $($module)::+::*;
+				match self {
+					$(Self::$variant => $code,)*
+				}
+			}
+		}
+
+		impl fmt::Display for $name {
+			fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+				write!(f, "{self:?} ({})", self.code())
+			}
+		}
+	};
+}
+
+game_errors! {
+	/// Errors of the CasinoJam transitions, see [`game_casino_jam::error`].
+	pub enum CasinoJamError from game_casino_jam::error {
+		AssetsNotAllSameType = ASSETS_NOT_ALL_SAME_TYPE,
+		AssetTypeNotFoundInAccount = ASSET_TYPE_NOT_FOUND_IN_ACCOUNT,
+		AssetTypeAlreadyInAccount = ASSET_TYPE_ALREADY_IN_ACCOUNT,
+		AssetCouldNotReceiveFunds = ASSET_COULD_NOT_RECEIVE_FUNDS,
+		AssetCouldNotWithdrawFunds = ASSET_COULD_NOT_WITHDRAW_FUNDS,
+		AssetCouldNotWithdrawPlayFee = ASSET_COULD_NOT_WITHDRAW_PLAY_FEE,
+		AssetCouldNotReceivePlayFee = ASSET_COULD_NOT_RECEIVE_PLAY_FEE,
+		AssetCouldNotWithdrawMaxReward = ASSET_COULD_NOT_WITHDRAW_MAX_REWARD,
+		AssetCouldNotReceiveMaxReward = ASSET_COULD_NOT_RECEIVE_MAX_REWARD,
+		AssetCouldNotWithdrawSpinReward = ASSET_COULD_NOT_WITHDRAW_SPIN_REWARD,
+		AssetCouldNotReceiveSpinReward = ASSET_COULD_NOT_RECEIVE_SPIN_REWARD,
+		CouldNotPerformMachineSpins = COULD_NOT_PERFORM_MACHINE_SPINS,
+		MachineCannotRentMoreSeats = MACHINE_CANNOT_RENT_MORE_SEATS,
+		SeatIsNotLinkedToPlayer = SEAT_IS_NOT_LINKED_TO_PLAYER,
+		SeatReservationHasExpired = SEAT_RESERVATION_HAS_EXPIRED,
+		SeatIsNotLinkedToSpecifiedPlayer = SEAT_IS_NOT_LINKED_TO_SPECIFIED_PLAYER,
+		SeatHasNoFunds = SEAT_HAS_NO_FUNDS,
+		SeatReservationIsNotValid = SEAT_RESERVATION_IS_NOT_VALID,
+		SeatIsNotLinkedToMachine = SEAT_IS_NOT_LINED_TO_MACHINE,
+		/// The machine has no linked seats, or still has linked seats when being withdrawn.
+		///
+		/// `MACHINE_HAS_NO_LINKED_SEATS` and `MACHINE_STILL_HAS_LINKED_SEATS` share the same code,
+		/// so both are reported as this error.
+		MachineLinkedSeats = MACHINE_HAS_NO_LINKED_SEATS,
+		SeatIsStillLinkedToPlayer = SEAT_IS_STILL_LINKED_TO_PLAYER,
+		SeatIsNotReclaimable = SEAT_IS_NOT_RECLAIMABLE,
+		AssetNotFound = ASSET_NOT_FOUND,
+		AssetScoreNotEnough = ASSET_SCORE_NOT_ENOUGH,
+		AssetTypeNotValid = ASSET_TYPE_NOT_VALID,
+		AssetVariantIsNotPlayer = ASSET_VARIANT_IS_NOT_PLAYER,
+		AssetVariantIsNotMachine = ASSET_VARIANT_IS_NOT_MACHINE,
+		AssetVariantIsNotHuman = ASSET_VARIANT_IS_NOT_HUMAN,
+		AssetVariantIsNotTracker = ASSET_VARIANT_IS_NOT_TRACKER,
+		AssetVariantIsNotSeat = ASSET_VARIANT_IS_NOT_SEAT,
+	}
+}
+
+game_errors! {
+	/// Errors of the BattleMogs transitions, see [`game_battle_mogs::error`].
+	pub enum BattleMogsError from game_battle_mogs::error {
+		AssetNotFound = ASSET_NOT_FOUND,
+		MogwaiLimitReached = MOGWAI_LIMIT_REACHED,
+		PlayerAlreadyRegistered = PLAYER_ALREADY_REGISTERED,
+		AssetIsNotMogwai = ASSET_IS_NOT_MOGWAI,
+		AssetIsNotPlayer = ASSET_IS_NOT_PLAYER,
+		CannotUseSameAssetForBreeding = CANNOT_USE_SAME_ASSET_FOR_BREEDING,
+		MogwaiStillInBredPhase = MOGWAI_STILL_IN_BRED_PHASE,
+		MogwaiNotInBredPhase = MOGWAI_NOT_IN_BRED_PHASE,
+		MogwaiHasInvalidRarity = MOGWAI_HAS_INVALID_RARITY,
+		MogwaiNotOfferedAsStud = MOGWAI_NOT_OFFERED_AS_STUD,
+		InvalidStudOffer = INVALID_STUD_OFFER,
+		MogwaiHasNoFunds = MOGWAI_HAS_NO_FUNDS,
+		MogwaisTooCloselyRelated = MOGWAIS_TOO_CLOSELY_RELATED,
+		MogwaiOnBreedingCooldown = MOGWAI_ON_BREEDING_COOLDOWN,
+		MogwaiBreedLimitReached = MOGWAI_BREED_LIMIT_REACHED,
+		PlayerNotRegistered = PLAYER_NOT_REGISTERED,
+		AssetIsNotItem = ASSET_IS_NOT_ITEM,
+		ItemAlreadyEquipped = ITEM_ALREADY_EQUIPPED,
+		ItemNotEquipped = ITEM_NOT_EQUIPPED,
+		ItemSlotOccupied = ITEM_SLOT_OCCUPIED,
+		InvalidCraftingIngredients = INVALID_CRAFTING_INGREDIENTS,
+		MogwaiOnExpedition = MOGWAI_ON_EXPEDITION,
+		MogwaiNotOnExpedition = MOGWAI_NOT_ON_EXPEDITION,
+		ExpeditionNotFinished = EXPEDITION_NOT_FINISHED,
+		MogwaiInjured = MOGWAI_INJURED,
+		InvalidBatch = INVALID_BATCH,
+		AssetCouldNotReceiveFunds = ASSET_COULD_NOT_RECEIVE_FUNDS,
+		AssetCouldNotWithdrawFunds = ASSET_COULD_NOT_WITHDRAW_FUNDS,
+	}
+}
+
+/// The error a game returned for a failed transition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+	CasinoJam(CasinoJamError),
+	BattleMogs(BattleMogsError),
+	/// A code that isn't known to this version of the client.
+	Unknown {
+		game: SageGame,
+		code: u8,
+	},
+}
+
+impl GameError {
+	/// Names the error `code` returned by `game`.
+	pub fn from_code(game: SageGame, code: u8) -> Self {
+		let known = match game {
+			SageGame::CasinoJam => CasinoJamError::from_code(code).map(GameError::CasinoJam),
+			SageGame::BattleMogs => BattleMogsError::from_code(code).map(GameError::BattleMogs),
+		};
+		known.unwrap_or(GameError::Unknown { game, code })
+	}
+
+	/// Extracts the game error from the dispatch error of a failed transition.
+	///
+	/// Returns `None` if the transition failed before reaching the game, e.g. because the
+	/// signer doesn't own one of the assets, or if `error` doesn't stem from a SAGE pallet.
+	pub fn from_dispatch_error(error: &DispatchError) -> Option<Self> {
+		let DispatchError::Module(ModuleError { index, error, .. }) = error else {
+			return None;
+		};
+		let game = match usize::from(*index) {
+			index if index == SageCasinoJam::index() => SageGame::CasinoJam,
+			index if index == SageBattleMogs::index() => SageGame::BattleMogs,
+			_ => return None,
+		};
+
+		let [variant, code, ..] = *error;
+		(Some(variant) == transition_error_index(game)).then(|| Self::from_code(game, code))
+	}
+
+	/// The game that returned the error.
+	pub fn game(&self) -> SageGame {
+		match self {
+			GameError::CasinoJam(_) => SageGame::CasinoJam,
+			GameError::BattleMogs(_) => SageGame::BattleMogs,
+			GameError::Unknown { game, .. } => *game,
+		}
+	}
+}
+
+impl fmt::Display for GameError {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			GameError::CasinoJam(error) => write!(f, "CasinoJam: {error}"),
+			GameError::BattleMogs(error) => write!(f, "BattleMogs: {error}"),
+			GameError::Unknown { game, code } => write!(f, "{game:?}: unknown error ({code})"),
+		}
+	}
+}
+
+impl std::error::Error for GameError {}
+
+/// Index of the `pallet_sage` error variant carrying the `code` of a failed transition.
+///
+/// Taken from the type information of the pallet error, so that the client keeps working if
+/// variants are added to `pallet_sage`.
+fn transition_error_index(game: SageGame) -> Option<u8> {
+	let error_type = match game {
+		SageGame::CasinoJam =>
+			pallet_sage::Error::<Runtime, SageCasinoJamInstance>::type_info().type_def,
+		SageGame::BattleMogs =>
+			pallet_sage::Error::<Runtime, SageBattleMogsInstance>::type_info().type_def,
+	};
+	let TypeDef::Variant(error_type) = error_type else {
+		return None;
+	};
+
+	error_type
+		.variants
+		.iter()
+		.find(|variant| matches!(variant.fields.as_slice(), [field] if field.name == Some("code")))
+		.map(|variant| variant.index)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn codes_round_trip() {
+		for error in CasinoJamError::ALL {
+			assert_eq!(CasinoJamError::from_code(error.code()), Some(*error));
+		}
+		for error in BattleMogsError::ALL {
+			assert_eq!(BattleMogsError::from_code(error.code()), Some(*error));
+		}
+	}
+
+	#[test]
+	fn shared_casino_jam_code_is_named() {
+		use game_casino_jam::error::*;
+
+		assert_eq!(MACHINE_HAS_NO_LINKED_SEATS, MACHINE_STILL_HAS_LINKED_SEATS);
+		assert_eq!(
+			CasinoJamError::from_code(MACHINE_STILL_HAS_LINKED_SEATS),
+			Some(CasinoJamError::MachineLinkedSeats)
+		);
+	}
+
+	#[test]
+	fn unknown_codes_are_kept() {
+		assert_eq!(
+			GameError::from_code(SageGame::BattleMogs, u8::MAX),
+			GameError::Unknown { game: SageGame::BattleMogs, code: u8::MAX }
+		);
+	}
+
+	#[test]
+	fn decodes_module_errors_of_the_games() {
+		let variant = transition_error_index(SageGame::CasinoJam)
+			.expect("pallet_sage has a variant for transition errors");
+		let module_error = |index: usize, variant: u8, code: u8| {
+			DispatchError::Module(ModuleError {
+				index: index as u8,
+				error: [variant, code, 0, 0],
+				message: None,
+			})
+		};
+
+		assert_eq!(
+			GameError::from_dispatch_error(&module_error(
+				SageCasinoJam::index(),
+				variant,
+				game_casino_jam::error::SEAT_HAS_NO_FUNDS
+			)),
+			Some(GameError::CasinoJam(CasinoJamError::SeatHasNoFunds))
+		);
+		assert_eq!(
+			GameError::from_dispatch_error(&module_error(
+				SageBattleMogs::index(),
+				variant,
+				game_battle_mogs::error::MOGWAI_LIMIT_REACHED
+			)),
+			Some(GameError::BattleMogs(BattleMogsError::MogwaiLimitReached))
+		);
+		// Errors of other pallets or other variants are not game errors.
+		assert_eq!(GameError::from_dispatch_error(&module_error(0, variant, 1)), None);
+		assert_eq!(
+			GameError::from_dispatch_error(&module_error(
+				SageCasinoJam::index(),
+				variant.wrapping_add(1),
+				1
+			)),
+			None
+		);
+		assert_eq!(GameError::from_dispatch_error(&DispatchError::BadOrigin), None);
+	}
+}
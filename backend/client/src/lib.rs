@@ -0,0 +1,77 @@
+//! Typed client for the SAGE playground chain.
+//!
+//! Connects to a node over a WebSocket, builds and signs the `pallet_sage` transitions of
+//! CasinoJam and BattleMogs, reads their assets and follows their events, so that bots and
+//! backend services don't have to hand-build extrinsics.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), sage_client::Error> {
+//! use sage_client::{
+//! 	battle_mogs::BattleMogsAction,
+//! 	sp_core::{sr25519, Pair},
+//! 	SageClient,
+//! };
+//!
+//! let client = SageClient::connect("ws://127.0.0.1:9944").await?;
+//! let signer = sr25519::Pair::from_string("//Bob", None).expect("valid seed");
+//!
+//! let outcome = client
+//! 	.battle_mogs_transition(&signer, BattleMogsAction::RegisterPlayer, vec![], None)
+//! 	.await?;
+//! if let Some(error) = outcome.game_error() {
+//! 	println!("Registering failed: {error}");
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+mod client;
+mod error;
+mod events;
+mod extrinsic;
+mod game_error;
+
+pub use client::{AccountInfo, SageClient, TransitionOutcome};
+pub use error::Error;
+pub use events::{EventRecord, SageEvent};
+pub use extrinsic::{sign, SigningContext};
+pub use game_error::{BattleMogsError, CasinoJamError, GameError};
+
+pub use sage_playground_runtime::{
+	configs::{sage::metrics::GameMetrics, SageGame},
+	AccountId, Balance, BlockNumber, Hash, Nonce, RuntimeCall, RuntimeEvent, UncheckedExtrinsic,
+	UNIT,
+};
+pub use sp_core;
+
+/// Actions, assets and events of CasinoJam.
+pub mod casino_jam {
+	pub use game_casino_jam::{
+		asset::*,
+		prelude::{
+			AssetType, CasinoAction, CasinoJamEvent, MachineType, MultiplierType, PlayerType,
+			RentDuration, ReservationDuration, TokenType,
+		},
+	};
+	pub use sage_playground_runtime::configs::sage::casino_jam::CasinoJamAsset;
+}
+
+/// Actions, assets and events of BattleMogs.
+pub mod battle_mogs {
+	pub use game_battle_mogs::{
+		asset::{
+			achievement_table::*, item::*, mogwai::*, player::*, BattleMogsAsset, BattleMogsId,
+			BattleMogsVariant,
+		},
+		prelude::{BatchMode, BattleMogsEvent, BreedType, MogwaiBatch},
+		stats::Stats,
+		visuals::*,
+		BattleMogsAction,
+	};
+	pub use sage_playground_runtime::configs::sage::battle_mogs::BattleMogsAssetFor;
+}
+
+/// Asset paying the fees of a transition, both games accept the same kinds of payment.
+pub type PaymentAsset = <sage_playground_runtime::Runtime as pallet_sage::Config<
+	sage_playground_runtime::configs::sage::casino_jam::SageCasinoJamInstance,
+>>::FungiblesAssetId;
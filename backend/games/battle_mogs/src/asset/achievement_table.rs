@@ -17,6 +17,7 @@
 use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum AchievementState {
 	InProgress { current: u16, target: u16 },
 	Completed,
@@ -40,6 +41,10 @@ impl AchievementState {
 }
 
 impl AchievementState {
+	pub fn is_completed(&self) -> bool {
+		matches!(self, AchievementState::Completed)
+	}
+
 	pub fn new(target: u16) -> Self {
 		Self::InProgress { current: Default::default(), target }
 	}
@@ -60,7 +65,19 @@ impl AchievementState {
 	}
 }
 
+/// Identifies one of the achievements tracked by an [`AchievementTable`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Achievement {
+	EggHatcher,
+	Sacrificer,
+	Morpheus,
+	LegendBreeder,
+	Promiscuous,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct AchievementTable {
 	pub egg_hatcher: AchievementState,
 	pub sacrificer: AchievementState,
@@ -68,3 +85,28 @@ pub struct AchievementTable {
 	pub legend_breeder: AchievementState,
 	pub promiscuous: AchievementState,
 }
+
+impl AchievementTable {
+	pub fn completed_count(&self) -> u8 {
+		[
+			&self.egg_hatcher,
+			&self.sacrificer,
+			&self.morpheus,
+			&self.legend_breeder,
+			&self.promiscuous,
+		]
+		.into_iter()
+		.filter(|state| state.is_completed())
+		.count() as u8
+	}
+
+	pub fn state_mut(&mut self, achievement: Achievement) -> &mut AchievementState {
+		match achievement {
+			Achievement::EggHatcher => &mut self.egg_hatcher,
+			Achievement::Sacrificer => &mut self.sacrificer,
+			Achievement::Morpheus => &mut self.morpheus,
+			Achievement::LegendBreeder => &mut self.legend_breeder,
+			Achievement::Promiscuous => &mut self.promiscuous,
+		}
+	}
+}
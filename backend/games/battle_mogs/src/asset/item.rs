@@ -0,0 +1,76 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::{mogwai::RarityType, BattleMogsId};
+use crate::stats::Stats;
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+
+/// Slot an item takes on a mogwai, which holds at most one item of each kind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum ItemKind {
+	Weapon,
+	Armor,
+	Charm,
+}
+
+impl From<u8> for ItemKind {
+	fn from(num: u8) -> Self {
+		match num {
+			1 => ItemKind::Armor,
+			2 => ItemKind::Charm,
+			_ => ItemKind::Weapon,
+		}
+	}
+}
+
+/// Item crafted by consuming mogwais or other items or found on expeditions, raising the stats of
+/// the mogwai it is equipped on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Item {
+	pub kind: ItemKind,
+	pub rarity: RarityType,
+	pub bonus: Stats,
+	/// Mogwai of the owner this item is equipped on.
+	pub equipped_on: Option<BattleMogsId>,
+}
+
+impl Item {
+	/// Item of `kind` and `rarity`, its bonus rolled from `random_hash`.
+	pub fn craft(kind: ItemKind, rarity: RarityType, random_hash: &[u8; 32]) -> Self {
+		// Bonuses range from 10 to 19 for common items, up to 60 to 69 for mythical ones.
+		let roll = |index: usize| (rarity as u16 + 1) * 10 + u16::from(random_hash[index] % 10);
+		let bonus = match kind {
+			ItemKind::Weapon => Stats { attack: roll(0), speed: roll(1) / 2, ..Default::default() },
+			ItemKind::Armor =>
+				Stats { defense: roll(0), vitality: roll(1) / 2, ..Default::default() },
+			ItemKind::Charm => Stats {
+				attack: roll(0) / 2,
+				defense: roll(1) / 2,
+				vitality: roll(2) / 2,
+				speed: roll(3) / 2,
+			},
+		};
+
+		Self { kind, rarity, bonus, equipped_on: None }
+	}
+
+	pub fn is_equipped_on(&self, mogwai_id: &BattleMogsId) -> bool {
+		self.equipped_on == Some(*mogwai_id)
+	}
+}
@@ -21,21 +21,26 @@ use sage_api::{traits::GetId, TransitionError};
 use frame_support::pallet_prelude::*;
 
 pub mod achievement_table;
+pub mod item;
 pub mod mogwai;
+pub mod player;
 
 pub type BattleMogsId = u64;
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
-pub enum BattleMogsVariant {
-	Mogwai(mogwai::Mogwai),
-	AchievementTable(achievement_table::AchievementTable),
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum BattleMogsVariant<BlockNumber> {
+	Mogwai(mogwai::Mogwai<BlockNumber>),
+	Player(player::Player),
+	Item(item::Item),
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BattleMogsAsset<BlockNumber> {
 	pub id: BattleMogsId,
 	pub genesis: BlockNumber,
-	pub variant: BattleMogsVariant,
+	pub variant: BattleMogsVariant<BlockNumber>,
 }
 
 impl<BlockNumber> GetId<BattleMogsId> for BattleMogsAsset<BlockNumber> {
@@ -49,25 +54,32 @@ impl<BlockNumber> BattleMogsAsset<BlockNumber> {
 		matches!(self.variant, BattleMogsVariant::Mogwai(_))
 	}
 
-	pub fn is_achievement(&self) -> bool {
-		matches!(self.variant, BattleMogsVariant::AchievementTable(_))
+	pub fn is_player(&self) -> bool {
+		matches!(self.variant, BattleMogsVariant::Player(_))
 	}
 
-	pub fn as_mogwai(&mut self) -> Result<&mut mogwai::Mogwai, TransitionError> {
+	pub fn is_item(&self) -> bool {
+		matches!(self.variant, BattleMogsVariant::Item(_))
+	}
+
+	pub fn as_mogwai(&mut self) -> Result<&mut mogwai::Mogwai<BlockNumber>, TransitionError> {
 		match &mut self.variant {
 			BattleMogsVariant::Mogwai(mogwai) => Ok(mogwai),
-			BattleMogsVariant::AchievementTable(_) =>
-				Err(TransitionError::Transition { code: ASSET_IS_NOT_MOGWAI }),
+			_ => Err(TransitionError::Transition { code: ASSET_IS_NOT_MOGWAI }),
+		}
+	}
+
+	pub fn as_player(&mut self) -> Result<&mut player::Player, TransitionError> {
+		match &mut self.variant {
+			BattleMogsVariant::Player(player) => Ok(player),
+			_ => Err(TransitionError::Transition { code: ASSET_IS_NOT_PLAYER }),
 		}
 	}
 
-	pub fn as_achievement(
-		&mut self,
-	) -> Result<&mut achievement_table::AchievementTable, TransitionError> {
+	pub fn as_item(&mut self) -> Result<&mut item::Item, TransitionError> {
 		match &mut self.variant {
-			BattleMogsVariant::AchievementTable(achievement_table) => Ok(achievement_table),
-			BattleMogsVariant::Mogwai(_) =>
-				Err(TransitionError::Transition { code: ASSET_IS_NOT_ACHIEVEMENT_TABLE }),
+			BattleMogsVariant::Item(item) => Ok(item),
+			_ => Err(TransitionError::Transition { code: ASSET_IS_NOT_ITEM }),
 		}
 	}
 }
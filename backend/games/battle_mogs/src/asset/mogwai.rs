@@ -14,17 +14,129 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
+use super::BattleMogsId;
+
 use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
-pub struct Mogwai {
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Mogwai<BlockNumber> {
 	pub dna: [[u8; 32]; 2],
 	pub generation: MogwaiGeneration,
 	pub rarity: RarityType,
 	pub phase: PhaseType,
+	/// Offer of the owner to breed this mogwai with the ones of other accounts.
+	pub stud: Option<StudOffer<BlockNumber>>,
+	/// The mogwais this one was bred from, `None` for mogwais created from scratch.
+	pub parents: Option<[BattleMogsId; 2]>,
+	/// Number of generations of ancestors this mogwai has, `0` for created ones.
+	pub lineage_depth: u16,
+	/// Block this mogwai was last bred in, as either of the parents.
+	pub last_bred: Option<BlockNumber>,
+	/// Number of times this mogwai has been bred.
+	pub breed_count: u16,
+	/// Expedition this mogwai is away on, locking it until completed.
+	pub expedition: Option<Expedition<BlockNumber>>,
+	/// Last block this mogwai is recovering from an injury in.
+	pub injured_until: Option<BlockNumber>,
+	/// Player profile counting this mogwai, `None` for mogwais counted by their owner before
+	/// counts followed trades and transfers.
+	pub counted_by: Option<BattleMogsId>,
+}
+
+impl<BlockNumber> Mogwai<BlockNumber> {
+	pub fn has_parent(&self, mogwai_id: &BattleMogsId) -> bool {
+		self.parents.is_some_and(|parents| parents.contains(mogwai_id))
+	}
+
+	/// Whether this mogwai counts towards the mogwais of the player profile `player_id`,
+	/// provided the player owns it.
+	pub fn is_counted_by(&self, player_id: &BattleMogsId) -> bool {
+		self.counted_by.unwrap_or(*player_id) == *player_id
+	}
+
+	/// Whether both mogwais share at least one parent.
+	pub fn is_sibling_of(&self, other: &Self) -> bool {
+		match (self.parents, other.parents) {
+			(Some(parents), Some(other_parents)) =>
+				parents.iter().any(|parent| other_parents.contains(parent)),
+			_ => false,
+		}
+	}
+}
+
+impl<BlockNumber: PartialOrd> Mogwai<BlockNumber> {
+	pub fn is_injured_at(&self, block_number: &BlockNumber) -> bool {
+		self.injured_until.as_ref().is_some_and(|until| block_number <= until)
+	}
+}
+
+/// Price other accounts pay on top of the pairing price to breed with a mogwai, until the
+/// offer expires.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct StudOffer<BlockNumber> {
+	pub price: u128,
+	/// Last block the offer can be used in.
+	pub until: BlockNumber,
+}
+
+impl<BlockNumber: PartialOrd> StudOffer<BlockNumber> {
+	pub fn is_active_at(&self, block_number: &BlockNumber) -> bool {
+		*block_number <= self.until
+	}
+}
+
+/// Dungeon a mogwai explores while away on an [`Expedition`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Dungeon {
+	Cave = 1,
+	Forest = 2,
+	Ruins = 3,
+	Volcano = 4,
+}
+
+impl Dungeon {
+	/// Sum of stats a mogwai needs to clear the dungeon with an average roll.
+	pub fn difficulty(&self) -> u16 {
+		match self {
+			Dungeon::Cave => 400,
+			Dungeon::Forest => 480,
+			Dungeon::Ruins => 560,
+			Dungeon::Volcano => 640,
+		}
+	}
+
+	/// Rarity of the items found in the dungeon.
+	pub fn loot_rarity(&self) -> RarityType {
+		match self {
+			Dungeon::Cave => RarityType::Common,
+			Dungeon::Forest => RarityType::Uncommon,
+			Dungeon::Ruins => RarityType::Rare,
+			Dungeon::Volcano => RarityType::Epic,
+		}
+	}
+}
+
+/// Expedition of a mogwai into a dungeon, which can be completed from block `until` on.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Expedition<BlockNumber> {
+	pub dungeon: Dungeon,
+	pub until: BlockNumber,
+	/// Sum of the stats of the mogwai and its equipped items when it was sent.
+	pub power: u16,
+}
+
+impl<BlockNumber: PartialOrd> Expedition<BlockNumber> {
+	pub fn is_finished_at(&self, block_number: &BlockNumber) -> bool {
+		*block_number >= self.until
+	}
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum MogwaiGeneration {
 	#[default]
 	First = 1,
@@ -86,6 +198,7 @@ impl From<u16> for MogwaiGeneration {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum RarityType {
 	#[default]
 	Common = 0,
@@ -117,6 +230,7 @@ impl From<u16> for RarityType {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum PhaseType {
 	#[default]
 	None = 0,
@@ -0,0 +1,88 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{achievement_table::AchievementTable, BattleMogsId},
+	config::XP_PER_LEVEL,
+};
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+use sp_core::hashing::blake2_256;
+use sp_runtime::SaturatedConversion;
+
+/// Set on player ids to keep them apart from the ids SAGE hands out in sequence.
+const PLAYER_ID_FLAG: BattleMogsId = 1 << 63;
+
+/// Battles fought by the mogwais of a player.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct BattleRecord {
+	pub wins: u32,
+	pub losses: u32,
+}
+
+/// Profile of a registered player, minted once per account by `RegisterPlayer`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Player {
+	pub achievements: AchievementTable,
+	pub xp: u32,
+	pub level: u16,
+	/// Number of mogwais owned by the player, `None` until they are counted for players
+	/// upgraded from an achievement table.
+	pub mogwai_count: Option<u16>,
+	pub battles: BattleRecord,
+	/// Mogwai of the player shown on its profile.
+	pub showcase: Option<BattleMogsId>,
+}
+
+impl Player {
+	pub fn new(achievements: AchievementTable, mogwai_count: u16) -> Self {
+		Self {
+			achievements,
+			xp: 0,
+			level: Self::level_at(0),
+			mogwai_count: Some(mogwai_count),
+			battles: BattleRecord::default(),
+			showcase: None,
+		}
+	}
+
+	/// Id of the player profile of `account`, derived from the account so the profile is
+	/// fetched directly instead of searched for among its assets.
+	pub fn id_of(account: &impl Encode) -> BattleMogsId {
+		let hash = blake2_256(&account.encode());
+		let mut id = [0_u8; 8];
+		id.copy_from_slice(&hash[..8]);
+		BattleMogsId::from_le_bytes(id) | PLAYER_ID_FLAG
+	}
+
+	/// Level of a player with `xp` experience, starting at level 1.
+	pub fn level_at(xp: u32) -> u16 {
+		(xp / XP_PER_LEVEL).saturating_add(1).saturated_into()
+	}
+
+	/// Adds `xp` to the player, returning its new level if it leveled up.
+	pub fn gain_xp(&mut self, xp: u32) -> Option<u16> {
+		self.xp = self.xp.saturating_add(xp);
+
+		let level = Self::level_at(self.xp);
+		(level > self.level).then(|| {
+			self.level = level;
+			level
+		})
+	}
+}
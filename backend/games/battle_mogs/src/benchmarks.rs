@@ -39,6 +39,14 @@ where
 				generation: MogwaiGeneration::First,
 				rarity: RarityType::Common,
 				phase: PhaseType::Bred,
+				stud: None,
+				parents: None,
+				lineage_depth: 0,
+				last_bred: None,
+				breed_count: 0,
+				expedition: None,
+				injured_until: None,
+				counted_by: None,
 			}),
 		};
 
@@ -46,20 +54,22 @@ where
 	}
 
 	fn create_bench_transition() -> (BattleMogsAction, Vec<BattleMogsId>) {
-		(BattleMogsAction::CreateMogwai, Vec::with_capacity(0))
+		(BattleMogsAction::RegisterPlayer, Vec::with_capacity(0))
 	}
 
 	fn create_trade_filter_for(asset: &BattleMogsAsset<BlockNumber>) -> RarityType {
 		match asset.variant {
 			BattleMogsVariant::Mogwai(mogwai) => mogwai.rarity,
-			BattleMogsVariant::AchievementTable(_) => RarityType::Common,
+			BattleMogsVariant::Item(item) => item.rarity,
+			BattleMogsVariant::Player(_) => RarityType::Common,
 		}
 	}
 
 	fn create_transfer_filter_for(asset: &BattleMogsAsset<BlockNumber>) -> RarityType {
 		match asset.variant {
 			BattleMogsVariant::Mogwai(mogwai) => mogwai.rarity,
-			BattleMogsVariant::AchievementTable(_) => RarityType::Common,
+			BattleMogsVariant::Item(item) => item.rarity,
+			BattleMogsVariant::Player(_) => RarityType::Common,
 		}
 	}
 
@@ -28,6 +28,22 @@ use sp_std::marker::PhantomData;
 
 pub const MILLIARD: u64 = 1_000_000_000;
 
+/// Experience a player needs for each level.
+pub const XP_PER_LEVEL: u32 = 100;
+
+/// Experience players gain for their actions.
+pub struct Experience;
+impl Experience {
+	pub const HATCH: u32 = 10;
+	pub const MORPH: u32 = 10;
+	pub const BREED: u32 = 20;
+	pub const SACRIFICE: u32 = 5;
+	pub const CRAFT: u32 = 15;
+	pub const ACHIEVEMENT: u32 = 50;
+	/// Gained for each level of depth of a cleared dungeon.
+	pub const EXPEDITION: u32 = 15;
+}
+
 pub struct Pricing<Balance>(PhantomData<Balance>);
 impl<Balance> Pricing<Balance>
 where
@@ -65,16 +81,22 @@ where
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameEventType {
 	#[default]
 	Default = 0,
 	Hatch = 1,
+	Expedition = 2,
+	Recovery = 3,
 }
 
 impl GameEventType {
 	pub fn time_till(game_type: GameEventType) -> u16 {
 		match game_type {
 			GameEventType::Hatch => 100,
+			// Per level of depth of the dungeon.
+			GameEventType::Expedition => 50,
+			GameEventType::Recovery => 200,
 			GameEventType::Default => 0,
 		}
 	}
@@ -18,21 +18,51 @@ use sage_api::TransitionError;
 
 pub const ASSET_NOT_FOUND: u8 = 0;
 pub const MOGWAI_LIMIT_REACHED: u8 = 1;
-pub const PLAYER_ALREADY_HAS_ACHIEVEMENT_TABLE: u8 = 2;
+pub const PLAYER_ALREADY_REGISTERED: u8 = 2;
 pub const ASSET_IS_NOT_MOGWAI: u8 = 3;
-pub const ASSET_IS_NOT_ACHIEVEMENT_TABLE: u8 = 4;
+pub const ASSET_IS_NOT_PLAYER: u8 = 4;
 pub const CANNOT_USE_SAME_ASSET_FOR_BREEDING: u8 = 5;
 pub const MOGWAI_STILL_IN_BRED_PHASE: u8 = 6;
 pub const MOGWAI_NOT_IN_BRED_PHASE: u8 = 7;
 pub const MOGWAI_HAS_INVALID_RARITY: u8 = 8;
+pub const MOGWAI_NOT_OFFERED_AS_STUD: u8 = 9;
+pub const INVALID_STUD_OFFER: u8 = 10;
+pub const MOGWAI_HAS_NO_FUNDS: u8 = 11;
+pub const MOGWAIS_TOO_CLOSELY_RELATED: u8 = 12;
+pub const MOGWAI_ON_BREEDING_COOLDOWN: u8 = 13;
+pub const MOGWAI_BREED_LIMIT_REACHED: u8 = 14;
+pub const PLAYER_NOT_REGISTERED: u8 = 15;
+pub const ASSET_IS_NOT_ITEM: u8 = 16;
+pub const ITEM_ALREADY_EQUIPPED: u8 = 17;
+pub const ITEM_NOT_EQUIPPED: u8 = 18;
+pub const ITEM_SLOT_OCCUPIED: u8 = 19;
+pub const INVALID_CRAFTING_INGREDIENTS: u8 = 20;
+pub const MOGWAI_ON_EXPEDITION: u8 = 21;
+pub const MOGWAI_NOT_ON_EXPEDITION: u8 = 22;
+pub const EXPEDITION_NOT_FINISHED: u8 = 23;
+pub const MOGWAI_INJURED: u8 = 24;
+pub const INVALID_BATCH: u8 = 25;
 
 pub const ASSET_COULD_NOT_RECEIVE_FUNDS: u8 = 100;
 pub const ASSET_COULD_NOT_WITHDRAW_FUNDS: u8 = 101;
 
+/// Codes reporting the errors of SAGE itself, which carry no game code.
+pub const ASSET_NOT_OWNED: u8 = 200;
+pub const SAGE_ERROR: u8 = 201;
+
 pub(crate) struct BattleMogsError;
 
 impl BattleMogsError {
 	pub(crate) fn from(code: u8) -> TransitionError {
 		TransitionError::Transition { code }
 	}
+
+	/// Code of `error`, as reported by the game events.
+	pub(crate) fn code_of(error: &TransitionError) -> u8 {
+		match error {
+			TransitionError::Transition { code } => *code,
+			TransitionError::AssetOwnership => ASSET_NOT_OWNED,
+			_ => SAGE_ERROR,
+		}
+	}
 }
@@ -0,0 +1,91 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Events describing the outcome of BattleMogs transitions.
+//!
+//! Transitions only report which assets they minted, mutated or consumed. The events in here
+//! carry the game specific outcome on top of that, so that clients don't need to diff assets
+//! to find out what happened.
+
+use crate::{
+	asset::{
+		achievement_table::Achievement,
+		item::ItemKind,
+		mogwai::{Dungeon, MogwaiGeneration, RarityType},
+		BattleMogsId,
+	},
+	transitions::BreedType,
+};
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum BattleMogsEvent {
+	/// A mogwai hatched out of its egg with the given rarity.
+	MogwaiHatched { mogwai_id: BattleMogsId, rarity: RarityType },
+	/// A mogwai has been sacrificed, returning part of its funds to the owner.
+	MogwaiSacrificed { mogwai_id: BattleMogsId },
+	/// A mogwai has been sacrificed into another one, which jumped by `generation_jump`
+	/// generations up to `generation`.
+	MogwaiSacrificedInto {
+		mogwai_id: BattleMogsId,
+		into_id: BattleMogsId,
+		generation_jump: u16,
+		generation: MogwaiGeneration,
+	},
+	/// A mogwai had its DNA morphed.
+	MogwaiMorphed { mogwai_id: BattleMogsId, breed_type: BreedType },
+	/// Two mogwais have been bred into a new egg.
+	MogwaiBred {
+		mogwai_id_1: BattleMogsId,
+		mogwai_id_2: BattleMogsId,
+		offspring_id: BattleMogsId,
+		breed_type: BreedType,
+		generation: MogwaiGeneration,
+		rarity: RarityType,
+	},
+	/// A mogwai has been offered to other accounts for breeding, at `price` on top of the
+	/// pairing price.
+	StudOffered { mogwai_id: BattleMogsId, price: u128 },
+	/// The stud offer of a mogwai has been revoked.
+	StudRevoked { mogwai_id: BattleMogsId },
+	/// The funds a mogwai accumulated from breeding have been withdrawn to its owner.
+	StudFeesClaimed { mogwai_id: BattleMogsId, amount: u128 },
+	/// An achievement of the player has been completed.
+	AchievementCompleted { player_id: BattleMogsId, achievement: Achievement },
+	/// The player reached a new level.
+	PlayerLeveledUp { player_id: BattleMogsId, level: u16 },
+	/// An item has been crafted out of the consumed ingredients.
+	ItemCrafted { item_id: BattleMogsId, kind: ItemKind, rarity: RarityType },
+	/// An item has been equipped on a mogwai.
+	ItemEquipped { item_id: BattleMogsId, mogwai_id: BattleMogsId },
+	/// An item has been taken off the mogwai it was equipped on.
+	ItemUnequipped { item_id: BattleMogsId, mogwai_id: BattleMogsId },
+	/// A mogwai has been sent on an expedition into a dungeon.
+	ExpeditionSent { mogwai_id: BattleMogsId, dungeon: Dungeon },
+	/// A mogwai returned from its expedition, having found `loot` if it cleared the dungeon
+	/// or possibly being injured otherwise.
+	ExpeditionCompleted {
+		mogwai_id: BattleMogsId,
+		dungeon: Dungeon,
+		cleared: bool,
+		loot: Option<BattleMogsId>,
+		injured: bool,
+	},
+	/// A batch transition skipped a mogwai it could not act on, failing with the error `code`.
+	MogwaiSkipped { mogwai_id: BattleMogsId, code: u8 },
+}
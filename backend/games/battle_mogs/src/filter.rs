@@ -17,7 +17,9 @@ where
 
 	fn can_be_traded_using(asset: &Self::Asset, filter: &Self::TradeFilter) -> bool {
 		match asset.variant {
-			BattleMogsVariant::Mogwai(mogwai) => mogwai.rarity == *filter,
+			BattleMogsVariant::Mogwai(mogwai) =>
+				mogwai.expedition.is_none() && mogwai.rarity == *filter,
+			BattleMogsVariant::Item(item) => item.equipped_on.is_none() && item.rarity == *filter,
 			_ => false,
 		}
 	}
@@ -32,7 +34,9 @@ where
 
 	fn can_be_transferred_using(asset: &Self::Asset, filter: &Self::TransferFilter) -> bool {
 		match asset.variant {
-			BattleMogsVariant::Mogwai(mogwai) => mogwai.rarity == *filter,
+			BattleMogsVariant::Mogwai(mogwai) =>
+				mogwai.expedition.is_none() && mogwai.rarity == *filter,
+			BattleMogsVariant::Item(item) => item.equipped_on.is_none() && item.rarity == *filter,
 			_ => false,
 		}
 	}
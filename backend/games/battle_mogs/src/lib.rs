@@ -17,11 +17,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use crate::{
-	asset::{BattleMogsAsset, BattleMogsId},
-	transitions::BattleMogsTransitionConfig,
+	asset::{mogwai::Dungeon, BattleMogsAsset, BattleMogsId},
+	events::BattleMogsEvent,
+	transitions::{
+		BatchMode, BattleMogsTransitionConfig, MogwaiBatch, PaymentFungibles, CRAFTING_INGREDIENTS,
+	},
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, SageGameTransition, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -35,40 +39,64 @@ pub mod asset;
 mod benchmarks;
 pub mod config;
 pub mod error;
+pub mod events;
 pub mod filter;
+pub mod lineage;
+pub mod migration;
+pub mod stats;
 pub mod transitions;
+pub mod visuals;
 
 pub mod prelude {
 	pub use crate::{
 		asset::{
-			achievement_table::*, mogwai::*, BattleMogsAsset, BattleMogsId, BattleMogsVariant,
+			achievement_table::*, item::*, mogwai::*, player::*, BattleMogsAsset, BattleMogsId,
+			BattleMogsVariant,
 		},
 		benchmarks::GameBenchmarkHelper,
 		error::*,
+		events::BattleMogsEvent,
 		filter::GameFilter,
-		transitions::BattleMogsTransitionConfig,
+		transitions::{
+			BatchMode, BattleMogsTransitionConfig, BreedType, FertilityRules, FundsDestination,
+			InbreedingRules, MogwaiBatch, PaymentFungibleList, PaymentFungibles,
+			CRAFTING_INGREDIENTS, MAX_BATCH_SIZE, MAX_PAYMENT_FUNGIBLES,
+		},
 		BattleMogsTransition,
 	};
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum BattleMogsAction {
 	RegisterPlayer,
 	CreateMogwai,
 	Remove { mogwai: BattleMogsId },
-	Hatch { mogwai: BattleMogsId, table: BattleMogsId },
-	Sacrifice { mogwai: BattleMogsId, table: BattleMogsId },
-	SacrificeInto { mogwai: BattleMogsId, into: BattleMogsId, table: BattleMogsId },
-	Morph { mogwai: BattleMogsId, table: BattleMogsId },
-	Breed { mogwai_1: BattleMogsId, mogwai_2: BattleMogsId, table: BattleMogsId },
+	Hatch { mogwai: BattleMogsId },
+	Sacrifice { mogwai: BattleMogsId },
+	SacrificeInto { mogwai: BattleMogsId, into: BattleMogsId },
+	Morph { mogwai: BattleMogsId },
+	Breed { mogwai_1: BattleMogsId, mogwai_2: BattleMogsId },
+	OfferStud { mogwai: BattleMogsId, price: u128, duration: u32 },
+	RevokeStud { mogwai: BattleMogsId },
+	ClaimStudFees { mogwai: BattleMogsId },
+	Showcase { mogwai: Option<BattleMogsId> },
+	Craft { ingredients: [BattleMogsId; CRAFTING_INGREDIENTS] },
+	Equip { item: BattleMogsId, mogwai: BattleMogsId },
+	Unequip { item: BattleMogsId },
+	SendExpedition { mogwai: BattleMogsId, dungeon: Dungeon },
+	CompleteExpedition { mogwai: BattleMogsId },
+	HatchMany { mogwais: MogwaiBatch, mode: BatchMode },
+	SacrificeMany { mogwais: MogwaiBatch, mode: BatchMode },
+	RemoveMany { mogwais: MogwaiBatch, mode: BatchMode },
 }
 
-pub struct BattleMogsTransition<AccountId, BlockNumber, Sage> {
-	_phantom: PhantomData<(AccountId, BlockNumber, Sage)>,
+pub struct BattleMogsTransition<AccountId, BlockNumber, Sage, Events = (), Fungibles = ()> {
+	_phantom: PhantomData<(AccountId, BlockNumber, Sage, Events, Fungibles)>,
 }
 
-impl<AccountId, BlockNumber, Balance, Sage> SageGameTransition
-	for BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles> SageGameTransition
+	for BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -82,6 +110,8 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	type TransitionId = BattleMogsAction;
 	type TransitionConfig = BattleMogsTransitionConfig;
@@ -98,20 +128,43 @@ where
 		_: &Self::Extra,
 		payment_asset: Option<Self::PaymentFungible>,
 	) -> Result<Vec<TransitionOutput<Self::AssetId, Self::Asset>>, TransitionError> {
-		match transition_id {
+		let mut output = match transition_id {
 			BattleMogsAction::RegisterPlayer => Self::register_player(account_id),
 			BattleMogsAction::CreateMogwai => Self::create_mogwai(account_id),
-			BattleMogsAction::Remove { mogwai } => Self::remove_mogwai(account_id, mogwai),
-			BattleMogsAction::Hatch { mogwai, table } =>
-				Self::hatch_mogwai(account_id, mogwai, table),
-			BattleMogsAction::Sacrifice { mogwai, table } =>
-				Self::sacrifice_mogwai(account_id, mogwai, table, payment_asset),
-			BattleMogsAction::SacrificeInto { mogwai, into, table } =>
-				Self::sacrifice_mogwai_into(account_id, mogwai, into, table, payment_asset),
-			BattleMogsAction::Morph { mogwai, table } =>
-				Self::morph_mogwai(account_id, mogwai, table, payment_asset),
-			BattleMogsAction::Breed { mogwai_1, mogwai_2, table } =>
-				Self::breed_mogwais(account_id, mogwai_1, mogwai_2, table, payment_asset),
-		}
+			BattleMogsAction::Remove { mogwai } =>
+				Self::remove_mogwai(account_id, mogwai, payment_asset),
+			BattleMogsAction::Hatch { mogwai } => Self::hatch_mogwai(account_id, mogwai),
+			BattleMogsAction::Sacrifice { mogwai } =>
+				Self::sacrifice_mogwai(account_id, mogwai, payment_asset),
+			BattleMogsAction::SacrificeInto { mogwai, into } =>
+				Self::sacrifice_mogwai_into(account_id, mogwai, into, payment_asset),
+			BattleMogsAction::Morph { mogwai } =>
+				Self::morph_mogwai(account_id, mogwai, payment_asset),
+			BattleMogsAction::Breed { mogwai_1, mogwai_2 } =>
+				Self::breed_mogwais(account_id, mogwai_1, mogwai_2, payment_asset),
+			BattleMogsAction::OfferStud { mogwai, price, duration } =>
+				Self::offer_stud(account_id, mogwai, *price, *duration),
+			BattleMogsAction::RevokeStud { mogwai } => Self::revoke_stud(account_id, mogwai),
+			BattleMogsAction::ClaimStudFees { mogwai } =>
+				Self::claim_stud_fees(account_id, mogwai, payment_asset),
+			BattleMogsAction::Showcase { mogwai } => Self::showcase_mogwai(account_id, mogwai),
+			BattleMogsAction::Craft { ingredients } =>
+				Self::craft_item(account_id, ingredients, payment_asset),
+			BattleMogsAction::Equip { item, mogwai } => Self::equip_item(account_id, item, mogwai),
+			BattleMogsAction::Unequip { item } => Self::unequip_item(account_id, item),
+			BattleMogsAction::SendExpedition { mogwai, dungeon } =>
+				Self::send_expedition(account_id, mogwai, dungeon),
+			BattleMogsAction::CompleteExpedition { mogwai } =>
+				Self::complete_expedition(account_id, mogwai),
+			BattleMogsAction::HatchMany { mogwais, mode } =>
+				Self::hatch_mogwais(account_id, mogwais, mode),
+			BattleMogsAction::SacrificeMany { mogwais, mode } =>
+				Self::sacrifice_mogwais(account_id, mogwais, mode, payment_asset),
+			BattleMogsAction::RemoveMany { mogwais, mode } =>
+				Self::remove_mogwais(account_id, mogwais, mode, payment_asset),
+		}?;
+
+		Self::count_traded_mogwais(account_id, &mut output)?;
+		Ok(output)
 	}
 }
@@ -0,0 +1,83 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Family trees of bred mogwais.
+//!
+//! Every bred mogwai records the ids of its parents, so its ancestry can be walked through
+//! the assets stored by SAGE for as long as the ancestors have not been consumed.
+
+use crate::asset::{mogwai::Mogwai, BattleMogsAsset, BattleMogsId, BattleMogsVariant};
+
+use ajuna_primitives::sage_api::SageApi;
+use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+/// An ancestor of a mogwai, as found by [`ancestors`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Ancestor<BlockNumber> {
+	pub id: BattleMogsId,
+	/// Generations between the mogwai and this ancestor, `1` for its parents.
+	pub distance: u16,
+	/// The ancestor, or `None` if it has been consumed in the meantime.
+	pub mogwai: Option<Mogwai<BlockNumber>>,
+}
+
+/// Walks the ancestry of `mogwai_id` through [`SageApi::get_asset`], up to `max_distance`
+/// generations back.
+///
+/// Ancestors are returned closest first, and only once even if they appear on both sides of
+/// the family tree. The walk stops at consumed ancestors, whose parents are unknown.
+pub fn ancestors<Sage, BlockNumber>(
+	mogwai_id: &BattleMogsId,
+	max_distance: u16,
+) -> Vec<Ancestor<BlockNumber>>
+where
+	Sage: SageApi<AssetId = BattleMogsId, Asset = BattleMogsAsset<BlockNumber>>,
+{
+	let mut ancestors = Vec::new();
+	let mut visited = BTreeSet::new();
+	let mut generation = mogwai_of::<Sage, BlockNumber>(mogwai_id)
+		.and_then(|mogwai| mogwai.parents)
+		.map(Vec::from)
+		.unwrap_or_default();
+
+	for distance in 1..=max_distance {
+		let mut next_generation = Vec::new();
+		for id in generation.into_iter().filter(|id| visited.insert(*id)) {
+			let mogwai = mogwai_of::<Sage, BlockNumber>(&id);
+			if let Some(parents) = mogwai.as_ref().and_then(|mogwai| mogwai.parents) {
+				next_generation.extend(parents);
+			}
+			ancestors.push(Ancestor { id, distance, mogwai });
+		}
+
+		if next_generation.is_empty() {
+			break;
+		}
+		generation = next_generation;
+	}
+
+	ancestors
+}
+
+fn mogwai_of<Sage, BlockNumber>(mogwai_id: &BattleMogsId) -> Option<Mogwai<BlockNumber>>
+where
+	Sage: SageApi<AssetId = BattleMogsId, Asset = BattleMogsAsset<BlockNumber>>,
+{
+	match Sage::get_asset(mogwai_id).ok()?.variant {
+		BattleMogsVariant::Mogwai(mogwai) => Some(mogwai),
+		_ => None,
+	}
+}
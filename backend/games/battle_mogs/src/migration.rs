@@ -0,0 +1,709 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Versioned asset layouts of BattleMogs.
+//!
+//! Assets are SCALE encoded into storage as is, so every change to their layout gets a new
+//! version here, together with a module holding the replaced definitions and the conversion
+//! into the next layout. This way assets written with any previous layout can be upgraded
+//! to the current one.
+
+use crate::asset::{mogwai::Mogwai, BattleMogsAsset, BattleMogsVariant};
+
+use parity_scale_codec::{Decode, DecodeAll};
+
+/// Layout version of the assets as currently defined by this crate.
+pub const ASSET_LAYOUT_VERSION: u16 = 7;
+
+/// Decodes `encoded` as an asset of the given layout `version` and upgrades it to the
+/// current layout.
+///
+/// Returns `None` if the version is unknown or the bytes do not match its layout.
+pub fn upgrade_asset_from<BlockNumber: Decode>(
+	version: u16,
+	encoded: &[u8],
+) -> Option<BattleMogsAsset<BlockNumber>> {
+	let input = &mut &encoded[..];
+	match version {
+		0 => v0::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v1::BattleMogsAsset::from)
+			.map(v2::BattleMogsAsset::from)
+			.map(v3::BattleMogsAsset::from)
+			.map(v4::BattleMogsAsset::from)
+			.map(v5::BattleMogsAsset::from)
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		1 => v1::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v2::BattleMogsAsset::from)
+			.map(v3::BattleMogsAsset::from)
+			.map(v4::BattleMogsAsset::from)
+			.map(v5::BattleMogsAsset::from)
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		2 => v2::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v3::BattleMogsAsset::from)
+			.map(v4::BattleMogsAsset::from)
+			.map(v5::BattleMogsAsset::from)
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		3 => v3::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v4::BattleMogsAsset::from)
+			.map(v5::BattleMogsAsset::from)
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		4 => v4::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v5::BattleMogsAsset::from)
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		5 => v5::BattleMogsAsset::<BlockNumber>::decode_all(input)
+			.ok()
+			.map(v6::BattleMogsAsset::from)
+			.map(Into::into),
+		6 => v6::BattleMogsAsset::<BlockNumber>::decode_all(input).ok().map(Into::into),
+		ASSET_LAYOUT_VERSION => BattleMogsAsset::<BlockNumber>::decode_all(input).ok(),
+		_ => None,
+	}
+}
+
+/// Layout before mogwais could be offered as studs.
+pub mod v0 {
+	use super::v3::AchievementTable;
+	use crate::asset::{
+		mogwai::{MogwaiGeneration, PhaseType, RarityType},
+		BattleMogsId,
+	};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant {
+		Mogwai(Mogwai),
+		AchievementTable(AchievementTable),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Mogwai {
+		pub dna: [[u8; 32]; 2],
+		pub generation: MogwaiGeneration,
+		pub rarity: RarityType,
+		pub phase: PhaseType,
+	}
+}
+
+/// Layout before mogwais recorded their parents.
+pub mod v1 {
+	use super::{v0, v3::AchievementTable};
+	use crate::asset::{
+		mogwai::{MogwaiGeneration, PhaseType, RarityType, StudOffer},
+		BattleMogsId,
+	};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		AchievementTable(AchievementTable),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Mogwai<BlockNumber> {
+		pub dna: [[u8; 32]; 2],
+		pub generation: MogwaiGeneration,
+		pub rarity: RarityType,
+		pub phase: PhaseType,
+		pub stud: Option<StudOffer<BlockNumber>>,
+	}
+
+	impl<BlockNumber> From<v0::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v0::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				v0::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(Mogwai {
+					dna: mogwai.dna,
+					generation: mogwai.generation,
+					rarity: mogwai.rarity,
+					phase: mogwai.phase,
+					stud: None,
+				}),
+				v0::BattleMogsVariant::AchievementTable(table) =>
+					BattleMogsVariant::AchievementTable(table),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+/// Layout before breeding was limited by cooldowns and a lifetime breed count.
+pub mod v2 {
+	use super::{v1, v3::AchievementTable};
+	use crate::asset::{
+		mogwai::{MogwaiGeneration, PhaseType, RarityType, StudOffer},
+		BattleMogsId,
+	};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		AchievementTable(AchievementTable),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Mogwai<BlockNumber> {
+		pub dna: [[u8; 32]; 2],
+		pub generation: MogwaiGeneration,
+		pub rarity: RarityType,
+		pub phase: PhaseType,
+		pub stud: Option<StudOffer<BlockNumber>>,
+		pub parents: Option<[BattleMogsId; 2]>,
+		pub lineage_depth: u16,
+	}
+
+	impl<BlockNumber> From<v1::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v1::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				// Mogwais bred before lineages were tracked start a lineage of their own.
+				v1::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(Mogwai {
+					dna: mogwai.dna,
+					generation: mogwai.generation,
+					rarity: mogwai.rarity,
+					phase: mogwai.phase,
+					stud: mogwai.stud,
+					parents: None,
+					lineage_depth: 0,
+				}),
+				v1::BattleMogsVariant::AchievementTable(table) =>
+					BattleMogsVariant::AchievementTable(table),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+/// Layout before achievement tables counted the mogwais of their player.
+pub mod v3 {
+	use super::{v2, v5::Mogwai};
+	use crate::asset::{achievement_table::AchievementState, BattleMogsId};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		AchievementTable(AchievementTable),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct AchievementTable {
+		pub egg_hatcher: AchievementState,
+		pub sacrificer: AchievementState,
+		pub morpheus: AchievementState,
+		pub legend_breeder: AchievementState,
+		pub promiscuous: AchievementState,
+	}
+
+	impl<BlockNumber> From<v2::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v2::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				// Previous breeds are unknown, so mogwais start out fully rested and fertile.
+				v2::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(Mogwai {
+					dna: mogwai.dna,
+					generation: mogwai.generation,
+					rarity: mogwai.rarity,
+					phase: mogwai.phase,
+					stud: mogwai.stud,
+					parents: mogwai.parents,
+					lineage_depth: mogwai.lineage_depth,
+					last_bred: None,
+					breed_count: 0,
+				}),
+				v2::BattleMogsVariant::AchievementTable(table) =>
+					BattleMogsVariant::AchievementTable(table),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+/// Layout before players had a profile of their own.
+pub mod v4 {
+	use super::{v3, v5::Mogwai};
+	use crate::asset::{achievement_table::AchievementState, BattleMogsId};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		AchievementTable(AchievementTable),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct AchievementTable {
+		pub egg_hatcher: AchievementState,
+		pub sacrificer: AchievementState,
+		pub morpheus: AchievementState,
+		pub legend_breeder: AchievementState,
+		pub promiscuous: AchievementState,
+		pub mogwai_count: Option<u16>,
+	}
+
+	impl<BlockNumber> From<v3::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v3::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				v3::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(mogwai),
+				// Mogwais of the player are counted once its table is used.
+				v3::BattleMogsVariant::AchievementTable(table) =>
+					BattleMogsVariant::AchievementTable(AchievementTable {
+						egg_hatcher: table.egg_hatcher,
+						sacrificer: table.sacrificer,
+						morpheus: table.morpheus,
+						legend_breeder: table.legend_breeder,
+						promiscuous: table.promiscuous,
+						mogwai_count: None,
+					}),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+/// Layout before mogwais could go on expeditions.
+pub mod v5 {
+	use super::v4;
+	use crate::asset::{
+		achievement_table::AchievementTable,
+		item::Item,
+		mogwai::{MogwaiGeneration, PhaseType, RarityType, StudOffer},
+		player::{BattleRecord, Player},
+		BattleMogsId,
+	};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		Player(Player),
+		Item(Item),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Mogwai<BlockNumber> {
+		pub dna: [[u8; 32]; 2],
+		pub generation: MogwaiGeneration,
+		pub rarity: RarityType,
+		pub phase: PhaseType,
+		pub stud: Option<StudOffer<BlockNumber>>,
+		pub parents: Option<[BattleMogsId; 2]>,
+		pub lineage_depth: u16,
+		pub last_bred: Option<BlockNumber>,
+		pub breed_count: u16,
+	}
+
+	impl<BlockNumber> From<v4::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v4::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				v4::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(mogwai),
+				// Achievement tables become the profile of their player, which starts without
+				// experience as it was not gained before.
+				v4::BattleMogsVariant::AchievementTable(table) =>
+					BattleMogsVariant::Player(Player {
+						achievements: AchievementTable {
+							egg_hatcher: table.egg_hatcher,
+							sacrificer: table.sacrificer,
+							morpheus: table.morpheus,
+							legend_breeder: table.legend_breeder,
+							promiscuous: table.promiscuous,
+						},
+						xp: 0,
+						level: Player::level_at(0),
+						mogwai_count: table.mogwai_count,
+						battles: BattleRecord::default(),
+						showcase: None,
+					}),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+/// Layout before mogwais recorded the player profile counting them.
+pub mod v6 {
+	use super::v5;
+	use crate::asset::{
+		item::Item,
+		mogwai::{Expedition, MogwaiGeneration, PhaseType, RarityType, StudOffer},
+		player::Player,
+		BattleMogsId,
+	};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct BattleMogsAsset<BlockNumber> {
+		pub id: BattleMogsId,
+		pub genesis: BlockNumber,
+		pub variant: BattleMogsVariant<BlockNumber>,
+	}
+
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum BattleMogsVariant<BlockNumber> {
+		Mogwai(Mogwai<BlockNumber>),
+		Player(Player),
+		Item(Item),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Mogwai<BlockNumber> {
+		pub dna: [[u8; 32]; 2],
+		pub generation: MogwaiGeneration,
+		pub rarity: RarityType,
+		pub phase: PhaseType,
+		pub stud: Option<StudOffer<BlockNumber>>,
+		pub parents: Option<[BattleMogsId; 2]>,
+		pub lineage_depth: u16,
+		pub last_bred: Option<BlockNumber>,
+		pub breed_count: u16,
+		pub expedition: Option<Expedition<BlockNumber>>,
+		pub injured_until: Option<BlockNumber>,
+	}
+
+	impl<BlockNumber> From<v5::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+		fn from(asset: v5::BattleMogsAsset<BlockNumber>) -> Self {
+			let variant = match asset.variant {
+				v5::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(Mogwai {
+					dna: mogwai.dna,
+					generation: mogwai.generation,
+					rarity: mogwai.rarity,
+					phase: mogwai.phase,
+					stud: mogwai.stud,
+					parents: mogwai.parents,
+					lineage_depth: mogwai.lineage_depth,
+					last_bred: mogwai.last_bred,
+					breed_count: mogwai.breed_count,
+					expedition: None,
+					injured_until: None,
+				}),
+				v5::BattleMogsVariant::Player(player) => BattleMogsVariant::Player(player),
+				v5::BattleMogsVariant::Item(item) => BattleMogsVariant::Item(item),
+			};
+
+			BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+		}
+	}
+}
+
+impl<BlockNumber> From<v6::BattleMogsAsset<BlockNumber>> for BattleMogsAsset<BlockNumber> {
+	fn from(asset: v6::BattleMogsAsset<BlockNumber>) -> Self {
+		let variant = match asset.variant {
+			// Mogwais from before stay counted by whichever player owns them.
+			v6::BattleMogsVariant::Mogwai(mogwai) => BattleMogsVariant::Mogwai(Mogwai {
+				dna: mogwai.dna,
+				generation: mogwai.generation,
+				rarity: mogwai.rarity,
+				phase: mogwai.phase,
+				stud: mogwai.stud,
+				parents: mogwai.parents,
+				lineage_depth: mogwai.lineage_depth,
+				last_bred: mogwai.last_bred,
+				breed_count: mogwai.breed_count,
+				expedition: mogwai.expedition,
+				injured_until: mogwai.injured_until,
+				counted_by: None,
+			}),
+			v6::BattleMogsVariant::Player(player) => BattleMogsVariant::Player(player),
+			v6::BattleMogsVariant::Item(item) => BattleMogsVariant::Item(item),
+		};
+
+		BattleMogsAsset { id: asset.id, genesis: asset.genesis, variant }
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::asset::{
+		achievement_table::{AchievementState, AchievementTable},
+		item::{Item, ItemKind},
+		mogwai::{Dungeon, Expedition, MogwaiGeneration, PhaseType, RarityType, StudOffer},
+		player::Player,
+	};
+	use parity_scale_codec::Encode;
+
+	fn v0_mogwai() -> v0::BattleMogsAsset<u32> {
+		v0::BattleMogsAsset {
+			id: 1,
+			genesis: 10,
+			variant: v0::BattleMogsVariant::Mogwai(v0::Mogwai {
+				dna: [[1; 32], [2; 32]],
+				generation: MogwaiGeneration::Third,
+				rarity: RarityType::Rare,
+				phase: PhaseType::Hatched,
+			}),
+		}
+	}
+
+	fn mogwai(stud: Option<StudOffer<u32>>) -> BattleMogsAsset<u32> {
+		BattleMogsAsset {
+			id: 1,
+			genesis: 10,
+			variant: BattleMogsVariant::Mogwai(Mogwai {
+				dna: [[1; 32], [2; 32]],
+				generation: MogwaiGeneration::Third,
+				rarity: RarityType::Rare,
+				phase: PhaseType::Hatched,
+				stud,
+				parents: None,
+				lineage_depth: 0,
+				last_bred: None,
+				breed_count: 0,
+				expedition: None,
+				injured_until: None,
+				counted_by: None,
+			}),
+		}
+	}
+
+	fn player(mogwai_count: Option<u16>) -> BattleMogsAsset<u32> {
+		let achievements = AchievementTable {
+			egg_hatcher: AchievementState::new(1),
+			sacrificer: AchievementState::new(2),
+			morpheus: AchievementState::new(3),
+			legend_breeder: AchievementState::new(4),
+			promiscuous: AchievementState::Completed,
+		};
+		let mut player = Player::new(achievements, 0);
+		player.mogwai_count = mogwai_count;
+		BattleMogsAsset { id: 2, genesis: 10, variant: BattleMogsVariant::Player(player) }
+	}
+
+	#[test]
+	fn upgrades_v0_mogwai() {
+		assert_eq!(upgrade_asset_from::<u32>(0, &v0_mogwai().encode()), Some(mogwai(None)));
+		// A v0 mogwai is too short to be mistaken for a later layout.
+		assert_eq!(upgrade_asset_from::<u32>(1, &v0_mogwai().encode()), None);
+		assert_eq!(upgrade_asset_from::<u32>(2, &v0_mogwai().encode()), None);
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &v0_mogwai().encode()), None);
+	}
+
+	#[test]
+	fn upgrades_v1_mogwai_keeping_its_stud_offer() {
+		let stud = Some(StudOffer { price: 5, until: 20 });
+		let mut old = v1::BattleMogsAsset::from(v0_mogwai());
+		if let v1::BattleMogsVariant::Mogwai(mogwai) = &mut old.variant {
+			mogwai.stud = stud;
+		}
+
+		assert_eq!(upgrade_asset_from::<u32>(1, &old.encode()), Some(mogwai(stud)));
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &old.encode()), None);
+	}
+
+	#[test]
+	fn upgrades_v2_mogwai_keeping_its_lineage() {
+		let mut old = v2::BattleMogsAsset::from(v1::BattleMogsAsset::from(v0_mogwai()));
+		if let v2::BattleMogsVariant::Mogwai(mogwai) = &mut old.variant {
+			mogwai.parents = Some([3, 4]);
+			mogwai.lineage_depth = 2;
+		}
+
+		let mut expected = mogwai(None);
+		let mogwai = expected.as_mogwai().unwrap();
+		mogwai.parents = Some([3, 4]);
+		mogwai.lineage_depth = 2;
+
+		assert_eq!(upgrade_asset_from::<u32>(2, &old.encode()), Some(expected));
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &old.encode()), None);
+	}
+
+	#[test]
+	fn upgrades_achievement_tables_into_players() {
+		let v3_table = v3::BattleMogsAsset::<u32> {
+			id: 2,
+			genesis: 10,
+			variant: v3::BattleMogsVariant::AchievementTable(v3::AchievementTable {
+				egg_hatcher: AchievementState::new(1),
+				sacrificer: AchievementState::new(2),
+				morpheus: AchievementState::new(3),
+				legend_breeder: AchievementState::new(4),
+				promiscuous: AchievementState::Completed,
+			}),
+		};
+		for version in 0..=3 {
+			assert_eq!(upgrade_asset_from::<u32>(version, &v3_table.encode()), Some(player(None)));
+		}
+		assert_eq!(upgrade_asset_from::<u32>(4, &v3_table.encode()), None);
+
+		// Tables that counted their mogwais keep the count.
+		let mut v4_table = v4::BattleMogsAsset::from(v3_table);
+		if let v4::BattleMogsVariant::AchievementTable(table) = &mut v4_table.variant {
+			table.mogwai_count = Some(3);
+		}
+		assert_eq!(upgrade_asset_from::<u32>(4, &v4_table.encode()), Some(player(Some(3))));
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &v4_table.encode()), None);
+	}
+
+	#[test]
+	fn current_players_keep_their_encoding() {
+		let mut player = player(Some(3));
+		let profile = player.as_player().unwrap();
+		profile.gain_xp(250);
+		profile.battles.wins = 2;
+		profile.showcase = Some(1);
+		for version in [5, 6, ASSET_LAYOUT_VERSION] {
+			assert_eq!(upgrade_asset_from::<u32>(version, &player.encode()), Some(player.clone()));
+		}
+	}
+
+	#[test]
+	fn items_keep_their_encoding() {
+		let mut item = Item::craft(ItemKind::Armor, RarityType::Epic, &[7; 32]);
+		item.equipped_on = Some(1);
+		let item = BattleMogsAsset { id: 3, genesis: 10, variant: BattleMogsVariant::Item(item) };
+		for version in [5, 6, ASSET_LAYOUT_VERSION] {
+			assert_eq!(upgrade_asset_from::<u32>(version, &item.encode()), Some(item.clone()));
+		}
+	}
+
+	#[test]
+	fn upgrades_v5_mogwai_keeping_its_breeds() {
+		let stud = Some(StudOffer { price: 5, until: 20 });
+		let old = v5::BattleMogsAsset::<u32> {
+			id: 1,
+			genesis: 10,
+			variant: v5::BattleMogsVariant::Mogwai(v5::Mogwai {
+				dna: [[1; 32], [2; 32]],
+				generation: MogwaiGeneration::Third,
+				rarity: RarityType::Rare,
+				phase: PhaseType::Hatched,
+				stud,
+				parents: Some([3, 4]),
+				lineage_depth: 2,
+				last_bred: Some(15),
+				breed_count: 1,
+			}),
+		};
+
+		let mut expected = mogwai(stud);
+		let mogwai = expected.as_mogwai().unwrap();
+		mogwai.parents = Some([3, 4]);
+		mogwai.lineage_depth = 2;
+		mogwai.last_bred = Some(15);
+		mogwai.breed_count = 1;
+
+		for version in [3, 4, 5] {
+			assert_eq!(upgrade_asset_from::<u32>(version, &old.encode()), Some(expected.clone()));
+		}
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &old.encode()), None);
+	}
+
+	#[test]
+	fn upgrades_v6_mogwai_keeping_its_expedition() {
+		let expedition = Some(Expedition { dungeon: Dungeon::Ruins, until: 30, power: 500 });
+		let old = v6::BattleMogsAsset::<u32> {
+			id: 1,
+			genesis: 10,
+			variant: v6::BattleMogsVariant::Mogwai(v6::Mogwai {
+				dna: [[1; 32], [2; 32]],
+				generation: MogwaiGeneration::Third,
+				rarity: RarityType::Rare,
+				phase: PhaseType::Hatched,
+				stud: None,
+				parents: None,
+				lineage_depth: 0,
+				last_bred: None,
+				breed_count: 0,
+				expedition,
+				injured_until: Some(25),
+			}),
+		};
+
+		let mut expected = mogwai(None);
+		let mogwai = expected.as_mogwai().unwrap();
+		mogwai.expedition = expedition;
+		mogwai.injured_until = Some(25);
+
+		assert_eq!(upgrade_asset_from::<u32>(6, &old.encode()), Some(expected));
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &old.encode()), None);
+	}
+
+	#[test]
+	fn current_mogwais_keep_their_encoding() {
+		let mut explorer = mogwai(None);
+		let mogwai = explorer.as_mogwai().unwrap();
+		mogwai.expedition = Some(Expedition { dungeon: Dungeon::Ruins, until: 30, power: 500 });
+		mogwai.injured_until = Some(25);
+		mogwai.counted_by = Some(2);
+		assert_eq!(
+			upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &explorer.encode()),
+			Some(explorer.clone())
+		);
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION + 1, &explorer.encode()), None);
+	}
+}
@@ -0,0 +1,68 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Battle stats of mogwais as encoded in the stats segment of their DNA.
+//!
+//! Each stat sums the nibbles of a quarter of the stats segment (`dna[0]`), so it ranges from
+//! `0` to `240` before the bonuses of equipped items are added.
+
+use crate::asset::item::Item;
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Stats {
+	pub attack: u16,
+	pub defense: u16,
+	pub vitality: u16,
+	pub speed: u16,
+}
+
+impl Stats {
+	pub fn from_dna(dna: &[[u8; 32]; 2]) -> Self {
+		let stat = |quarter: usize| {
+			dna[0][quarter * 8..(quarter + 1) * 8]
+				.iter()
+				.map(|byte| u16::from(byte >> 4) + u16::from(byte & 0x0F))
+				.sum()
+		};
+
+		Self { attack: stat(0), defense: stat(1), vitality: stat(2), speed: stat(3) }
+	}
+
+	/// Stats raised by the bonuses of `items`.
+	pub fn equipped_with<'a>(self, items: impl IntoIterator<Item = &'a Item>) -> Self {
+		items.into_iter().fold(self, |stats, item| stats.saturating_add(&item.bonus))
+	}
+
+	/// Sum of all stats, the power of a mogwai on expeditions.
+	pub fn total(&self) -> u16 {
+		self.attack
+			.saturating_add(self.defense)
+			.saturating_add(self.vitality)
+			.saturating_add(self.speed)
+	}
+
+	pub fn saturating_add(&self, other: &Self) -> Self {
+		Self {
+			attack: self.attack.saturating_add(other.attack),
+			defense: self.defense.saturating_add(other.defense),
+			vitality: self.vitality.saturating_add(other.vitality),
+			speed: self.speed.saturating_add(other.speed),
+		}
+	}
+}
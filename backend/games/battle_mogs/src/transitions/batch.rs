@@ -0,0 +1,175 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{BattleMogsAsset, BattleMogsId},
+	error::*,
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
+	BattleMogsTransition,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
+use sage_api::TransitionError;
+
+use frame_support::{
+	pallet_prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+};
+use parity_scale_codec::Codec;
+use sp_core::H256;
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
+use sp_std::vec::Vec;
+
+/// Number of mogwais a batch transition acts on at most.
+pub const MAX_BATCH_SIZE: u32 = 10;
+
+/// Mogwais a batch transition acts on, in order.
+pub type MogwaiBatch = BoundedVec<BattleMogsId, ConstU32<MAX_BATCH_SIZE>>;
+
+/// How batch transitions treat the mogwais they cannot act on.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum BatchMode {
+	/// Fails the whole batch.
+	#[default]
+	AllOrNothing,
+	/// Skips them, notifying each with `MogwaiSkipped` and the code of its error.
+	SkipInvalid,
+}
+
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
+where
+	AccountId: Member + Codec,
+	BlockNumber: BlockNumberT,
+	Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen,
+	Sage: SageApi<
+		AccountId = AccountId,
+		AssetId = BattleMogsId,
+		Asset = BattleMogsAsset<BlockNumber>,
+		Balance = Balance,
+		BlockNumber = BlockNumber,
+		TransitionConfig = BattleMogsTransitionConfig,
+		HashOutput = H256,
+	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
+{
+	pub(crate) fn hatch_mogwais(
+		owner: &AccountId,
+		mogwai_ids: &MogwaiBatch,
+		mode: &BatchMode,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::batch(owner, mogwai_ids, mode, |mogwai_id, player_id, player_asset| {
+			Self::hatch_mogwai_of(owner, mogwai_id, player_id, player_asset)
+		})
+	}
+
+	pub(crate) fn sacrifice_mogwais(
+		owner: &AccountId,
+		mogwai_ids: &MogwaiBatch,
+		mode: &BatchMode,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::batch(owner, mogwai_ids, mode, |mogwai_id, player_id, player_asset| {
+			Self::sacrifice_mogwai_of(
+				owner,
+				mogwai_id,
+				payment_asset.clone(),
+				player_id,
+				player_asset,
+			)
+		})
+	}
+
+	pub(crate) fn remove_mogwais(
+		owner: &AccountId,
+		mogwai_ids: &MogwaiBatch,
+		mode: &BatchMode,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::batch(owner, mogwai_ids, mode, |mogwai_id, _, player_asset| {
+			Self::remove_mogwai_of(owner, mogwai_id, payment_asset.clone(), player_asset)
+		})
+	}
+
+	/// Runs `transition` for each of `mogwai_ids`, updating the player profile of `owner`
+	/// once for the whole batch.
+	///
+	/// Each mogwai is acted on in its own storage layer and on a copy of the profile, so the
+	/// mogwais it skips leave neither funds moved nor the profile changed. Mogwais listed more
+	/// than once are invalid after their first occurrence. The batch fails if it ends up acting
+	/// on none of its mogwais.
+	fn batch(
+		owner: &AccountId,
+		mogwai_ids: &MogwaiBatch,
+		mode: &BatchMode,
+		transition: impl Fn(
+			&BattleMogsId,
+			&BattleMogsId,
+			&mut BattleMogsAsset<BlockNumber>,
+		) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::with_player(owner, |player_id, player_asset| {
+			let mut output = Vec::new();
+			for (i, mogwai_id) in mogwai_ids.iter().enumerate() {
+				let mut mogwai_player_asset = player_asset.clone();
+				let mogwai_output = if mogwai_ids[..i].contains(mogwai_id) {
+					Err(BattleMogsError::from(INVALID_BATCH))
+				} else {
+					Self::in_storage_layer(|| {
+						transition(mogwai_id, player_id, &mut mogwai_player_asset)
+					})
+				};
+
+				match (mogwai_output, mode) {
+					(Ok(mogwai_output), _) => {
+						*player_asset = mogwai_player_asset;
+						output.extend(mogwai_output);
+					},
+					(Err(error), BatchMode::SkipInvalid) => Events::deposit_game_event(
+						owner,
+						BattleMogsEvent::MogwaiSkipped {
+							mogwai_id: *mogwai_id,
+							code: BattleMogsError::code_of(&error),
+						},
+					),
+					(Err(error), BatchMode::AllOrNothing) => return Err(error),
+				}
+			}
+
+			ensure!(!output.is_empty(), BattleMogsError::from(INVALID_BATCH));
+			Ok(output)
+		})
+	}
+
+	/// Runs `transition`, reverting the storage changes it made if it fails.
+	fn in_storage_layer(
+		transition: impl FnOnce() -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		with_transaction(|| {
+			let output = transition();
+			if output.is_ok() {
+				TransactionOutcome::Commit(Ok(output))
+			} else {
+				TransactionOutcome::Rollback(Ok(output))
+			}
+		})
+		.unwrap_or_else(|_: DispatchError| Err(BattleMogsError::from(INVALID_BATCH)))
+	}
+}
@@ -17,24 +17,33 @@
 use crate::{
 	algorithm::{Breeding, Generation},
 	asset::{
+		achievement_table::Achievement,
 		mogwai::{Mogwai as MogwaiVariant, PhaseType, RarityType},
 		BattleMogsAsset, BattleMogsId, BattleMogsVariant,
 	},
-	config::Pricing,
+	config::{Experience, Pricing},
 	error::*,
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType},
+	events::BattleMogsEvent,
+	transitions::{
+		BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType, PaymentFungibles,
+	},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
 use parity_scale_codec::Codec;
 use sp_core::H256;
-use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member},
+	SaturatedConversion,
+};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -48,19 +57,19 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn breed_mogwais(
 		owner: &AccountId,
 		mogwai_id_1: &BattleMogsId,
 		mogwai_id_2: &BattleMogsId,
-		table_id: &BattleMogsId,
 		payment_asset: Option<Sage::FungiblesAssetId>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		ensure!(
 			mogwai_id_1 != mogwai_id_2,
 			BattleMogsError::from(CANNOT_USE_SAME_ASSET_FOR_BREEDING),
 		);
-		Self::ensure_not_max_mogwais(owner)?;
 
 		let mut asset_1 = Self::get_owned_mogwai(owner, mogwai_id_1)?;
 		let mogwai_1 = asset_1.as_mogwai()?;
@@ -76,7 +85,45 @@ where
 			BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE)
 		);
 
-		let mut table_asset = Self::get_owned_achievement_table(owner, table_id)?;
+		let config = Sage::get_transition_config();
+		let block_number = Sage::get_current_block_number();
+		for mogwai in [&*mogwai_1, &*mogwai_2] {
+			ensure!(
+				!config.fertility.is_exhausted(mogwai),
+				BattleMogsError::from(MOGWAI_BREED_LIMIT_REACHED)
+			);
+			ensure!(
+				!config.fertility.is_resting(mogwai, block_number),
+				BattleMogsError::from(MOGWAI_ON_BREEDING_COOLDOWN)
+			);
+		}
+
+		let inbreeding = config.inbreeding;
+		ensure!(
+			!inbreeding.no_parents ||
+				!(mogwai_1.has_parent(mogwai_id_2) || mogwai_2.has_parent(mogwai_id_1)),
+			BattleMogsError::from(MOGWAIS_TOO_CLOSELY_RELATED)
+		);
+		ensure!(
+			!inbreeding.no_siblings || !mogwai_1.is_sibling_of(mogwai_2),
+			BattleMogsError::from(MOGWAIS_TOO_CLOSELY_RELATED)
+		);
+
+		// Breeding with the mogwai of another account requires its owner to offer it as stud.
+		let is_mogwai_2_owned = Sage::ensure_ownership(owner, mogwai_id_2).is_ok();
+		let stud_price = if is_mogwai_2_owned {
+			Balance::zero()
+		} else {
+			let offer = mogwai_2
+				.stud
+				.filter(|offer| offer.is_active_at(&block_number))
+				.ok_or(BattleMogsError::from(MOGWAI_NOT_OFFERED_AS_STUD))?;
+			offer.price.saturated_into()
+		};
+
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		Self::ensure_not_max_mogwais(owner, &mut player_asset)?;
+		Self::count_minted_mogwai(owner, &mut player_asset)?;
 
 		let mogwai_id = Self::new_asset_id()?;
 
@@ -93,17 +140,34 @@ where
 			&next_gen_hash,
 		);
 
-		let block_number = Sage::get_current_block_number();
 		let breed_type = BreedType::calculate_breed_type(block_number);
 
 		let pairing_price = Pricing::<Balance>::pairing(mogwai_1.rarity, mogwai_2.rarity);
-		Self::deposit_funds_to_asset(mogwai_id_2, owner, payment_asset, pairing_price)?;
+		let breeding_price = pairing_price.saturating_add(stud_price);
+		Self::deposit_funds_to_asset(mogwai_id_2, owner, payment_asset, breeding_price)?;
 
 		let final_dna = Breeding::pairing(breed_type, &mogwai_1.dna[0], &mogwai_2.dna[0]);
 		let mogwai_rarity = RarityType::from(((max_rarity as u8) << 4) + rarity as u8);
 
-		let bred_mogwai =
-			MogwaiVariant { dna: final_dna, generation: next_gen, rarity, phase: PhaseType::Bred };
+		let bred_mogwai = MogwaiVariant {
+			dna: final_dna,
+			generation: next_gen,
+			rarity,
+			phase: PhaseType::Bred,
+			stud: None,
+			parents: Some([*mogwai_id_1, *mogwai_id_2]),
+			lineage_depth: mogwai_1.lineage_depth.max(mogwai_2.lineage_depth).saturating_add(1),
+			last_bred: None,
+			breed_count: 0,
+			expedition: None,
+			injured_until: None,
+			counted_by: Some(player_id),
+		};
+
+		for mogwai in [&mut *mogwai_1, &mut *mogwai_2] {
+			mogwai.last_bred = Some(block_number);
+			mogwai.breed_count = mogwai.breed_count.saturating_add(1);
+		}
 
 		let bred_asset = BattleMogsAsset {
 			id: mogwai_id,
@@ -112,16 +176,42 @@ where
 		};
 
 		if mogwai_rarity == RarityType::Mythical {
-			let table = table_asset.as_achievement()?;
-			table.legend_breeder = table.legend_breeder.increase_by(1);
+			Self::progress_achievement(
+				owner,
+				&player_id,
+				&mut player_asset,
+				Achievement::LegendBreeder,
+			)?;
 		}
 
-		let is_mogwai_2_owned = Sage::ensure_ownership(owner, mogwai_id_2).is_ok();
 		if !is_mogwai_2_owned {
-			let table = table_asset.as_achievement()?;
-			table.promiscuous = table.promiscuous.increase_by(1);
+			Self::progress_achievement(
+				owner,
+				&player_id,
+				&mut player_asset,
+				Achievement::Promiscuous,
+			)?;
 		}
+		Self::gain_xp(owner, &player_id, &mut player_asset, Experience::BREED)?;
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::MogwaiBred {
+				mogwai_id_1: *mogwai_id_1,
+				mogwai_id_2: *mogwai_id_2,
+				offspring_id: mogwai_id,
+				breed_type,
+				generation: next_gen,
+				rarity,
+			},
+		);
 
-		Ok(sp_std::vec![TransitionOutput::Minted(bred_asset)])
+		// `Mutated` only updates the stored asset, so a foreign stud stays with its owner.
+		Ok(sp_std::vec![
+			TransitionOutput::Mutated(*mogwai_id_1, asset_1),
+			TransitionOutput::Mutated(*mogwai_id_2, asset_2),
+			TransitionOutput::Minted(bred_asset),
+			TransitionOutput::Mutated(player_id, player_asset)
+		])
 	}
 }
@@ -20,11 +20,15 @@ use crate::{
 		mogwai::{Mogwai as MogwaiVariant, MogwaiGeneration, PhaseType, RarityType},
 		BattleMogsAsset, BattleMogsId, BattleMogsVariant,
 	},
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType},
+	events::BattleMogsEvent,
+	transitions::{
+		BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType, PaymentFungibles,
+	},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -32,7 +36,8 @@ use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -46,11 +51,15 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn create_mogwai(
 		owner: &AccountId,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
-		Self::ensure_not_max_mogwais(owner)?;
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		Self::ensure_not_max_mogwais(owner, &mut player_asset)?;
+		Self::count_minted_mogwai(owner, &mut player_asset)?;
 
 		let block_number = Sage::get_current_block_number();
 		let mogwai_id = Self::new_asset_id()?;
@@ -74,8 +83,20 @@ where
 
 		let final_dna = Breeding::pairing(breed_type, &random_dna_1.0, &random_dna_2.0);
 
-		let mogwai =
-			MogwaiVariant { dna: final_dna, generation: next_gen, rarity, phase: PhaseType::Bred };
+		let mogwai = MogwaiVariant {
+			dna: final_dna,
+			generation: next_gen,
+			rarity,
+			phase: PhaseType::Bred,
+			stud: None,
+			parents: None,
+			lineage_depth: 0,
+			last_bred: None,
+			breed_count: 0,
+			expedition: None,
+			injured_until: None,
+			counted_by: Some(player_id),
+		};
 
 		let asset = BattleMogsAsset {
 			id: mogwai_id,
@@ -83,6 +104,9 @@ where
 			variant: BattleMogsVariant::Mogwai(mogwai),
 		};
 
-		Ok(sp_std::vec![TransitionOutput::Minted(asset)])
+		Ok(sp_std::vec![
+			TransitionOutput::Minted(asset),
+			TransitionOutput::Mutated(player_id, player_asset)
+		])
 	}
 }
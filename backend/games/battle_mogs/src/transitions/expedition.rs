@@ -0,0 +1,184 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{
+		item::{Item, ItemKind},
+		mogwai::{Dungeon, Expedition, PhaseType},
+		BattleMogsAsset, BattleMogsId, BattleMogsVariant,
+	},
+	config::{Experience, GameEventType},
+	error::*,
+	events::BattleMogsEvent,
+	stats::Stats,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
+	BattleMogsTransition,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
+use sage_api::{traits::TransitionOutput, TransitionError};
+
+use frame_support::pallet_prelude::*;
+use parity_scale_codec::Codec;
+use sp_core::H256;
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
+use sp_std::vec::Vec;
+
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
+where
+	AccountId: Member + Codec,
+	BlockNumber: BlockNumberT,
+	Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen,
+	Sage: SageApi<
+		AccountId = AccountId,
+		AssetId = BattleMogsId,
+		Asset = BattleMogsAsset<BlockNumber>,
+		Balance = Balance,
+		BlockNumber = BlockNumber,
+		TransitionConfig = BattleMogsTransitionConfig,
+		HashOutput = H256,
+	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
+{
+	/// Sends a hatched mogwai into `dungeon`, locking it for as many expedition periods as the
+	/// dungeon is deep. Its power is taken from its stats and equipped items as they are now.
+	pub(crate) fn send_expedition(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		dungeon: &Dungeon,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
+		let block_number = Sage::get_current_block_number();
+		let items = Self::equipped_items(owner, mogwai_id);
+
+		let mogwai = asset.as_mogwai()?;
+		ensure!(mogwai.phase != PhaseType::Bred, BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE));
+		ensure!(!mogwai.is_injured_at(&block_number), BattleMogsError::from(MOGWAI_INJURED));
+
+		let stats = Stats::from_dna(&mogwai.dna).equipped_with(items.iter().filter_map(
+			|(_, item_asset)| match &item_asset.variant {
+				BattleMogsVariant::Item(item) => Some(item),
+				_ => None,
+			},
+		));
+		let duration =
+			u32::from(GameEventType::time_till(GameEventType::Expedition)) * *dungeon as u32;
+		mogwai.expedition = Some(Expedition {
+			dungeon: *dungeon,
+			until: block_number.saturating_add(duration.into()),
+			power: stats.total(),
+		});
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::ExpeditionSent { mogwai_id: *mogwai_id, dungeon: *dungeon },
+		);
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(*mogwai_id, asset)])
+	}
+
+	/// Brings a mogwai back from its finished expedition.
+	///
+	/// Clearing the dungeon counts as a win of the player, gains experience by the depth of the
+	/// dungeon and may yield an item. Otherwise it counts as a loss and may leave the mogwai
+	/// injured, keeping it from new expeditions while it recovers.
+	pub(crate) fn complete_expedition(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut asset = Self::ensure_ownership(owner, mogwai_id)?;
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		let block_number = Sage::get_current_block_number();
+
+		let mogwai = asset.as_mogwai()?;
+		let expedition = mogwai
+			.expedition
+			.take()
+			.ok_or(BattleMogsError::from(MOGWAI_NOT_ON_EXPEDITION))?;
+		ensure!(
+			expedition.is_finished_at(&block_number),
+			BattleMogsError::from(EXPEDITION_NOT_FINISHED)
+		);
+
+		let subject = (owner, mogwai_id, b"complete_expedition").encode();
+		let random_hash = Sage::random_hash(&subject).0;
+		let depth = expedition.dungeon as u8;
+
+		// Rolls from 0 to 199 against the difficulty raised by 100, so that a mogwai as powerful
+		// as the difficulty clears the dungeon half of the time.
+		let roll = u16::from_le_bytes([random_hash[30], random_hash[31]]) % 200;
+		let cleared =
+			expedition.power.saturating_add(roll) >= expedition.dungeon.difficulty() + 100;
+
+		let mut output = Vec::new();
+		let (mut loot, mut injured) = (None, false);
+		if cleared {
+			let battles = &mut player_asset.as_player()?.battles;
+			battles.wins = battles.wins.saturating_add(1);
+
+			// Deeper dungeons hide loot more often, from a quarter of the time up to always.
+			if u16::from(random_hash[29]) < 64 * u16::from(depth) {
+				let item_id = Self::new_asset_id()?;
+				let kind = ItemKind::from(random_hash[28] % 3);
+				output.push(TransitionOutput::Minted(BattleMogsAsset {
+					id: item_id,
+					genesis: block_number,
+					variant: BattleMogsVariant::Item(Item::craft(
+						kind,
+						expedition.dungeon.loot_rarity(),
+						&random_hash,
+					)),
+				}));
+				loot = Some(item_id);
+			}
+
+			Self::gain_xp(
+				owner,
+				&player_id,
+				&mut player_asset,
+				Experience::EXPEDITION * u32::from(depth),
+			)?;
+		} else {
+			let battles = &mut player_asset.as_player()?.battles;
+			battles.losses = battles.losses.saturating_add(1);
+
+			// Half of the failed expeditions injure the mogwai.
+			injured = random_hash[27] < 128;
+			if injured {
+				let recovery = u32::from(GameEventType::time_till(GameEventType::Recovery));
+				mogwai.injured_until = Some(block_number.saturating_add(recovery.into()));
+			}
+		}
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::ExpeditionCompleted {
+				mogwai_id: *mogwai_id,
+				dungeon: expedition.dungeon,
+				cleared,
+				loot,
+				injured,
+			},
+		);
+
+		output.push(TransitionOutput::Mutated(*mogwai_id, asset));
+		output.push(TransitionOutput::Mutated(player_id, player_asset));
+		Ok(output)
+	}
+}
@@ -17,16 +17,19 @@
 use crate::{
 	algorithm::Breeding,
 	asset::{
+		achievement_table::Achievement,
 		mogwai::{Mogwai, PhaseType, RarityType},
 		BattleMogsAsset, BattleMogsId,
 	},
-	config::GameEventType,
+	config::{Experience, GameEventType},
 	error::*,
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput},
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -37,7 +40,8 @@ use sp_runtime::{
 	SaturatedConversion,
 };
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -51,14 +55,26 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn hatch_mogwai(
 		owner: &AccountId,
 		mogwai_id: &BattleMogsId,
-		table_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::with_player(owner, |player_id, player_asset| {
+			Self::hatch_mogwai_of(owner, mogwai_id, player_id, player_asset)
+		})
+	}
+
+	/// Hatches `mogwai_id`, progressing the player profile of `owner` without outputting it.
+	pub(crate) fn hatch_mogwai_of(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		player_id: &BattleMogsId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
-		let mut table_asset = Self::get_owned_achievement_table(owner, table_id)?;
 
 		let block_number = Sage::get_current_block_number();
 		let time_till_hatch = GameEventType::time_till(GameEventType::Hatch);
@@ -79,16 +95,20 @@ where
 		mogwai.rarity = rarity;
 		mogwai.dna = dna;
 
-		let table = table_asset.as_achievement()?;
-		table.egg_hatcher = table.egg_hatcher.increase_by(1);
+		Self::progress_achievement(owner, player_id, player_asset, Achievement::EggHatcher)?;
+		Self::gain_xp(owner, player_id, player_asset, Experience::HATCH)?;
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::MogwaiHatched { mogwai_id: *mogwai_id, rarity },
+		);
 
-		Ok(sp_std::vec![
-			TransitionOutput::Mutated(*mogwai_id, asset),
-			TransitionOutput::Mutated(*table_id, table_asset)
-		])
+		Ok(sp_std::vec![TransitionOutput::Mutated(*mogwai_id, asset)])
 	}
 
-	fn segment_and_bake(mogwai: &mut Mogwai, hash: [u8; 32]) -> ([[u8; 32]; 2], RarityType) {
+	fn segment_and_bake(
+		mogwai: &mut Mogwai<BlockNumber>,
+		hash: [u8; 32],
+	) -> ([[u8; 32]; 2], RarityType) {
 		(Breeding::segmenting(mogwai.dna, hash), Breeding::bake(mogwai.rarity, hash))
 	}
 }
@@ -0,0 +1,218 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{
+		item::{Item, ItemKind},
+		mogwai::{PhaseType, RarityType},
+		BattleMogsAsset, BattleMogsId, BattleMogsVariant,
+	},
+	config::Experience,
+	error::*,
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
+	BattleMogsTransition,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
+use sage_api::{traits::TransitionOutput, TransitionError};
+
+use frame_support::pallet_prelude::*;
+use parity_scale_codec::Codec;
+use sp_core::H256;
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
+use sp_std::vec::Vec;
+
+/// Number of mogwais or items consumed to craft an item.
+pub const CRAFTING_INGREDIENTS: usize = 3;
+
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
+where
+	AccountId: Member + Codec,
+	BlockNumber: BlockNumberT,
+	Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen,
+	Sage: SageApi<
+		AccountId = AccountId,
+		AssetId = BattleMogsId,
+		Asset = BattleMogsAsset<BlockNumber>,
+		Balance = Balance,
+		BlockNumber = BlockNumber,
+		TransitionConfig = BattleMogsTransitionConfig,
+		HashOutput = H256,
+	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
+{
+	/// Crafts an item out of hatched mogwais or unequipped items of `owner`, as rare as the
+	/// rarest of them and with a chance to be one rarity above.
+	///
+	/// The funds of consumed mogwais are settled like those of removed mogwais, and the items
+	/// equipped on them are unequipped.
+	pub(crate) fn craft_item(
+		owner: &AccountId,
+		ingredients: &[BattleMogsId; CRAFTING_INGREDIENTS],
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut rarity = RarityType::Common;
+		let mut mogwai_ids = Vec::with_capacity(CRAFTING_INGREDIENTS);
+		for (i, ingredient_id) in ingredients.iter().enumerate() {
+			ensure!(
+				!ingredients[..i].contains(ingredient_id),
+				BattleMogsError::from(INVALID_CRAFTING_INGREDIENTS)
+			);
+
+			let ingredient_rarity = match Self::ensure_ownership(owner, ingredient_id)?.variant {
+				BattleMogsVariant::Mogwai(mogwai) => {
+					ensure!(
+						mogwai.phase != PhaseType::Bred,
+						BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE)
+					);
+					ensure!(
+						mogwai.expedition.is_none(),
+						BattleMogsError::from(MOGWAI_ON_EXPEDITION)
+					);
+					mogwai_ids.push(*ingredient_id);
+					mogwai.rarity
+				},
+				BattleMogsVariant::Item(item) => {
+					ensure!(
+						item.equipped_on.is_none(),
+						BattleMogsError::from(ITEM_ALREADY_EQUIPPED)
+					);
+					item.rarity
+				},
+				BattleMogsVariant::Player(_) =>
+					return Err(BattleMogsError::from(INVALID_CRAFTING_INGREDIENTS)),
+			};
+			if ingredient_rarity as u8 > rarity as u8 {
+				rarity = ingredient_rarity;
+			}
+		}
+
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		let mut output: BattleMogsTransitionOutput<BlockNumber> =
+			ingredients.iter().map(|id| TransitionOutput::Consumed(*id)).collect();
+		for mogwai_id in &mogwai_ids {
+			Self::settle_asset_funds(owner, mogwai_id, payment_asset.clone())?;
+			Self::count_consumed_mogwai(owner, &mut player_asset, mogwai_id)?;
+			output.extend(Self::unequip_items_of(owner, mogwai_id)?);
+		}
+
+		let random_hash = Sage::random_hash(&(owner, ingredients, b"craft_item").encode()).0;
+		if random_hash[31] < 64 && rarity != RarityType::Mythical {
+			rarity = RarityType::from(rarity as u8 + 1);
+		}
+		let kind = ItemKind::from(random_hash[30] % 3);
+
+		let item_id = Self::new_asset_id()?;
+		output.push(TransitionOutput::Minted(BattleMogsAsset {
+			id: item_id,
+			genesis: Sage::get_current_block_number(),
+			variant: BattleMogsVariant::Item(Item::craft(kind, rarity, &random_hash)),
+		}));
+
+		Self::gain_xp(owner, &player_id, &mut player_asset, Experience::CRAFT)?;
+		Events::deposit_game_event(owner, BattleMogsEvent::ItemCrafted { item_id, kind, rarity });
+
+		output.push(TransitionOutput::Mutated(player_id, player_asset));
+		Ok(output)
+	}
+
+	/// Equips `item_id` on `mogwai_id`, provided the mogwai has no other item of its kind.
+	pub(crate) fn equip_item(
+		owner: &AccountId,
+		item_id: &BattleMogsId,
+		mogwai_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut mogwai_asset = Self::get_owned_mogwai(owner, mogwai_id)?;
+		ensure!(
+			mogwai_asset.as_mogwai()?.phase != PhaseType::Bred,
+			BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE)
+		);
+
+		let mut item_asset = Self::ensure_ownership(owner, item_id)?;
+		let item = item_asset.as_item()?;
+		ensure!(item.equipped_on.is_none(), BattleMogsError::from(ITEM_ALREADY_EQUIPPED));
+		ensure!(
+			Self::equipped_items(owner, mogwai_id).iter().all(|(_, equipped)| {
+				!matches!(equipped.variant, BattleMogsVariant::Item(other) if other.kind == item.kind)
+			}),
+			BattleMogsError::from(ITEM_SLOT_OCCUPIED)
+		);
+
+		item.equipped_on = Some(*mogwai_id);
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::ItemEquipped { item_id: *item_id, mogwai_id: *mogwai_id },
+		);
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(*item_id, item_asset)])
+	}
+
+	/// Takes `item_id` off the mogwai it is equipped on, which no longer needs to be owned.
+	///
+	/// Items stay on mogwais away on an expedition, whose power counts them until it completes.
+	pub(crate) fn unequip_item(
+		owner: &AccountId,
+		item_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut item_asset = Self::ensure_ownership(owner, item_id)?;
+		let item = item_asset.as_item()?;
+		let mogwai_id = item.equipped_on.take().ok_or(BattleMogsError::from(ITEM_NOT_EQUIPPED))?;
+		if let Ok(mogwai_asset) = Sage::get_asset(&mogwai_id) {
+			Self::ensure_mogwai(&mogwai_asset)?;
+		}
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::ItemUnequipped { item_id: *item_id, mogwai_id },
+		);
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(*item_id, item_asset)])
+	}
+
+	/// Items of `owner` equipped on `mogwai_id`, at most one of each kind.
+	pub(crate) fn equipped_items(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+	) -> Vec<(BattleMogsId, BattleMogsAsset<BlockNumber>)> {
+		Sage::iter_assets_from(owner)
+			.filter(|(_, asset)| {
+				matches!(asset.variant, BattleMogsVariant::Item(item) if item.is_equipped_on(mogwai_id))
+			})
+			.collect()
+	}
+
+	/// Unequips the items of `owner` from `mogwai_id` consumed by the transition.
+	pub(crate) fn unequip_items_of(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut output = Vec::new();
+		for (item_id, mut item_asset) in Self::equipped_items(owner, mogwai_id) {
+			item_asset.as_item()?.equipped_on = None;
+			Events::deposit_game_event(
+				owner,
+				BattleMogsEvent::ItemUnequipped { item_id, mogwai_id: *mogwai_id },
+			);
+			output.push(TransitionOutput::Mutated(item_id, item_asset));
+		}
+
+		Ok(output)
+	}
+}
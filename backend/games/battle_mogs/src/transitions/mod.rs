@@ -14,16 +14,20 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use crate::{error::*, BattleMogsTransition};
+use crate::{config::Experience, error::*, events::BattleMogsEvent, BattleMogsTransition};
 
 use ajuna_payment_handler::NativeId;
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
-use crate::asset::{BattleMogsAsset, BattleMogsId};
+use crate::asset::{
+	achievement_table::Achievement, mogwai::Mogwai, player::Player, BattleMogsAsset, BattleMogsId,
+	BattleMogsVariant,
+};
 use frame_support::{
 	ensure,
-	pallet_prelude::{Decode, Encode, TypeInfo},
+	pallet_prelude::{BoundedVec, ConstU32, Decode, Encode, TypeInfo},
 	Parameter,
 };
 use parity_scale_codec::{Codec, MaxEncodedLen};
@@ -34,19 +38,28 @@ use sp_runtime::{
 };
 use sp_std::vec::Vec;
 
+mod batch;
 mod breed;
 mod create;
+mod expedition;
 mod hatch;
+mod item;
 mod morph;
 mod register;
 mod remove;
 mod sacrifice;
 mod sarifice_into;
+mod showcase;
+mod stud;
+
+pub use batch::{BatchMode, MogwaiBatch, MAX_BATCH_SIZE};
+pub use item::CRAFTING_INGREDIENTS;
 
 pub(crate) type BattleMogsTransitionOutput<BlockNumber> =
 	Vec<TransitionOutput<BattleMogsId, BattleMogsAsset<BlockNumber>>>;
 
-#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq, TypeInfo)]
+#[derive(Encode, Decode, Debug, Copy, Clone, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum BreedType {
 	DomDom = 0,
 	DomRez = 1,
@@ -72,6 +85,7 @@ impl BreedType {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BattleMogsTransitionConfig {
 	pub max_mogwais: u16,
 	pub target_egg_hatcher: u16,
@@ -79,10 +93,101 @@ pub struct BattleMogsTransitionConfig {
 	pub target_morpheus: u16,
 	pub target_legend_breeder: u16,
 	pub target_promiscuous: u16,
+	pub inbreeding: InbreedingRules,
+	pub fertility: FertilityRules,
+	pub removed_funds: FundsDestination,
+}
+
+/// Restrictions on breeding related mogwais, all disabled by default.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct InbreedingRules {
+	/// Forbids breeding a mogwai with one of its parents.
+	pub no_parents: bool,
+	/// Forbids breeding mogwais that share a parent.
+	pub no_siblings: bool,
+}
+
+/// Limits on how often a mogwai can breed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct FertilityRules {
+	/// Blocks a common first generation mogwai rests after breeding, the cooldown of other
+	/// mogwais is multiplied by their rarity and generation. `0` disables cooldowns.
+	pub base_cooldown: u16,
+	/// Number of times a mogwai can breed in its lifetime, `0` for no limit.
+	pub max_breeds: u16,
+}
+
+impl FertilityRules {
+	/// Blocks `mogwai` has to rest after breeding before it can breed again.
+	pub fn cooldown_of<BlockNumber>(&self, mogwai: &Mogwai<BlockNumber>) -> u32 {
+		u32::from(self.base_cooldown) * (mogwai.rarity as u32 + 1) * mogwai.generation as u32
+	}
+
+	/// Whether `mogwai` is still resting from its last breed at `block_number`.
+	pub fn is_resting<BlockNumber: BlockNumberT>(
+		&self,
+		mogwai: &Mogwai<BlockNumber>,
+		block_number: BlockNumber,
+	) -> bool {
+		let cooldown = BlockNumber::from(self.cooldown_of(mogwai));
+		mogwai
+			.last_bred
+			.is_some_and(|last_bred| block_number < last_bred.saturating_add(cooldown))
+	}
+
+	/// Whether `mogwai` has used up all of its breeds.
+	pub fn is_exhausted<BlockNumber>(&self, mogwai: &Mogwai<BlockNumber>) -> bool {
+		self.max_breeds > 0 && mogwai.breed_count >= self.max_breeds
+	}
+}
+
+/// Where the funds left on consumed mogwais are settled to.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum FundsDestination {
+	/// Back to the owner of the mogwai.
+	#[default]
+	Owner,
+	/// To a fixed account, such as a treasury or an unspendable burn address.
+	Account([u8; 32]),
+}
+
+/// Number of fungibles besides the native currency that consumed mogwais settle at most.
+pub const MAX_PAYMENT_FUNGIBLES: u32 = 4;
+
+/// Fungibles besides the native currency consumed mogwais settle their funds in.
+pub type PaymentFungibleList<FungiblesAssetId> =
+	BoundedVec<FungiblesAssetId, ConstU32<MAX_PAYMENT_FUNGIBLES>>;
+
+/// Lists the fungibles besides the native currency and the payment asset of a transition
+/// that mogwais may hold funds in, so that consumed mogwais settle them as well.
+pub trait PaymentFungibles<FungiblesAssetId> {
+	fn payment_fungibles() -> PaymentFungibleList<FungiblesAssetId>;
+}
+
+impl<FungiblesAssetId> PaymentFungibles<FungiblesAssetId> for () {
+	fn payment_fungibles() -> PaymentFungibleList<FungiblesAssetId> {
+		BoundedVec::new()
+	}
+}
+
+impl FundsDestination {
+	/// Account receiving the funds of an asset owned by `owner`, `None` if the configured
+	/// account does not decode into an `AccountId`.
+	pub fn account_for<AccountId: Decode + Clone>(&self, owner: &AccountId) -> Option<AccountId> {
+		match self {
+			Self::Owner => Some(owner.clone()),
+			Self::Account(account) => AccountId::decode(&mut &account[..]).ok(),
+		}
+	}
 }
 
 pub const DEFAULT_MAX_MOGWAIS: u16 = 10;
 pub const DEFAULT_TARGET: u16 = 100;
+pub const DEFAULT_BREEDING_COOLDOWN: u16 = 20;
+pub const DEFAULT_MAX_BREEDS: u16 = 10;
 
 impl Default for BattleMogsTransitionConfig {
 	fn default() -> Self {
@@ -93,11 +198,18 @@ impl Default for BattleMogsTransitionConfig {
 			target_morpheus: DEFAULT_TARGET,
 			target_legend_breeder: DEFAULT_TARGET,
 			target_promiscuous: DEFAULT_TARGET,
+			inbreeding: InbreedingRules::default(),
+			fertility: FertilityRules {
+				base_cooldown: DEFAULT_BREEDING_COOLDOWN,
+				max_breeds: DEFAULT_MAX_BREEDS,
+			},
+			removed_funds: FundsDestination::default(),
 		}
 	}
 }
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -111,36 +223,215 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	fn new_asset_id() -> Result<BattleMogsId, TransitionError> {
 		Sage::create_next_asset_id().ok_or(TransitionError::CouldNotCreateAssetId)
 	}
 
-	pub(crate) fn ensure_not_max_mogwais(account: &AccountId) -> Result<(), TransitionError> {
-		let mogwai_count =
-			Sage::iter_assets_from(account).filter(|(_, asset)| asset.is_mogwai()).count();
+	/// Number of mogwais owned by `account` that no other player counts, or an error if it is
+	/// already registered.
+	pub(crate) fn count_mogwais_of_new_player(account: &AccountId) -> Result<u16, TransitionError> {
+		let mut mogwai_count = 0_u16;
+		for (_, asset) in Sage::iter_assets_from(account) {
+			ensure!(
+				!asset.is_player(),
+				TransitionError::Transition { code: PLAYER_ALREADY_REGISTERED }
+			);
+			if matches!(&asset.variant, BattleMogsVariant::Mogwai(mogwai) if mogwai.counted_by.is_none())
+			{
+				mogwai_count = mogwai_count.saturating_add(1);
+			}
+		}
+
+		Ok(mogwai_count)
+	}
+
+	/// The player profile of `owner` together with its id.
+	///
+	/// Profiles are fetched by [`Player::id_of`], falling back to searching the assets of
+	/// `owner` for players registered before their ids were derived from their account.
+	pub(crate) fn get_player(
+		owner: &AccountId,
+	) -> Result<(BattleMogsId, BattleMogsAsset<BlockNumber>), TransitionError> {
+		let player_id = Player::id_of(owner);
+		match Sage::ensure_ownership(owner, &player_id) {
+			Ok(player_asset) if player_asset.is_player() => Ok((player_id, player_asset)),
+			_ => Sage::iter_assets_from(owner)
+				.find(|(_, asset)| asset.is_player())
+				.ok_or(TransitionError::Transition { code: PLAYER_NOT_REGISTERED }),
+		}
+	}
+
+	/// Mogwai count kept in the player profile of `owner`, counting its mogwais once for
+	/// players upgraded from an achievement table.
+	fn mogwai_count<'a>(
+		owner: &AccountId,
+		player_asset: &'a mut BattleMogsAsset<BlockNumber>,
+	) -> Result<&'a mut u16, TransitionError> {
+		let player_id = player_asset.id;
+		let player = player_asset.as_player()?;
+		Ok(player.mogwai_count.get_or_insert_with(|| {
+			let mogwai_count = Sage::iter_assets_from(owner)
+				.filter(|(_, asset)| match &asset.variant {
+					BattleMogsVariant::Mogwai(mogwai) => mogwai.is_counted_by(&player_id),
+					_ => false,
+				})
+				.count();
+			mogwai_count.saturated_into()
+		}))
+	}
+
+	/// Runs `transition` on the player profile of `owner`, outputting the profile after the
+	/// output of the transition.
+	pub(crate) fn with_player(
+		owner: &AccountId,
+		transition: impl FnOnce(
+			&BattleMogsId,
+			&mut BattleMogsAsset<BlockNumber>,
+		) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		let mut output = transition(&player_id, &mut player_asset)?;
+		output.push(TransitionOutput::Mutated(player_id, player_asset));
+		Ok(output)
+	}
+
+	pub(crate) fn ensure_not_max_mogwais(
+		owner: &AccountId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
+	) -> Result<(), TransitionError> {
+		let mogwai_count = *Self::mogwai_count(owner, player_asset)?;
 		let max_mogwais = Sage::get_transition_config().max_mogwais;
 		ensure!(
-			mogwai_count <= max_mogwais as usize,
+			mogwai_count < max_mogwais,
 			TransitionError::Transition { code: MOGWAI_LIMIT_REACHED }
 		);
 
 		Ok(())
 	}
 
-	pub(crate) fn ensure_has_not_achievement_table(
-		account: &AccountId,
+	/// Counts a mogwai minted for `owner` in its player profile.
+	pub(crate) fn count_minted_mogwai(
+		owner: &AccountId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
 	) -> Result<(), TransitionError> {
-		let has_achievement_table =
-			Sage::iter_assets_from(account).any(|(_, asset)| asset.is_achievement());
-		ensure!(
-			!has_achievement_table,
-			TransitionError::Transition { code: PLAYER_ALREADY_HAS_ACHIEVEMENT_TABLE }
-		);
+		let mogwai_count = Self::mogwai_count(owner, player_asset)?;
+		*mogwai_count = mogwai_count.saturating_add(1);
+		Ok(())
+	}
+
+	/// Uncounts `mogwai_id` consumed by the transition from the player profile of `owner`,
+	/// taking it off its showcase.
+	pub(crate) fn count_consumed_mogwai(
+		owner: &AccountId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
+		mogwai_id: &BattleMogsId,
+	) -> Result<(), TransitionError> {
+		// Traded mogwais not used before are still counted by their previous player.
+		let player_id = player_asset.id;
+		let is_counted = match Sage::get_asset(mogwai_id) {
+			Ok(BattleMogsAsset { variant: BattleMogsVariant::Mogwai(mogwai), .. }) =>
+				mogwai.is_counted_by(&player_id),
+			_ => true,
+		};
+		if is_counted {
+			let mogwai_count = Self::mogwai_count(owner, player_asset)?;
+			*mogwai_count = mogwai_count.saturating_sub(1);
+		}
+
+		let player = player_asset.as_player()?;
+		if player.showcase == Some(*mogwai_id) {
+			player.showcase = None;
+		}
 
 		Ok(())
 	}
 
+	/// Moves the count of the mogwais `owner` got traded or transferred by another player,
+	/// and touched in the transition with `output`, from the previous player to `owner`.
+	///
+	/// Mogwais change owners without any transition of the game, so their counts follow them
+	/// lazily once their new owner uses them. Consumed ones are only uncounted from the
+	/// previous player, as [`Self::count_consumed_mogwai`] leaves them to it.
+	pub(crate) fn count_traded_mogwais(
+		owner: &AccountId,
+		output: &mut BattleMogsTransitionOutput<BlockNumber>,
+	) -> Result<(), TransitionError> {
+		let Ok((player_id, _)) = Self::get_player(owner) else { return Ok(()) };
+
+		let mut gained = 0_u16;
+		let mut previous_counters = Vec::new();
+		for entry in output.iter_mut() {
+			match entry {
+				TransitionOutput::Mutated(mogwai_id, asset)
+					if asset.is_mogwai() && Sage::ensure_ownership(owner, mogwai_id).is_ok() =>
+				{
+					// Mogwais without a counter were counted by their owner, as they could not
+					// be traded before recording it.
+					let mogwai = asset.as_mogwai()?;
+					match mogwai.counted_by.replace(player_id) {
+						Some(counter_id) if counter_id != player_id => {
+							gained = gained.saturating_add(1);
+							previous_counters.push(counter_id);
+						},
+						_ => {},
+					}
+				},
+				TransitionOutput::Consumed(mogwai_id) => {
+					if let Ok(BattleMogsAsset {
+						variant: BattleMogsVariant::Mogwai(mogwai), ..
+					}) = Sage::get_asset(mogwai_id)
+					{
+						previous_counters.extend(
+							mogwai.counted_by.filter(|counter_id| *counter_id != player_id),
+						);
+					}
+				},
+				_ => {},
+			}
+		}
+
+		if gained > 0 {
+			if let Some(count) = Self::output_player(output, &player_id)?.mogwai_count.as_mut() {
+				*count = count.saturating_add(gained);
+			}
+		}
+		for counter_id in previous_counters {
+			if let Ok(counter) = Self::output_player(output, &counter_id) {
+				if let Some(count) = counter.mogwai_count.as_mut() {
+					*count = count.saturating_sub(1);
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Player profile `player_id` in `output`, adding it as mutated if not output yet.
+	fn output_player<'a>(
+		output: &'a mut BattleMogsTransitionOutput<BlockNumber>,
+		player_id: &BattleMogsId,
+	) -> Result<&'a mut Player, TransitionError> {
+		let position = output.iter().position(
+			|entry| matches!(entry, TransitionOutput::Mutated(asset_id, _) if asset_id == player_id),
+		);
+		let index = match position {
+			Some(index) => index,
+			None => {
+				let player_asset = Sage::get_asset(player_id)
+					.map_err(|_| TransitionError::Transition { code: ASSET_NOT_FOUND })?;
+				output.push(TransitionOutput::Mutated(*player_id, player_asset));
+				output.len() - 1
+			},
+		};
+		match &mut output[index] {
+			TransitionOutput::Mutated(_, player_asset) => player_asset.as_player(),
+			_ => Err(TransitionError::Transition { code: ASSET_IS_NOT_PLAYER }),
+		}
+	}
+
 	pub(crate) fn ensure_ownership(
 		owner: &AccountId,
 		mogwai_id: &BattleMogsId,
@@ -148,21 +439,20 @@ where
 		Sage::ensure_ownership(owner, mogwai_id).map_err(|_| TransitionError::AssetOwnership)
 	}
 
+	/// Ensures `asset` is a mogwai that is not locked away on an expedition.
 	pub(crate) fn ensure_mogwai(
 		asset: &BattleMogsAsset<BlockNumber>,
 	) -> Result<(), TransitionError> {
-		ensure!(asset.is_mogwai(), TransitionError::Transition { code: ASSET_IS_NOT_MOGWAI });
-		Ok(())
-	}
-
-	pub(crate) fn ensure_achievement_table(
-		asset: &BattleMogsAsset<BlockNumber>,
-	) -> Result<(), TransitionError> {
-		ensure!(
-			asset.is_achievement(),
-			TransitionError::Transition { code: ASSET_IS_NOT_ACHIEVEMENT_TABLE }
-		);
-		Ok(())
+		match &asset.variant {
+			BattleMogsVariant::Mogwai(mogwai) => {
+				ensure!(
+					mogwai.expedition.is_none(),
+					TransitionError::Transition { code: MOGWAI_ON_EXPEDITION }
+				);
+				Ok(())
+			},
+			_ => Err(TransitionError::Transition { code: ASSET_IS_NOT_MOGWAI }),
+		}
 	}
 
 	pub(crate) fn get_mogwai(
@@ -185,15 +475,43 @@ where
 		Ok(asset)
 	}
 
-	pub(crate) fn get_owned_achievement_table(
+	/// Progresses `achievement` of the player, notifying its completion.
+	pub(crate) fn progress_achievement(
 		owner: &AccountId,
-		table_id: &BattleMogsId,
-	) -> Result<BattleMogsAsset<BlockNumber>, TransitionError> {
-		Self::ensure_ownership(owner, table_id)?;
-		let asset = Sage::get_asset(table_id)
-			.map_err(|_| TransitionError::Transition { code: ASSET_NOT_FOUND })?;
-		Self::ensure_achievement_table(&asset)?;
-		Ok(asset)
+		player_id: &BattleMogsId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
+		achievement: Achievement,
+	) -> Result<(), TransitionError> {
+		let state = player_asset.as_player()?.achievements.state_mut(achievement);
+		let was_completed = state.is_completed();
+		*state = state.increase_by(1);
+
+		if !was_completed && state.is_completed() {
+			Events::deposit_game_event(
+				owner,
+				BattleMogsEvent::AchievementCompleted { player_id: *player_id, achievement },
+			);
+			Self::gain_xp(owner, player_id, player_asset, Experience::ACHIEVEMENT)?;
+		}
+
+		Ok(())
+	}
+
+	/// Adds `xp` to the player, notifying when it reaches a new level.
+	pub(crate) fn gain_xp(
+		owner: &AccountId,
+		player_id: &BattleMogsId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
+		xp: u32,
+	) -> Result<(), TransitionError> {
+		if let Some(level) = player_asset.as_player()?.gain_xp(xp) {
+			Events::deposit_game_event(
+				owner,
+				BattleMogsEvent::PlayerLeveledUp { player_id: *player_id, level },
+			);
+		}
+
+		Ok(())
 	}
 
 	pub(crate) fn get_payment_id(
@@ -235,4 +553,49 @@ where
 		Sage::transfer_funds_from_asset(asset_id, to, fund_id, amount)
 			.map_err(|_| TransitionError::Transition { code: ASSET_COULD_NOT_WITHDRAW_FUNDS })
 	}
+
+	/// Settles everything `asset_id` holds in the native currency, in `payment_asset` and in
+	/// the other [`PaymentFungibles`] to the destination configured for consumed mogwais.
+	///
+	/// Funds in the other payment fungibles that cannot be withdrawn, such as those of a frozen
+	/// asset, are left on the mogwai instead of failing the transition.
+	pub(crate) fn settle_asset_funds(
+		owner: &AccountId,
+		asset_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<(), TransitionError> {
+		let destination = Sage::get_transition_config()
+			.removed_funds
+			.account_for(owner)
+			.ok_or(BattleMogsError::from(ASSET_COULD_NOT_WITHDRAW_FUNDS))?;
+
+		for fund in [None, payment_asset] {
+			let funds = Self::inspect_asset_funds(asset_id, fund.clone());
+			if !funds.is_zero() {
+				Self::withdraw_funds_from_asset(asset_id, &destination, fund, funds)?;
+			}
+		}
+		for fund in Fungibles::payment_fungibles() {
+			let funds = Sage::inspect_asset_funds(asset_id, &fund);
+			if !funds.is_zero() {
+				let _ = Sage::transfer_funds_from_asset(asset_id, &destination, fund, funds);
+			}
+		}
+		Ok(())
+	}
+
+	/// Settles the funds `asset_id` holds beyond the intrinsic share returned to `owner` when
+	/// sacrificing it, but only to a configured account. Otherwise they stay with the consumed
+	/// mogwai, so that sacrificing never returns more than the intrinsic share.
+	pub(crate) fn settle_sacrificed_funds(
+		owner: &AccountId,
+		asset_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<(), TransitionError> {
+		match Sage::get_transition_config().removed_funds {
+			FundsDestination::Owner => Ok(()),
+			FundsDestination::Account(_) =>
+				Self::settle_asset_funds(owner, asset_id, payment_asset),
+		}
+	}
 }
@@ -16,14 +16,18 @@
 
 use crate::{
 	algorithm::Breeding,
-	asset::{mogwai::PhaseType, BattleMogsAsset, BattleMogsId},
-	config::Pricing,
+	asset::{achievement_table::Achievement, mogwai::PhaseType, BattleMogsAsset, BattleMogsId},
+	config::{Experience, Pricing},
 	error::*,
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType},
+	events::BattleMogsEvent,
+	transitions::{
+		BattleMogsTransitionConfig, BattleMogsTransitionOutput, BreedType, PaymentFungibles,
+	},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -31,7 +35,8 @@ use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -45,15 +50,16 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn morph_mogwai(
 		owner: &AccountId,
 		mogwai_id: &BattleMogsId,
-		table_id: &BattleMogsId,
 		payment_asset: Option<Sage::FungiblesAssetId>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
-		let mut table_asset = Self::get_owned_achievement_table(owner, table_id)?;
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
 		let mogwai = asset.as_mogwai()?;
 		ensure!(mogwai.phase != PhaseType::Bred, BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE));
 
@@ -71,9 +77,16 @@ where
 
 		mogwai.dna[0] = Breeding::morph(breed_type, &dx, &dy);
 
-		let table = table_asset.as_achievement()?;
-		table.morpheus = table.morpheus.increase_by(1);
+		Self::progress_achievement(owner, &player_id, &mut player_asset, Achievement::Morpheus)?;
+		Self::gain_xp(owner, &player_id, &mut player_asset, Experience::MORPH)?;
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::MogwaiMorphed { mogwai_id: *mogwai_id, breed_type },
+		);
 
-		Ok(sp_std::vec![TransitionOutput::Mutated(*mogwai_id, asset)])
+		Ok(sp_std::vec![
+			TransitionOutput::Mutated(*mogwai_id, asset),
+			TransitionOutput::Mutated(player_id, player_asset)
+		])
 	}
 }
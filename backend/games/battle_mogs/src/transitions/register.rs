@@ -16,20 +16,27 @@
 
 use crate::{
 	asset::{BattleMogsAsset, BattleMogsId, BattleMogsVariant},
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput},
+	error::*,
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
-use crate::asset::achievement_table::{AchievementState, AchievementTable};
+use crate::asset::{
+	achievement_table::{AchievementState, AchievementTable},
+	player::Player,
+};
 use frame_support::pallet_prelude::*;
 use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -43,11 +50,13 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn register_player(
 		player: &AccountId,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
-		Self::ensure_has_not_achievement_table(player)?;
+		let mogwai_count = Self::count_mogwais_of_new_player(player)?;
 
 		let config = Sage::get_transition_config();
 
@@ -71,13 +80,18 @@ where
 			},
 		};
 
+		let player_id = Player::id_of(player);
+		ensure!(
+			Sage::get_asset(&player_id).is_err(),
+			TransitionError::Transition { code: PLAYER_ALREADY_REGISTERED }
+		);
+
 		let block_number = Sage::get_current_block_number();
-		let table_id = Self::new_asset_id()?;
 
 		let asset = BattleMogsAsset {
-			id: table_id,
+			id: player_id,
 			genesis: block_number,
-			variant: BattleMogsVariant::AchievementTable(table),
+			variant: BattleMogsVariant::Player(Player::new(table, mogwai_count)),
 		};
 
 		Ok(sp_std::vec![TransitionOutput::Minted(asset)])
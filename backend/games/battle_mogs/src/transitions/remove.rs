@@ -16,11 +16,13 @@
 
 use crate::{
 	asset::{BattleMogsAsset, BattleMogsId},
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput},
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -28,7 +30,8 @@ use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -42,13 +45,33 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn remove_mogwai(
 		owner: &AccountId,
 		mogwai_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::with_player(owner, |_, player_asset| {
+			Self::remove_mogwai_of(owner, mogwai_id, payment_asset, player_asset)
+		})
+	}
+
+	/// Removes `mogwai_id`, uncounting it from the player profile of `owner` without
+	/// outputting it. The funds it holds are settled before it is consumed.
+	pub(crate) fn remove_mogwai_of(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		let _ = Self::get_owned_mogwai(owner, mogwai_id)?;
+		Self::settle_asset_funds(owner, mogwai_id, payment_asset)?;
+		let mut output = Self::unequip_items_of(owner, mogwai_id)?;
 
-		Ok(sp_std::vec![TransitionOutput::Consumed(*mogwai_id)])
+		Self::count_consumed_mogwai(owner, player_asset, mogwai_id)?;
+		output.push(TransitionOutput::Consumed(*mogwai_id));
+		Ok(output)
 	}
 }
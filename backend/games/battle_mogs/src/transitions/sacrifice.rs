@@ -15,14 +15,16 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 use crate::{
-	asset::{mogwai::PhaseType, BattleMogsAsset, BattleMogsId},
-	config::Pricing,
+	asset::{achievement_table::Achievement, mogwai::PhaseType, BattleMogsAsset, BattleMogsId},
+	config::{Experience, Pricing},
 	error::*,
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput},
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -30,7 +32,8 @@ use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -44,15 +47,30 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
 	pub(crate) fn sacrifice_mogwai(
 		owner: &AccountId,
 		mogwai_id: &BattleMogsId,
-		table_id: &BattleMogsId,
 		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		Self::with_player(owner, |player_id, player_asset| {
+			Self::sacrifice_mogwai_of(owner, mogwai_id, payment_asset, player_id, player_asset)
+		})
+	}
+
+	/// Sacrifices `mogwai_id`, progressing the player profile of `owner` without outputting
+	/// it. Its intrinsic share of the funds goes to `owner`, the rest only to the account
+	/// configured for the funds of removed mogwais.
+	pub(crate) fn sacrifice_mogwai_of(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+		player_id: &BattleMogsId,
+		player_asset: &mut BattleMogsAsset<BlockNumber>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
-		let mut table_asset = Self::get_owned_achievement_table(owner, table_id)?;
 		let mogwai = asset.as_mogwai()?;
 
 		ensure!(mogwai.phase != PhaseType::Bred, BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE));
@@ -63,14 +81,24 @@ where
 			let intrinsic_return = Pricing::<Balance>::intrinsic_return(mogwai.phase);
 			mogwai_funds.checked_div(&intrinsic_return).unwrap_or(Balance::zero())
 		};
-		Self::withdraw_funds_from_asset(mogwai_id, owner, payment_asset, intrinsic_to_deposit)?;
+		Self::withdraw_funds_from_asset(
+			mogwai_id,
+			owner,
+			payment_asset.clone(),
+			intrinsic_to_deposit,
+		)?;
+		Self::settle_sacrificed_funds(owner, mogwai_id, payment_asset)?;
 
-		let table = table_asset.as_achievement()?;
-		table.sacrificer = table.sacrificer.increase_by(1);
+		Self::count_consumed_mogwai(owner, player_asset, mogwai_id)?;
+		Self::progress_achievement(owner, player_id, player_asset, Achievement::Sacrificer)?;
+		Self::gain_xp(owner, player_id, player_asset, Experience::SACRIFICE)?;
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::MogwaiSacrificed { mogwai_id: *mogwai_id },
+		);
 
-		Ok(sp_std::vec![
-			TransitionOutput::Consumed(*mogwai_id),
-			TransitionOutput::Mutated(*table_id, table_asset)
-		])
+		let mut output = Self::unequip_items_of(owner, mogwai_id)?;
+		output.push(TransitionOutput::Consumed(*mogwai_id));
+		Ok(output)
 	}
 }
@@ -17,15 +17,19 @@
 use crate::{
 	algorithm::Breeding,
 	asset::{
-		mogwai::{PhaseType, RarityType},
+		achievement_table::Achievement,
+		mogwai::{MogwaiGeneration, PhaseType, RarityType},
 		BattleMogsAsset, BattleMogsId,
 	},
+	config::Experience,
 	error::*,
-	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput},
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
 	BattleMogsTransition,
 };
 
 use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
 use sage_api::{traits::TransitionOutput, TransitionError};
 
 use frame_support::pallet_prelude::*;
@@ -33,7 +37,8 @@ use parity_scale_codec::Codec;
 use sp_core::H256;
 use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
 
-impl<AccountId, BlockNumber, Balance, Sage> BattleMogsTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -47,12 +52,16 @@ where
 		TransitionConfig = BattleMogsTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
 {
+	/// Sacrifices `sacrificed_mogwai_id` into `into_mogwai_id`, which takes over its funds in
+	/// the payment asset if it jumps generations. The remaining funds only go to the account
+	/// configured for the funds of removed mogwais.
 	pub(crate) fn sacrifice_mogwai_into(
 		owner: &AccountId,
 		sacrificed_mogwai_id: &BattleMogsId,
 		into_mogwai_id: &BattleMogsId,
-		table_id: &BattleMogsId,
 		payment_asset: Option<Sage::FungiblesAssetId>,
 	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
 		let mut sacrificed_asset = Self::get_owned_mogwai(owner, sacrificed_mogwai_id)?;
@@ -77,7 +86,7 @@ where
 			BattleMogsError::from(MOGWAI_HAS_INVALID_RARITY)
 		);
 
-		let mut table_asset = Self::get_owned_achievement_table(owner, table_id)?;
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
 
 		let gen_jump = Breeding::sacrifice(
 			sacrificed_mogwai.generation,
@@ -88,7 +97,8 @@ where
 			&into_mogwai.dna,
 		) as u16;
 
-		if gen_jump > 0 && (into_mogwai.generation as u16 + gen_jump) <= 16 {
+		let can_jump = gen_jump > 0 && (into_mogwai.generation as u16 + gen_jump) <= 16;
+		if can_jump {
 			let sacrifice_funds =
 				Self::inspect_asset_funds(sacrificed_mogwai_id, payment_asset.clone());
 			Self::withdraw_funds_from_asset(
@@ -98,16 +108,35 @@ where
 				sacrifice_funds.clone(),
 			)?;
 
-			Self::deposit_funds_to_asset(into_mogwai_id, owner, payment_asset, sacrifice_funds)?;
+			Self::deposit_funds_to_asset(
+				into_mogwai_id,
+				owner,
+				payment_asset.clone(),
+				sacrifice_funds,
+			)?;
+
+			into_mogwai.generation =
+				MogwaiGeneration::coerce_from(into_mogwai.generation as u16 + gen_jump);
 		}
+		Self::settle_sacrificed_funds(owner, sacrificed_mogwai_id, payment_asset)?;
 
-		let table = table_asset.as_achievement()?;
-		table.sacrificer = table.sacrificer.increase_by(1);
+		Self::count_consumed_mogwai(owner, &mut player_asset, sacrificed_mogwai_id)?;
+		Self::progress_achievement(owner, &player_id, &mut player_asset, Achievement::Sacrificer)?;
+		Self::gain_xp(owner, &player_id, &mut player_asset, Experience::SACRIFICE)?;
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::MogwaiSacrificedInto {
+				mogwai_id: *sacrificed_mogwai_id,
+				into_id: *into_mogwai_id,
+				generation_jump: if can_jump { gen_jump } else { 0 },
+				generation: into_mogwai.generation,
+			},
+		);
 
-		Ok(sp_std::vec![
-			TransitionOutput::Consumed(*sacrificed_mogwai_id),
-			TransitionOutput::Mutated(*into_mogwai_id, into_asset),
-			TransitionOutput::Mutated(*table_id, table_asset)
-		])
+		let mut output = Self::unequip_items_of(owner, sacrificed_mogwai_id)?;
+		output.push(TransitionOutput::Consumed(*sacrificed_mogwai_id));
+		output.push(TransitionOutput::Mutated(*into_mogwai_id, into_asset));
+		output.push(TransitionOutput::Mutated(player_id, player_asset));
+		Ok(output)
 	}
 }
@@ -0,0 +1,65 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{BattleMogsAsset, BattleMogsId},
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
+	BattleMogsTransition,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
+use sage_api::{traits::TransitionOutput, TransitionError};
+
+use frame_support::pallet_prelude::*;
+use parity_scale_codec::Codec;
+use sp_core::H256;
+use sp_runtime::traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member};
+
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
+where
+	AccountId: Member + Codec,
+	BlockNumber: BlockNumberT,
+	Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen,
+	Sage: SageApi<
+		AccountId = AccountId,
+		AssetId = BattleMogsId,
+		Asset = BattleMogsAsset<BlockNumber>,
+		Balance = Balance,
+		BlockNumber = BlockNumber,
+		TransitionConfig = BattleMogsTransitionConfig,
+		HashOutput = H256,
+	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
+{
+	/// Shows `mogwai_id` on the player profile of `owner`, or clears its showcase for `None`.
+	pub(crate) fn showcase_mogwai(
+		owner: &AccountId,
+		mogwai_id: &Option<BattleMogsId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		if let Some(mogwai_id) = mogwai_id {
+			let _ = Self::get_owned_mogwai(owner, mogwai_id)?;
+		}
+
+		let (player_id, mut player_asset) = Self::get_player(owner)?;
+		player_asset.as_player()?.showcase = *mogwai_id;
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(player_id, player_asset)])
+	}
+}
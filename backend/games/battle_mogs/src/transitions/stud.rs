@@ -0,0 +1,119 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{
+	asset::{
+		mogwai::{PhaseType, StudOffer},
+		BattleMogsAsset, BattleMogsId,
+	},
+	error::*,
+	events::BattleMogsEvent,
+	transitions::{BattleMogsTransitionConfig, BattleMogsTransitionOutput, PaymentFungibles},
+	BattleMogsTransition,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use game_primitives::GameEventHandler;
+use sage_api::{traits::TransitionOutput, TransitionError};
+
+use frame_support::pallet_prelude::*;
+use parity_scale_codec::Codec;
+use sp_core::H256;
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, BlockNumber as BlockNumberT, Member},
+	SaturatedConversion,
+};
+
+impl<AccountId, BlockNumber, Balance, Sage, Events, Fungibles>
+	BattleMogsTransition<AccountId, BlockNumber, Sage, Events, Fungibles>
+where
+	AccountId: Member + Codec,
+	BlockNumber: BlockNumberT,
+	Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen,
+	Sage: SageApi<
+		AccountId = AccountId,
+		AssetId = BattleMogsId,
+		Asset = BattleMogsAsset<BlockNumber>,
+		Balance = Balance,
+		BlockNumber = BlockNumber,
+		TransitionConfig = BattleMogsTransitionConfig,
+		HashOutput = H256,
+	>,
+	Events: GameEventHandler<AccountId, BattleMogsEvent>,
+	Fungibles: PaymentFungibles<Sage::FungiblesAssetId>,
+{
+	/// Allows other accounts to breed with `mogwai_id` for `price` during the next `duration`
+	/// blocks, replacing any previous offer.
+	pub(crate) fn offer_stud(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		price: u128,
+		duration: u32,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		ensure!(duration > 0, BattleMogsError::from(INVALID_STUD_OFFER));
+
+		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
+		let mogwai = asset.as_mogwai()?;
+		ensure!(mogwai.phase != PhaseType::Bred, BattleMogsError::from(MOGWAI_STILL_IN_BRED_PHASE));
+
+		let until = Sage::get_current_block_number().saturating_add(duration.into());
+		mogwai.stud = Some(StudOffer { price, until });
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::StudOffered { mogwai_id: *mogwai_id, price },
+		);
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(*mogwai_id, asset)])
+	}
+
+	pub(crate) fn revoke_stud(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let mut asset = Self::get_owned_mogwai(owner, mogwai_id)?;
+		let mogwai = asset.as_mogwai()?;
+		ensure!(mogwai.stud.take().is_some(), BattleMogsError::from(MOGWAI_NOT_OFFERED_AS_STUD));
+
+		Events::deposit_game_event(owner, BattleMogsEvent::StudRevoked { mogwai_id: *mogwai_id });
+
+		Ok(sp_std::vec![TransitionOutput::Mutated(*mogwai_id, asset)])
+	}
+
+	/// Withdraws all funds `mogwai_id` accumulated in `payment_asset`, which includes the
+	/// pairing and stud fees paid by the accounts breeding with it.
+	pub(crate) fn claim_stud_fees(
+		owner: &AccountId,
+		mogwai_id: &BattleMogsId,
+		payment_asset: Option<Sage::FungiblesAssetId>,
+	) -> Result<BattleMogsTransitionOutput<BlockNumber>, TransitionError> {
+		let _ = Self::get_owned_mogwai(owner, mogwai_id)?;
+
+		let funds = Self::inspect_asset_funds(mogwai_id, payment_asset.clone());
+		ensure!(!funds.is_zero(), BattleMogsError::from(MOGWAI_HAS_NO_FUNDS));
+		Self::withdraw_funds_from_asset(mogwai_id, owner, payment_asset, funds.clone())?;
+
+		Events::deposit_game_event(
+			owner,
+			BattleMogsEvent::StudFeesClaimed {
+				mogwai_id: *mogwai_id,
+				amount: funds.saturated_into(),
+			},
+		);
+
+		Ok(sp_std::vec![])
+	}
+}
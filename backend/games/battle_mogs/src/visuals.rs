@@ -0,0 +1,400 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Appearance of mogwais as encoded in the visuals segment of their DNA.
+//!
+//! Hatching runs [`Breeding::segmenting`](crate::algorithm), which records in every nibble
+//! of the visuals segment (`dna[1]`) how the matching part of the stats segment was
+//! inherited. The traits of a mogwai are read from fixed nibbles of that segment, so runtime
+//! and clients derive the same [`Appearance`] from the same DNA.
+
+use crate::asset::mogwai::{Mogwai, PhaseType};
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+
+/// How a part of the stats segment was inherited, as recorded by `Breeding::segmenting`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Inheritance {
+	/// Taken from the first segment and increased, code `0x4`.
+	FirstBoosted,
+	/// Taken from the first segment, code `0xA`.
+	First,
+	/// Both segments xor-ed, code `0x7`.
+	Blended,
+	/// Taken from the second segment and increased, code `0x8`.
+	SecondBoosted,
+	/// Taken from the second segment, code `0xB`.
+	Second,
+	/// The bits only the first segment has, decreased, code `0x1`.
+	FirstFaded,
+	/// The bits only the second segment has, decreased, code `0x0`.
+	SecondFaded,
+	/// Both segments or-ed and increased, code `0xC`.
+	Merged,
+	/// Taken from the random hash, code `0xF`.
+	Mutated,
+	/// Taken from the inverted random hash, code `0xE`.
+	Inverted,
+	/// Not written by segmenting, code `0x3` or any other.
+	Unset,
+}
+
+impl Inheritance {
+	pub fn from_code(code: u8) -> Self {
+		match code & 0x0F {
+			0x4 => Self::FirstBoosted,
+			0xA => Self::First,
+			0x7 => Self::Blended,
+			0x8 => Self::SecondBoosted,
+			0xB => Self::Second,
+			0x1 => Self::FirstFaded,
+			0x0 => Self::SecondFaded,
+			0xC => Self::Merged,
+			0xF => Self::Mutated,
+			0xE => Self::Inverted,
+			_ => Self::Unset,
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Body {
+	Round,
+	Slim,
+	Chubby,
+	Spiky,
+	Fluffy,
+}
+
+/// Colours of the palette, one for each kind of [`Inheritance`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Colour {
+	Gold,
+	Red,
+	Purple,
+	Silver,
+	Blue,
+	Pink,
+	Teal,
+	Green,
+	Black,
+	White,
+	Grey,
+}
+
+impl Colour {
+	pub fn rgb(&self) -> [u8; 3] {
+		match self {
+			Self::Gold => [0xE6, 0xB8, 0x2E],
+			Self::Red => [0xD6, 0x45, 0x3D],
+			Self::Purple => [0x8E, 0x5C, 0xC2],
+			Self::Silver => [0xBF, 0xC5, 0xCC],
+			Self::Blue => [0x3D, 0x7E, 0xD6],
+			Self::Pink => [0xF0, 0x8C, 0xB4],
+			Self::Teal => [0x2E, 0xA8, 0x9C],
+			Self::Green => [0x5C, 0xB8, 0x4A],
+			Self::Black => [0x2B, 0x2B, 0x30],
+			Self::White => [0xF5, 0xF5, 0xF0],
+			Self::Grey => [0x8A, 0x8A, 0x8A],
+		}
+	}
+}
+
+impl From<Inheritance> for Colour {
+	fn from(inheritance: Inheritance) -> Self {
+		match inheritance {
+			Inheritance::FirstBoosted => Self::Gold,
+			Inheritance::First => Self::Red,
+			Inheritance::Blended => Self::Purple,
+			Inheritance::SecondBoosted => Self::Silver,
+			Inheritance::Second => Self::Blue,
+			Inheritance::FirstFaded => Self::Pink,
+			Inheritance::SecondFaded => Self::Teal,
+			Inheritance::Merged => Self::Green,
+			Inheritance::Mutated => Self::Black,
+			Inheritance::Inverted => Self::White,
+			Inheritance::Unset => Self::Grey,
+		}
+	}
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+	pub primary: Colour,
+	pub secondary: Colour,
+	pub accent: Colour,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Pattern {
+	Plain,
+	Spots,
+	Stripes,
+	Patches,
+	Sparkles,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Eyes {
+	Round,
+	Sleepy,
+	Fierce,
+	Starry,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum Accessory {
+	Crown,
+	Horns,
+	Bow,
+	Scarf,
+}
+
+/// Typed appearance of a mogwai, derived from its DNA.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Appearance {
+	pub body: Body,
+	pub palette: Palette,
+	pub pattern: Pattern,
+	pub eyes: Eyes,
+	/// Accessories worn on the head and around the neck.
+	pub accessories: [Option<Accessory>; 2],
+}
+
+/// Nibbles of the visuals segment each trait is read from.
+const BODY: usize = 0;
+const PALETTE: [usize; 3] = [8, 9, 10];
+const PATTERN: usize = 16;
+const EYES: usize = 24;
+const ACCESSORIES: [usize; 2] = [32, 33];
+
+impl Appearance {
+	/// Appearance of `mogwai`, `None` while it is still an egg as its visuals are only
+	/// segmented on hatching.
+	pub fn of<BlockNumber>(mogwai: &Mogwai<BlockNumber>) -> Option<Self> {
+		match mogwai.phase {
+			PhaseType::None | PhaseType::Bred => None,
+			_ => Some(Self::from_dna(&mogwai.dna)),
+		}
+	}
+
+	pub fn from_dna(dna: &[[u8; 32]; 2]) -> Self {
+		let visuals = &dna[1];
+		let inheritance = |nibble: usize| {
+			let byte = visuals[nibble / 2];
+			Inheritance::from_code(if nibble % 2 == 0 { byte >> 4 } else { byte })
+		};
+
+		let body = match inheritance(BODY) {
+			Inheritance::FirstBoosted | Inheritance::First | Inheritance::FirstFaded => Body::Round,
+			Inheritance::SecondBoosted | Inheritance::Second | Inheritance::SecondFaded =>
+				Body::Slim,
+			Inheritance::Blended | Inheritance::Merged => Body::Chubby,
+			Inheritance::Mutated | Inheritance::Inverted => Body::Spiky,
+			Inheritance::Unset => Body::Fluffy,
+		};
+
+		let [primary, secondary, accent] = PALETTE.map(|nibble| inheritance(nibble).into());
+
+		let pattern = match inheritance(PATTERN) {
+			Inheritance::First | Inheritance::Second | Inheritance::Unset => Pattern::Plain,
+			Inheritance::Blended | Inheritance::Merged => Pattern::Spots,
+			Inheritance::FirstBoosted | Inheritance::SecondBoosted => Pattern::Stripes,
+			Inheritance::FirstFaded | Inheritance::SecondFaded => Pattern::Patches,
+			Inheritance::Mutated | Inheritance::Inverted => Pattern::Sparkles,
+		};
+
+		let eyes = match inheritance(EYES) {
+			Inheritance::FirstBoosted | Inheritance::First | Inheritance::Unset => Eyes::Round,
+			Inheritance::SecondBoosted |
+			Inheritance::Second |
+			Inheritance::FirstFaded |
+			Inheritance::SecondFaded => Eyes::Sleepy,
+			Inheritance::Blended | Inheritance::Merged => Eyes::Fierce,
+			Inheritance::Mutated | Inheritance::Inverted => Eyes::Starry,
+		};
+
+		// Only boosted or merged genes show as accessories.
+		let [head, neck] = ACCESSORIES.map(inheritance);
+		let head = match head {
+			Inheritance::FirstBoosted => Some(Accessory::Crown),
+			Inheritance::SecondBoosted | Inheritance::Merged => Some(Accessory::Horns),
+			_ => None,
+		};
+		let neck = match neck {
+			Inheritance::FirstBoosted => Some(Accessory::Bow),
+			Inheritance::SecondBoosted | Inheritance::Merged => Some(Accessory::Scarf),
+			_ => None,
+		};
+
+		Self {
+			body,
+			palette: Palette { primary, secondary, accent },
+			pattern,
+			eyes,
+			accessories: [head, neck],
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Appearance {
+	/// Renders the appearance as a 128 by 128 SVG image.
+	pub fn to_svg(&self) -> String {
+		let hex = |colour: Colour| {
+			let [r, g, b] = colour.rgb();
+			format!("#{r:02x}{g:02x}{b:02x}")
+		};
+		let (primary, secondary, accent) =
+			(hex(self.palette.primary), hex(self.palette.secondary), hex(self.palette.accent));
+
+		let mut svg = String::from(
+			r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 128 128" width="128" height="128">"#,
+		);
+
+		svg += &match self.body {
+			Body::Round => format!(r#"<circle cx="64" cy="72" r="40" fill="{primary}"/>"#),
+			Body::Slim => format!(r#"<ellipse cx="64" cy="72" rx="28" ry="44" fill="{primary}"/>"#),
+			Body::Chubby =>
+				format!(r#"<ellipse cx="64" cy="76" rx="48" ry="36" fill="{primary}"/>"#),
+			Body::Spiky => format!(
+				r#"<polygon points="64,24 80,44 104,40 96,64 112,84 88,92 80,116 64,100 48,116 40,92 16,84 32,64 24,40 48,44" fill="{primary}"/>"#
+			),
+			Body::Fluffy => format!(
+				r#"<g fill="{primary}"><circle cx="64" cy="72" r="36"/><circle cx="36" cy="60" r="16"/><circle cx="92" cy="60" r="16"/><circle cx="40" cy="96" r="16"/><circle cx="88" cy="96" r="16"/></g>"#
+			),
+		};
+
+		svg += &match self.pattern {
+			Pattern::Plain => String::new(),
+			Pattern::Spots => format!(
+				r#"<g fill="{secondary}"><circle cx="44" cy="88" r="6"/><circle cx="84" cy="92" r="5"/><circle cx="64" cy="104" r="4"/></g>"#
+			),
+			Pattern::Stripes => format!(
+				r#"<g fill="{secondary}"><rect x="40" y="84" width="48" height="5"/><rect x="44" y="96" width="40" height="5"/></g>"#
+			),
+			Pattern::Patches => format!(
+				r#"<g fill="{secondary}"><ellipse cx="46" cy="92" rx="12" ry="8"/><ellipse cx="86" cy="84" rx="10" ry="7"/></g>"#
+			),
+			Pattern::Sparkles => format!(
+				r#"<g fill="{secondary}"><circle cx="40" cy="84" r="2"/><circle cx="56" cy="100" r="2"/><circle cx="76" cy="88" r="2"/><circle cx="90" cy="102" r="2"/></g>"#
+			),
+		};
+
+		svg += &match self.eyes {
+			Eyes::Round => format!(
+				r#"<g fill="white"><circle cx="50" cy="64" r="9"/><circle cx="78" cy="64" r="9"/></g><g fill="{accent}"><circle cx="50" cy="64" r="4"/><circle cx="78" cy="64" r="4"/></g>"#
+			),
+			Eyes::Sleepy => format!(
+				r#"<g stroke="{accent}" stroke-width="3" stroke-linecap="round"><line x1="42" y1="64" x2="58" y2="64"/><line x1="70" y1="64" x2="86" y2="64"/></g>"#
+			),
+			Eyes::Fierce => format!(
+				r#"<g fill="{accent}"><polygon points="40,58 58,64 42,68"/><polygon points="88,58 70,64 86,68"/></g>"#
+			),
+			Eyes::Starry => format!(
+				r#"<g fill="{accent}"><polygon points="50,55 53,62 60,64 53,66 50,73 47,66 40,64 47,62"/><polygon points="78,55 81,62 88,64 81,66 78,73 75,66 68,64 75,62"/></g>"#
+			),
+		};
+
+		for accessory in self.accessories.iter().flatten() {
+			svg += &match accessory {
+				Accessory::Crown =>
+					format!(r#"<polygon points="44,36 52,22 64,32 76,22 84,36" fill="{accent}"/>"#),
+				Accessory::Horns => format!(
+					r#"<g fill="{accent}"><polygon points="40,40 34,16 52,34"/><polygon points="88,40 94,16 76,34"/></g>"#
+				),
+				Accessory::Bow => format!(
+					r#"<g fill="{accent}"><polygon points="64,82 48,74 48,90"/><polygon points="64,82 80,74 80,90"/></g>"#
+				),
+				Accessory::Scarf =>
+					format!(r#"<rect x="36" y="78" width="56" height="8" rx="4" fill="{accent}"/>"#),
+			};
+		}
+
+		svg += "</svg>";
+		svg
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::algorithm::Breeding;
+
+	#[test]
+	fn reads_inheritance_codes_from_nibbles() {
+		let mut dna = [[0; 32]; 2];
+		dna[1][BODY / 2] = 0x4A;
+		dna[1][PALETTE[0] / 2] = 0x7B;
+		dna[1][PALETTE[2] / 2] = 0xF0;
+		dna[1][PATTERN / 2] = 0xCC;
+		dna[1][EYES / 2] = 0xE0;
+		dna[1][ACCESSORIES[0] / 2] = 0x48;
+
+		assert_eq!(
+			Appearance::from_dna(&dna),
+			Appearance {
+				body: Body::Round,
+				palette: Palette {
+					primary: Colour::Purple,
+					secondary: Colour::Blue,
+					accent: Colour::Black
+				},
+				pattern: Pattern::Spots,
+				eyes: Eyes::Starry,
+				accessories: [Some(Accessory::Crown), Some(Accessory::Scarf)],
+			}
+		);
+	}
+
+	#[test]
+	fn unset_visuals_have_a_plain_appearance() {
+		let dna = Breeding::segmenting([[0; 32]; 2], [0; 32]);
+
+		assert_eq!(
+			Appearance::from_dna(&dna),
+			Appearance {
+				body: Body::Fluffy,
+				palette: Palette {
+					primary: Colour::Grey,
+					secondary: Colour::Grey,
+					accent: Colour::Grey
+				},
+				pattern: Pattern::Plain,
+				eyes: Eyes::Round,
+				accessories: [None, None],
+			}
+		);
+	}
+
+	#[test]
+	fn renders_svg() {
+		let mut dna = [[0; 32]; 2];
+		dna[1] = [0x4C; 32];
+		let svg = Appearance::from_dna(&dna).to_svg();
+
+		assert!(svg.starts_with("<svg") && svg.ends_with("</svg>"));
+		assert!(svg.contains("#e6b82e"));
+	}
+}
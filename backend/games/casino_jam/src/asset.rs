@@ -8,6 +8,7 @@ use sp_runtime::traits::BlockNumber as BlockNumberT;
 pub type AssetId = u32;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Asset<BlockNumber> {
 	pub id: AssetId,
 	pub collection_id: u8,
@@ -39,6 +40,7 @@ where
 				slot_c_result: (0, 0),
 				slot_d_result: (0, 0),
 				last_reward: 0,
+				gamble_count: 0,
 			})),
 		}
 	}
@@ -109,6 +111,27 @@ where
 			_ => Err(TransitionError::Transition { code: ASSET_VARIANT_IS_NOT_SEAT }),
 		}
 	}
+
+	/// Whether the asset is a seat whose rent has run out at `now`.
+	pub fn is_seat_rent_expired_at(&self, now: BlockNumber) -> bool {
+		match &self.variant {
+			AssetVariant::Seat(seat) => {
+				let rent_blocks = seat.rent_duration.get_rent_duration_blocks();
+				self.genesis.saturating_add(rent_blocks.into()) < now
+			},
+			_ => false,
+		}
+	}
+
+	/// Whether the asset is a seat that anyone can reclaim at `now` with
+	/// [`CasinoAction::Reclaim`], because either its reservation or its rent has expired.
+	pub fn is_reclaimable_seat_at(&self, now: BlockNumber) -> bool {
+		match &self.variant {
+			AssetVariant::Seat(seat) =>
+				seat.is_reservation_expired_at(now) || self.is_seat_rent_expired_at(now),
+			_ => false,
+		}
+	}
 }
 
 impl<BlockNumber> GetId<AssetId> for Asset<BlockNumber> {
@@ -118,6 +141,7 @@ impl<BlockNumber> GetId<AssetId> for Asset<BlockNumber> {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum VariantType {
 	Player(PlayerType),
 	Machine(MachineType),
@@ -125,6 +149,7 @@ pub enum VariantType {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum AssetVariant<BlockNumber> {
 	Player(PlayerVariant),
 	Machine(MachineVariant),
@@ -151,6 +176,7 @@ impl<BlockNumber> AssetVariant<BlockNumber> {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlayerVariant {
 	Human(HumanVariant),
 	Tracker(TrackerVariant),
@@ -173,6 +199,7 @@ impl PlayerVariant {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct HumanVariant {
 	pub seat_id: Option<AssetId>,
 }
@@ -192,15 +219,19 @@ impl HumanVariant {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct TrackerVariant {
 	pub slot_a_result: (u16, u8),
 	pub slot_b_result: (u16, u8),
 	pub slot_c_result: (u16, u8),
 	pub slot_d_result: (u16, u8),
 	pub last_reward: u32,
+	/// Lifetime number of gambles performed, not reset by `clear`.
+	pub gamble_count: u32,
 }
 
 impl TrackerVariant {
+	/// Clears the results of the last gamble, keeping the lifetime stats.
 	pub(crate) fn clear(&mut self) {
 		self.slot_a_result = (0, 0);
 		self.slot_b_result = (0, 0);
@@ -211,6 +242,7 @@ impl TrackerVariant {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct MachineVariant {
 	pub seat_linked: u8,
 	pub seat_limit: u8,
@@ -232,17 +264,20 @@ impl MachineVariant {
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum MachineSubVariant {
 	Bandit(BanditVariant),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct BanditVariant {
 	pub max_spins: u8,
 	pub jackpot: u32,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct SeatVariant<BlockNumber> {
 	pub rent_duration: RentDuration,
 	pub player_fee: u16,
@@ -274,4 +309,15 @@ where
 			false
 		}
 	}
+
+	/// Whether the seat is reserved by a player, whose reservation and grace period have both
+	/// passed at `now`.
+	pub fn is_reservation_expired_at(&self, now: BlockNumber) -> bool {
+		let reservation_blocks = self.reservation_duration.get_reservation_duration_blocks();
+		self.player_id.is_some() &&
+			self.reservation_start_block
+				.saturating_add(reservation_blocks.into())
+				.saturating_add(self.player_grace_period.into()) <
+				now
+	}
 }
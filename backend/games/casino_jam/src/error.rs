@@ -23,6 +23,7 @@ pub const SEAT_IS_NOT_LINED_TO_MACHINE: u8 = 176;
 pub const MACHINE_HAS_NO_LINKED_SEATS: u8 = 177;
 pub const SEAT_IS_STILL_LINKED_TO_PLAYER: u8 = 178;
 pub const MACHINE_STILL_HAS_LINKED_SEATS: u8 = 177;
+pub const SEAT_IS_NOT_RECLAIMABLE: u8 = 179;
 
 pub const ASSET_NOT_FOUND: u8 = 200;
 pub const ASSET_SCORE_NOT_ENOUGH: u8 = 201;
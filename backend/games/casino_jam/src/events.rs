@@ -0,0 +1,26 @@
+//! Events describing the outcome of CasinoJam transitions.
+//!
+//! Transitions only report which assets they minted, mutated or consumed. The events in here
+//! carry the game specific outcome on top of that, so that clients don't need to diff assets
+//! to find out what happened.
+
+use crate::asset::AssetId;
+
+use frame_support::pallet_prelude::{Decode, Encode, TypeInfo};
+use sp_std::vec::Vec;
+
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum CasinoJamEvent {
+	/// A player gambled on a bandit machine.
+	Gambled {
+		player_id: AssetId,
+		machine_id: AssetId,
+		/// Packed slot result of every spin, in the same format stored in the tracker.
+		spins: Vec<(u16, u8)>,
+		/// Reward paid for the spins, without jackpot and special rewards.
+		spin_reward: u32,
+		jackpot_reward: u32,
+		special_reward: u32,
+	},
+}
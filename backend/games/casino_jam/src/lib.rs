@@ -19,7 +19,9 @@
 pub mod asset;
 mod benchmarks;
 pub mod error;
+pub mod events;
 pub mod filter;
+pub mod migration;
 mod rules;
 pub mod transition;
 
@@ -27,9 +29,13 @@ pub mod transition;
 /// of the gameplay logic into a SAGE instance.
 pub mod prelude {
 	pub use crate::{
-		asset::{Asset, AssetId, AssetVariant, MachineSubVariant, MachineVariant, VariantType},
+		asset::{
+			Asset, AssetId, AssetVariant, MachineSubVariant, MachineVariant, PlayerVariant,
+			VariantType,
+		},
 		benchmarks::GameBenchmarkHelper,
 		error,
+		events::CasinoJamEvent,
 		filter::GameFilter,
 		transition::{
 			AssetType, CasinoAction, CasinoJamTransition, CasinoJamTransitionConfig, MachineType,
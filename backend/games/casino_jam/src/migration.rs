@@ -0,0 +1,138 @@
+//! Versioned asset layouts of CasinoJam.
+//!
+//! Assets are SCALE encoded into storage as is, so every change to their layout gets a new
+//! version here. Each version keeps the definitions of the layout it replaced in its own
+//! module, together with the conversion into the next layout, so that assets written with
+//! any previous layout can be upgraded to the current one.
+
+use crate::asset::{Asset, AssetVariant, PlayerVariant, TrackerVariant};
+
+use parity_scale_codec::{Decode, DecodeAll};
+
+/// Layout version of the assets as currently defined by this crate.
+pub const ASSET_LAYOUT_VERSION: u16 = 1;
+
+/// Decodes `encoded` as an asset of the given layout `version` and upgrades it to the
+/// current layout.
+///
+/// Returns `None` if the version is unknown or the bytes do not match its layout.
+pub fn upgrade_asset_from<BlockNumber: Decode>(
+	version: u16,
+	encoded: &[u8],
+) -> Option<Asset<BlockNumber>> {
+	let input = &mut &encoded[..];
+	match version {
+		0 => v0::Asset::<BlockNumber>::decode_all(input).ok().map(Into::into),
+		ASSET_LAYOUT_VERSION => Asset::<BlockNumber>::decode_all(input).ok(),
+		_ => None,
+	}
+}
+
+/// Layout before the tracker kept a lifetime gamble counter.
+pub mod v0 {
+	use crate::asset::{AssetId, HumanVariant, MachineVariant, SeatVariant};
+
+	use parity_scale_codec::{Decode, Encode};
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct Asset<BlockNumber> {
+		pub id: AssetId,
+		pub collection_id: u8,
+		pub genesis: BlockNumber,
+		pub variant: AssetVariant<BlockNumber>,
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum AssetVariant<BlockNumber> {
+		Player(PlayerVariant),
+		Machine(MachineVariant),
+		Seat(SeatVariant<BlockNumber>),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub enum PlayerVariant {
+		Human(HumanVariant),
+		Tracker(TrackerVariant),
+	}
+
+	#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode)]
+	pub struct TrackerVariant {
+		pub slot_a_result: (u16, u8),
+		pub slot_b_result: (u16, u8),
+		pub slot_c_result: (u16, u8),
+		pub slot_d_result: (u16, u8),
+		pub last_reward: u32,
+	}
+}
+
+impl<BlockNumber> From<v0::Asset<BlockNumber>> for Asset<BlockNumber> {
+	fn from(asset: v0::Asset<BlockNumber>) -> Self {
+		let variant = match asset.variant {
+			v0::AssetVariant::Player(v0::PlayerVariant::Human(human)) =>
+				AssetVariant::Player(PlayerVariant::Human(human)),
+			v0::AssetVariant::Player(v0::PlayerVariant::Tracker(tracker)) =>
+				AssetVariant::Player(PlayerVariant::Tracker(TrackerVariant {
+					slot_a_result: tracker.slot_a_result,
+					slot_b_result: tracker.slot_b_result,
+					slot_c_result: tracker.slot_c_result,
+					slot_d_result: tracker.slot_d_result,
+					last_reward: tracker.last_reward,
+					gamble_count: 0,
+				})),
+			v0::AssetVariant::Machine(machine) => AssetVariant::Machine(machine),
+			v0::AssetVariant::Seat(seat) => AssetVariant::Seat(seat),
+		};
+
+		Asset { id: asset.id, collection_id: asset.collection_id, genesis: asset.genesis, variant }
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use parity_scale_codec::Encode;
+
+	#[test]
+	fn upgrades_v0_tracker() {
+		let old = v0::Asset::<u32> {
+			id: 2,
+			collection_id: 1,
+			genesis: 10,
+			variant: v0::AssetVariant::Player(v0::PlayerVariant::Tracker(v0::TrackerVariant {
+				slot_a_result: (1, 2),
+				slot_b_result: (3, 4),
+				slot_c_result: (5, 6),
+				slot_d_result: (7, 8),
+				last_reward: 9,
+			})),
+		};
+
+		let mut expected = Asset::<u32>::new_tracker(2, 10);
+		if let AssetVariant::Player(PlayerVariant::Tracker(tracker)) = &mut expected.variant {
+			tracker.slot_a_result = (1, 2);
+			tracker.slot_b_result = (3, 4);
+			tracker.slot_c_result = (5, 6);
+			tracker.slot_d_result = (7, 8);
+			tracker.last_reward = 9;
+		}
+
+		assert_eq!(upgrade_asset_from::<u32>(0, &old.encode()), Some(expected));
+		// A v0 tracker is too short to be mistaken for the current layout.
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &old.encode()), None);
+	}
+
+	#[test]
+	fn keeps_assets_without_layout_changes() {
+		let machine = Asset::<u32>::new_bandit_machine(1, 10);
+		let seat = Asset::<u32>::new_seat(3, 10, 1, crate::transition::RentDuration::Day1);
+
+		for asset in [machine, seat] {
+			assert_eq!(upgrade_asset_from::<u32>(0, &asset.encode()), Some(asset));
+			assert_eq!(
+				upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION, &asset.encode()),
+				Some(asset)
+			);
+		}
+		assert_eq!(upgrade_asset_from::<u32>(ASSET_LAYOUT_VERSION + 1, &machine.encode()), None);
+	}
+}
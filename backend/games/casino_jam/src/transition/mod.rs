@@ -1,11 +1,13 @@
 use crate::{
 	asset::{Asset, AssetId, VariantType},
 	error::*,
+	events::CasinoJamEvent,
 	rules::*,
 	transition::utils::CasinoJamUtils,
 };
 
 use ajuna_primitives::{payment_handler::NativeId, sage_api::SageApi};
+use game_primitives::GameEventHandler;
 use sage_api::{rules::*, traits::TransitionOutput, SageGameTransition, TransitionError};
 
 use frame_support::{
@@ -27,12 +29,14 @@ pub use enums::*;
 pub(crate) use utils::*;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum AssetType {
 	Player,
 	Machine(MachineType),
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub enum CasinoAction {
 	Create(AssetType),
 	Deposit(AssetType, TokenType),
@@ -43,18 +47,21 @@ pub enum CasinoAction {
 	Release,
 	Kick,
 	Return,
+	Reclaim,
 }
 
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Debug, Default, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct CasinoJamTransitionConfig {
 	pub reward_multiplier: u8,
 }
 
-pub struct CasinoJamTransition<AccountId, BlockNumber, Sage> {
-	_phantom: PhantomData<(AccountId, BlockNumber, Sage)>,
+pub struct CasinoJamTransition<AccountId, BlockNumber, Sage, Events = ()> {
+	_phantom: PhantomData<(AccountId, BlockNumber, Sage, Events)>,
 }
 
-impl<AccountId, BlockNumber, Balance, Sage> CasinoJamTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events>
+	CasinoJamTransition<AccountId, BlockNumber, Sage, Events>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -68,6 +75,7 @@ where
 		TransitionConfig = CasinoJamTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, CasinoJamEvent>,
 {
 	fn try_get_asset(asset_id: &AssetId) -> Result<Asset<BlockNumber>, TransitionError> {
 		let asset = Sage::get_asset(asset_id)
@@ -215,6 +223,19 @@ where
 				ensure_asset_type_at(&assets, VariantType::Machine(MachineType::Bandit), 0)?;
 				ensure_asset_type_at(&assets, VariantType::Seat, 1)?;
 
+				maybe_assets = Some(assets);
+			},
+			CasinoAction::Reclaim => {
+				let assets = Self::try_get_assets(asset_ids)?;
+
+				// The player only needs to be passed when the seat is still reserved.
+				if asset_ids.len() != 2 {
+					ensure_asset_length(asset_ids, 3)?;
+					ensure_asset_type_at(&assets, VariantType::Player(PlayerType::Human), 2)?;
+				}
+				ensure_asset_type_at(&assets, VariantType::Seat, 0)?;
+				ensure_asset_type_at(&assets, VariantType::Machine(MachineType::Bandit), 1)?;
+
 				maybe_assets = Some(assets);
 			},
 		}
@@ -390,6 +411,7 @@ where
 				// Then we register the spin result into the tracker
 				{
 					let tracker = player_tracker.try_as_tracker()?;
+					tracker.gamble_count = tracker.gamble_count.saturating_add(1);
 
 					for (i, spin) in full_spins.spin_results.iter().enumerate() {
 						match i {
@@ -414,6 +436,21 @@ where
 				seat.last_action_block =
 					current_block.saturating_sub(seat.reservation_start_block).saturated_into();
 
+				Events::deposit_game_event(
+					account_id,
+					CasinoJamEvent::Gambled {
+						player_id: human_id,
+						machine_id: bandit_id,
+						spins: full_spins.spin_results.iter().map(SpinResult::get_packed).collect(),
+						spin_reward: full_spins
+							.spin_results
+							.iter()
+							.fold(0_u32, |acc, spin| acc.saturating_add(spin.reward)),
+						jackpot_reward: full_spins.jackpot_reward,
+						special_reward: full_spins.special_reward,
+					},
+				);
+
 				sp_std::vec![
 					TransitionOutput::Mutated(human_id, human_asset),
 					TransitionOutput::Mutated(tracker_id, tracker_asset),
@@ -684,14 +721,81 @@ where
 					TransitionOutput::Consumed(seat_id),
 				]
 			},
+			CasinoAction::Reclaim => {
+				let maybe_player = if assets.len() == 3 { assets.pop() } else { None };
+				let (machine_id, mut machine_asset) =
+					assets.pop().ok_or(TransitionError::Transition { code: ASSET_NOT_FOUND })?;
+				let (seat_id, mut seat_asset) =
+					assets.pop().ok_or(TransitionError::Transition { code: ASSET_NOT_FOUND })?;
+
+				let current_block = Sage::get_current_block_number();
+				let is_rent_expired = seat_asset.is_seat_rent_expired_at(current_block);
+				let seat = seat_asset.try_as_seat()?;
+
+				if seat.machine_id != Some(machine_id) {
+					return Err(TransitionError::Transition { code: SEAT_IS_NOT_LINED_TO_MACHINE });
+				}
+
+				let mut output = Vec::new();
+				match (seat.player_id, maybe_player) {
+					(Some(player_id), Some((human_id, mut human_asset))) => {
+						let human = human_asset.try_as_player()?.try_as_human()?;
+						if player_id != human_id || !human.is_linked_to(seat_id) {
+							return Err(TransitionError::Transition {
+								code: SEAT_IS_NOT_LINKED_TO_SPECIFIED_PLAYER,
+							});
+						}
+						if !is_rent_expired && !seat.is_reservation_expired_at(current_block) {
+							return Err(TransitionError::Transition {
+								code: SEAT_IS_NOT_RECLAIMABLE,
+							});
+						}
+
+						// The reservation fee stays on the seat, it goes to the machine once
+						// the seat is returned.
+						human.release();
+						seat.release();
+
+						output.push(TransitionOutput::Mutated(human_id, human_asset));
+					},
+					(None, None) =>
+						if !is_rent_expired {
+							return Err(TransitionError::Transition {
+								code: SEAT_IS_NOT_RECLAIMABLE,
+							});
+						},
+					_ =>
+						return Err(TransitionError::Transition {
+							code: SEAT_IS_NOT_LINKED_TO_SPECIFIED_PLAYER,
+						}),
+				}
+
+				if is_rent_expired {
+					let machine = machine_asset.try_as_machine()?;
+					machine.seat_linked = machine.seat_linked.saturating_sub(1);
+
+					let seat_funds = Self::get_asset_funds(&seat_id, payment_asset.as_ref());
+					if !seat_funds.is_zero() {
+						Self::withdraw_funds_from_asset(&seat_id, account_id, seat_funds.clone())?;
+						Self::deposit_funds_to_asset(&machine_id, account_id, seat_funds)?;
+					}
+
+					output.push(TransitionOutput::Mutated(machine_id, machine_asset));
+					output.push(TransitionOutput::Consumed(seat_id));
+				} else {
+					output.push(TransitionOutput::Mutated(seat_id, seat_asset));
+				}
+
+				output
+			},
 		};
 
 		Ok(output)
 	}
 }
 
-impl<AccountId, BlockNumber, Balance, Sage> SageGameTransition
-	for CasinoJamTransition<AccountId, BlockNumber, Sage>
+impl<AccountId, BlockNumber, Balance, Sage, Events> SageGameTransition
+	for CasinoJamTransition<AccountId, BlockNumber, Sage, Events>
 where
 	AccountId: Member + Codec,
 	BlockNumber: BlockNumberT,
@@ -705,6 +809,7 @@ where
 		TransitionConfig = CasinoJamTransitionConfig,
 		HashOutput = H256,
 	>,
+	Events: GameEventHandler<AccountId, CasinoJamEvent>,
 {
 	type TransitionId = CasinoAction;
 	type TransitionConfig = CasinoJamTransitionConfig;
@@ -0,0 +1,26 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+/// Receives the events emitted while executing transitions.
+pub trait GameEventHandler<AccountId, Event> {
+	fn deposit_game_event(account: &AccountId, event: Event);
+}
+
+impl<AccountId, Event> GameEventHandler<AccountId, Event> for () {
+	fn deposit_game_event(_: &AccountId, _: Event) {}
+}
@@ -1,8 +1,10 @@
-use sage_playground_runtime::{AccountId, Signature, WASM_BINARY};
+use sage_playground_runtime::{
+	configs::SageAssetId, AccountId, Balance, BlockNumber, Signature, WASM_BINARY,
+};
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{crypto::Ss58Codec, sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
 
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
@@ -25,6 +27,12 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
+/// Parse an account from its SS58 address, or generate it from a dev seed such as `Alice`.
+pub fn parse_account_id(account: &str) -> AccountId {
+	AccountId::from_ss58check(account)
+		.unwrap_or_else(|_| get_account_id_from_seed::<sr25519::Public>(account))
+}
+
 /// Generate an Aura authority key.
 pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
@@ -44,13 +52,15 @@ pub fn development_config() -> Result<ChainSpec, String> {
 		// Sudo account
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		// Pre-funded accounts
-		vec![
+		endowed_with_default_balance(vec![
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			get_account_id_from_seed::<sr25519::Public>("Bob"),
 			get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 			get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-		],
-		true,
+		]),
+		GameGenesis::development(),
+		GameGenesis::development(),
+		vec![],
 	))
 	.build())
 }
@@ -69,7 +79,7 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 		// Sudo account
 		get_account_id_from_seed::<sr25519::Public>("Alice"),
 		// Pre-funded accounts
-		vec![
+		endowed_with_default_balance(vec![
 			get_account_id_from_seed::<sr25519::Public>("Alice"),
 			get_account_id_from_seed::<sr25519::Public>("Bob"),
 			get_account_id_from_seed::<sr25519::Public>("Charlie"),
@@ -82,26 +92,62 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 			get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
 			get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 			get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-		],
-		true,
+		]),
+		GameGenesis::development(),
+		GameGenesis::development(),
+		vec![],
 	))
 	.build())
 }
 
+/// Organizer and first season of a SAGE game at genesis.
+#[derive(Clone, Debug)]
+pub struct GameGenesis {
+	pub organizer: AccountId,
+	pub season_id: u32,
+	pub season_duration: BlockNumber,
+}
+
+impl GameGenesis {
+	/// Alice organizing a first season of ~4 hours.
+	pub fn development() -> Self {
+		Self {
+			organizer: get_account_id_from_seed::<sr25519::Public>("Alice"),
+			season_id: 0,
+			season_duration: 10 * 60 * 4,
+		}
+	}
+}
+
+/// An asset of `pallet_assets` created at genesis, which the games accept as payment.
+#[derive(Clone, Debug)]
+pub struct PaymentAsset {
+	pub id: SageAssetId,
+	pub owner: AccountId,
+	pub name: String,
+	pub symbol: String,
+	pub decimals: u8,
+	pub min_balance: Balance,
+	pub is_sufficient: bool,
+	pub balances: Vec<(AccountId, Balance)>,
+}
+
+fn endowed_with_default_balance(accounts: Vec<AccountId>) -> Vec<(AccountId, Balance)> {
+	accounts.into_iter().map(|account| (account, 1 << 60)).collect()
+}
+
 /// Configure initial storage state for FRAME modules.
-fn testnet_genesis(
+pub fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
-	endowed_accounts: Vec<AccountId>,
-	_enable_println: bool,
+	endowed_accounts: Vec<(AccountId, Balance)>,
+	casino_jam: GameGenesis,
+	battle_mogs: GameGenesis,
+	payment_assets: Vec<PaymentAsset>,
 ) -> serde_json::Value {
-	let genesis_season_id: u32 = 0;
-	let genesis_season_duration: u32 = 10 * 60 * 4; // ~4 Hours
-
 	serde_json::json!({
 		"balances": {
-			// Configure endowed accounts with initial balance of 1 << 60.
-			"balances": endowed_accounts.iter().cloned().map(|k| (k, 1u64 << 60)).collect::<Vec<_>>(),
+			"balances": endowed_accounts,
 		},
 		"aura": {
 			"authorities": initial_authorities.iter().map(|x| (x.0.clone())).collect::<Vec<_>>(),
@@ -113,19 +159,35 @@ fn testnet_genesis(
 			// Assign network admin rights.
 			"key": Some(root_key),
 		},
+		"sageAssets": {
+			"assets": payment_assets
+				.iter()
+				.map(|asset| (asset.id, asset.owner.clone(), asset.is_sufficient, asset.min_balance))
+				.collect::<Vec<_>>(),
+			"metadata": payment_assets
+				.iter()
+				.map(|asset| (asset.id, asset.name.as_bytes(), asset.symbol.as_bytes(), asset.decimals))
+				.collect::<Vec<_>>(),
+			"accounts": payment_assets
+				.iter()
+				.flat_map(|asset| {
+					asset.balances.iter().map(|(account, balance)| (asset.id, account.clone(), *balance))
+				})
+				.collect::<Vec<_>>(),
+		},
 		"sageCasinoJam": {
-			"organizer": Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
-			"season": Some(genesis_season_id)
+			"organizer": Some(casino_jam.organizer),
+			"season": Some(casino_jam.season_id)
 		},
 		"sageCasinoJamSeasons": {
-			"season": Some((genesis_season_id, genesis_season_duration))
+			"season": Some((casino_jam.season_id, casino_jam.season_duration))
 		},
 		"sageBattleMogs": {
-			"organizer": Some(get_account_id_from_seed::<sr25519::Public>("Alice")),
-			"season": Some(genesis_season_id)
+			"organizer": Some(battle_mogs.organizer),
+			"season": Some(battle_mogs.season_id)
 		},
 		"sageBattleMogsSeasons": {
-			"season": Some((genesis_season_id, genesis_season_duration))
+			"season": Some((battle_mogs.season_id, battle_mogs.season_duration))
 		},
 	})
 }
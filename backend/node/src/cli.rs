@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::str::FromStr;
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +8,47 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Author blocks with manual seal instead of Aura and Grandpa, for local development.
+	///
+	/// Accepts `instant` to seal a block for each incoming transaction, `manual` to seal only
+	/// when requested through the `engine_*` RPCs, or a block time in milliseconds.
+	#[arg(long)]
+	pub sealing: Option<Sealing>,
+
+	/// Index the history of the SAGE games into a SQLite database in the chain's base path,
+	/// and serve it through the `sage_accountHistory`, `sage_assetHistory` and
+	/// `sage_leaderboard` RPCs.
+	#[arg(long)]
+	pub sage_indexer: bool,
+}
+
+/// How blocks are sealed when running with `--sealing`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Sealing {
+	/// Seal a block as soon as a transaction enters the pool.
+	Instant,
+	/// Seal blocks only on request.
+	Manual,
+	/// Seal a block every given number of milliseconds.
+	Interval(u64),
+}
+
+impl FromStr for Sealing {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"instant" => Ok(Self::Instant),
+			"manual" => Ok(Self::Manual),
+			millis => match millis.parse::<u64>() {
+				Ok(millis) if millis > 0 => Ok(Self::Interval(millis)),
+				_ => Err(format!(
+					"expected `instant`, `manual` or a block time in milliseconds, got `{s}`"
+				)),
+			},
+		}
+	}
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -43,4 +85,17 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Run try-runtime checks against the state of a chain spec or an exported state.
+	#[cfg(feature = "try-runtime")]
+	#[command(subcommand)]
+	TryRuntime(crate::try_runtime::TryRuntimeCmd),
+
+	/// Run try-runtime checks, which requires building the node with `--features try-runtime`.
+	#[cfg(not(feature = "try-runtime"))]
+	TryRuntime,
+
+	/// Tooling for the SAGE games.
+	#[command(subcommand)]
+	Sage(crate::sage::SageSubcommand),
 }
@@ -0,0 +1,31 @@
+//! Decoding of runtime events on the node side, shared by the indexer and the metrics.
+
+use crate::service::FullClient;
+use parity_scale_codec::Decode;
+use sage_playground_runtime::{opaque::Hash, RuntimeEvent};
+use sc_client_api::StorageProvider;
+use sp_core::storage::StorageKey;
+
+pub type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
+
+/// Reads the events deposited in the block `hash`.
+pub fn block_events(client: &FullClient, hash: Hash) -> Result<Vec<EventRecord>, String> {
+	let key = StorageKey(frame_support::storage::storage_prefix(b"System", b"Events").to_vec());
+	match client.storage(hash, &key).map_err(|e| e.to_string())? {
+		Some(data) => Vec::<EventRecord>::decode(&mut &data.0[..])
+			.map_err(|e| format!("Undecodable events: {e}")),
+		None => Ok(Vec::new()),
+	}
+}
+
+/// Splits an externally tagged enum variant, as serialized by serde, into its name and fields.
+pub fn split_variant(value: serde_json::Value) -> (String, serde_json::Value) {
+	match value {
+		serde_json::Value::String(kind) => (kind, serde_json::Value::Null),
+		serde_json::Value::Object(map) if map.len() == 1 => {
+			let (kind, data) = map.into_iter().next().expect("map has one entry; qed");
+			(kind, data)
+		},
+		other => ("Unknown".into(), other),
+	}
+}
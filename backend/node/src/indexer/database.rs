@@ -0,0 +1,304 @@
+//! SQLite storage of the indexed game history.
+
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+use std::{path::Path, sync::Mutex};
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS blocks (
+		number INTEGER PRIMARY KEY,
+		hash BLOB NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS events (
+		id INTEGER PRIMARY KEY AUTOINCREMENT,
+		block_number INTEGER NOT NULL REFERENCES blocks(number) ON DELETE CASCADE,
+		extrinsic_index INTEGER,
+		game TEXT NOT NULL,
+		account TEXT NOT NULL,
+		kind TEXT NOT NULL,
+		data TEXT NOT NULL
+	);
+	CREATE INDEX IF NOT EXISTS events_by_account ON events(account, id);
+	CREATE INDEX IF NOT EXISTS events_by_game ON events(game, block_number);
+	CREATE TABLE IF NOT EXISTS event_assets (
+		event_id INTEGER NOT NULL REFERENCES events(id) ON DELETE CASCADE,
+		game TEXT NOT NULL,
+		asset_id INTEGER NOT NULL
+	);
+	CREATE INDEX IF NOT EXISTS event_assets_by_asset ON event_assets(game, asset_id, event_id);
+";
+
+/// The game an event belongs to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Game {
+	CasinoJam,
+	BattleMogs,
+}
+
+impl Game {
+	fn as_str(&self) -> &'static str {
+		match self {
+			Game::CasinoJam => "casino_jam",
+			Game::BattleMogs => "battle_mogs",
+		}
+	}
+
+	fn from_str(game: &str) -> rusqlite::Result<Self> {
+		match game {
+			"casino_jam" => Ok(Game::CasinoJam),
+			"battle_mogs" => Ok(Game::BattleMogs),
+			other => Err(rusqlite::Error::InvalidColumnType(
+				0,
+				other.to_string(),
+				rusqlite::types::Type::Text,
+			)),
+		}
+	}
+
+	/// SQL expression scoring a single event of the game for the leaderboard.
+	///
+	/// CasinoJam players are ranked by the rewards they won, BattleMogs players by the
+	/// achievements they completed.
+	fn score_expression(&self) -> &'static str {
+		match self {
+			Game::CasinoJam =>
+				"CASE kind WHEN 'Gambled' THEN json_extract(data, '$.spin_reward') + \
+				 json_extract(data, '$.jackpot_reward') + json_extract(data, '$.special_reward') \
+				 ELSE 0 END",
+			Game::BattleMogs => "CASE kind WHEN 'AchievementCompleted' THEN 1 ELSE 0 END",
+		}
+	}
+}
+
+/// An event decoded from a block, ready to be stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedEvent {
+	pub extrinsic_index: Option<u32>,
+	pub game: Game,
+	/// SS58 address of the account that executed the transition.
+	pub account: String,
+	/// Name of the event variant, e.g. `Gambled` or `MogwaiBred`.
+	pub kind: String,
+	/// Fields of the event variant.
+	pub data: serde_json::Value,
+	/// Assets the event refers to.
+	pub asset_ids: Vec<u64>,
+}
+
+/// An event of the history, as returned by the `sage_*History` RPCs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+	/// Position of the event in the history, to be used as `before` cursor when paging.
+	pub id: u64,
+	pub block_number: u32,
+	pub block_hash: H256,
+	pub extrinsic_index: Option<u32>,
+	pub game: Game,
+	pub account: String,
+	pub kind: String,
+	pub data: serde_json::Value,
+}
+
+/// Ranking of the players of a game at a given block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Leaderboard {
+	/// The block up to which events have been taken into account.
+	pub block_number: u32,
+	pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaderboardEntry {
+	pub rank: u32,
+	pub account: String,
+	pub score: u64,
+	/// Number of game events of the account, used to break ties.
+	pub events: u64,
+}
+
+/// Connection to the indexer database, shared between the indexer task and the RPCs.
+pub struct Database {
+	connection: Mutex<Connection>,
+}
+
+impl Database {
+	/// Opens the database at `path`, creating it if needed.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		Self::with_connection(Connection::open(path)?)
+	}
+
+	fn with_connection(connection: Connection) -> rusqlite::Result<Self> {
+		connection.pragma_update(None, "journal_mode", "WAL")?;
+		connection.pragma_update(None, "foreign_keys", true)?;
+		connection.execute_batch(SCHEMA)?;
+		Ok(Self { connection: Mutex::new(connection) })
+	}
+
+	fn connection(&self) -> std::sync::MutexGuard<'_, Connection> {
+		self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	/// The highest indexed block.
+	pub fn last_block(&self) -> rusqlite::Result<Option<(u32, H256)>> {
+		self.connection()
+			.query_row("SELECT number, hash FROM blocks ORDER BY number DESC LIMIT 1", [], |row| {
+				Ok((row.get(0)?, H256::from_slice(&row.get::<_, Vec<u8>>(1)?)))
+			})
+			.optional()
+	}
+
+	/// The hash of the indexed block at `number`.
+	pub fn block_hash(&self, number: u32) -> rusqlite::Result<Option<H256>> {
+		self.connection()
+			.query_row("SELECT hash FROM blocks WHERE number = ?1", [number], |row| {
+				Ok(H256::from_slice(&row.get::<_, Vec<u8>>(0)?))
+			})
+			.optional()
+	}
+
+	/// Stores the events of the block `number`.
+	///
+	/// Blocks are indexed along the best chain, so any block at or above `number` belongs to a
+	/// retracted fork and is removed along with its events.
+	pub fn insert_block(
+		&self,
+		number: u32,
+		hash: H256,
+		events: &[IndexedEvent],
+	) -> rusqlite::Result<()> {
+		let mut connection = self.connection();
+		let tx = connection.transaction()?;
+		tx.execute("DELETE FROM blocks WHERE number >= ?1", [number])?;
+		tx.execute(
+			"INSERT INTO blocks (number, hash) VALUES (?1, ?2)",
+			params![number, hash.as_bytes()],
+		)?;
+
+		for event in events {
+			tx.execute(
+				"INSERT INTO events (block_number, extrinsic_index, game, account, kind, data) \
+				 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+				params![
+					number,
+					event.extrinsic_index,
+					event.game.as_str(),
+					event.account,
+					event.kind,
+					event.data.to_string(),
+				],
+			)?;
+			let event_id = tx.last_insert_rowid();
+			for asset_id in &event.asset_ids {
+				tx.execute(
+					"INSERT INTO event_assets (event_id, game, asset_id) VALUES (?1, ?2, ?3)",
+					params![event_id, event.game.as_str(), asset_id],
+				)?;
+			}
+		}
+
+		tx.commit()
+	}
+
+	/// Events of `account`, newest first, optionally restricted to a single game.
+	pub fn account_history(
+		&self,
+		account: &str,
+		game: Option<Game>,
+		before: Option<u64>,
+		limit: u32,
+	) -> rusqlite::Result<Vec<HistoryEntry>> {
+		self.history(
+			"WHERE e.account = ?1 AND (?2 IS NULL OR e.game = ?2) AND e.id < ?3",
+			params![account, game.map(|game| game.as_str()), cursor(before), limit],
+		)
+	}
+
+	/// Events referring to the asset `asset_id` of `game`, newest first.
+	pub fn asset_history(
+		&self,
+		game: Game,
+		asset_id: u64,
+		before: Option<u64>,
+		limit: u32,
+	) -> rusqlite::Result<Vec<HistoryEntry>> {
+		self.history(
+			"JOIN event_assets a ON a.event_id = e.id \
+			 WHERE a.asset_id = ?1 AND a.game = ?2 AND e.id < ?3",
+			params![asset_id, game.as_str(), cursor(before), limit],
+		)
+	}
+
+	fn history(
+		&self,
+		filter: &str,
+		params: impl rusqlite::Params,
+	) -> rusqlite::Result<Vec<HistoryEntry>> {
+		let connection = self.connection();
+		let mut statement = connection.prepare(&format!(
+			"SELECT e.id, e.block_number, b.hash, e.extrinsic_index, e.game, e.account, e.kind, \
+			 e.data FROM events e JOIN blocks b ON b.number = e.block_number {filter} \
+			 ORDER BY e.id DESC LIMIT ?4"
+		))?;
+		let entries = statement.query_map(params, |row| {
+			Ok(HistoryEntry {
+				id: row.get(0)?,
+				block_number: row.get(1)?,
+				block_hash: H256::from_slice(&row.get::<_, Vec<u8>>(2)?),
+				extrinsic_index: row.get(3)?,
+				game: Game::from_str(&row.get::<_, String>(4)?)?,
+				account: row.get(5)?,
+				kind: row.get(6)?,
+				data: serde_json::from_str(&row.get::<_, String>(7)?).map_err(|e| {
+					rusqlite::Error::FromSqlConversionFailure(
+						7,
+						rusqlite::types::Type::Text,
+						Box::new(e),
+					)
+				})?,
+			})
+		})?;
+		entries.collect()
+	}
+
+	/// Ranks the players of `game` by the events indexed up to block `at`, or up to the last
+	/// indexed block if `at` is not given.
+	pub fn leaderboard(
+		&self,
+		game: Game,
+		at: Option<u32>,
+		limit: u32,
+	) -> rusqlite::Result<Leaderboard> {
+		let last_block = self.last_block()?.map(|(number, _)| number).unwrap_or_default();
+		let block_number = at.map_or(last_block, |at| at.min(last_block));
+
+		let connection = self.connection();
+		let mut statement = connection.prepare(&format!(
+			"SELECT account, SUM({}) AS score, COUNT(*) AS events FROM events \
+			 WHERE game = ?1 AND kind != 'TransitionExecuted' AND block_number <= ?2 \
+			 GROUP BY account ORDER BY score DESC, events DESC, account LIMIT ?3",
+			game.score_expression()
+		))?;
+		let entries = statement
+			.query_map(params![game.as_str(), block_number, limit], |row| {
+				Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+			})?
+			.zip(1..)
+			.map(|(row, rank)| {
+				let (account, score, events) = row?;
+				Ok(LeaderboardEntry { rank, account, score, events })
+			})
+			.collect::<rusqlite::Result<_>>()?;
+
+		Ok(Leaderboard { block_number, entries })
+	}
+}
+
+/// Event ids start at 1, so without a cursor the history starts at the newest event.
+fn cursor(before: Option<u64>) -> i64 {
+	before.map_or(i64::MAX, |before| before.min(i64::MAX as u64) as i64)
+}
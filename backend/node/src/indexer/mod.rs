@@ -0,0 +1,206 @@
+//! Indexer of the SAGE game history, enabled with `--sage-indexer`.
+//!
+//! Follows the best chain, decodes the `pallet_sage` and game events of CasinoJam and
+//! BattleMogs, and stores them in a SQLite database next to the chain database. The history is
+//! served by the `sage_*` RPCs in [`crate::rpc::indexer`].
+
+mod database;
+
+pub use database::{Database, Game, HistoryEntry, Leaderboard};
+
+use database::IndexedEvent;
+
+use crate::{
+	events::{block_events, split_variant, EventRecord},
+	service::FullClient,
+};
+use futures::StreamExt;
+use game_battle_mogs::{prelude::BattleMogsEvent, BattleMogsAction};
+use game_casino_jam::prelude::CasinoJamEvent;
+use sage_playground_runtime::{opaque::Hash, BlockNumber, RuntimeEvent};
+use sc_client_api::BlockchainEvents;
+use sp_blockchain::HeaderBackend;
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::traits::Header;
+use std::sync::Arc;
+
+const LOG_TARGET: &str = "sage-indexer";
+
+/// Indexes the blocks of the best chain into `database` until the node shuts down.
+///
+/// Blocks that were imported while the indexer wasn't running are indexed first.
+pub async fn run(client: Arc<FullClient>, database: Arc<Database>) {
+	// Subscribe before catching up, so that no block is missed in between.
+	let mut imports = client.import_notification_stream();
+
+	if let Err(error) = catch_up(&client, &database) {
+		log::error!(target: LOG_TARGET, "Failed to catch up with the best chain: {error}");
+	}
+
+	while let Some(notification) = imports.next().await {
+		if !notification.is_new_best {
+			continue;
+		}
+
+		let enacted = notification
+			.tree_route
+			.iter()
+			.flat_map(|route| route.enacted().iter().map(|block| (block.number, block.hash)));
+		let best = std::iter::once((*notification.header.number(), notification.hash));
+
+		for (number, hash) in enacted.chain(best) {
+			if let Err(error) = index_block(&client, &database, number, hash) {
+				log::error!(target: LOG_TARGET, "Failed to index block #{number} ({hash}): {error}");
+			}
+		}
+	}
+}
+
+fn catch_up(client: &FullClient, database: &Database) -> Result<(), String> {
+	let mut next = match database.last_block().map_err(|e| e.to_string())? {
+		Some((mut number, _)) => {
+			// Walk back to the last indexed block that is still part of the best chain.
+			while number > 0 &&
+				database.block_hash(number).map_err(|e| e.to_string())? !=
+					client.hash(number).map_err(|e| e.to_string())?
+			{
+				number -= 1;
+			}
+			number + 1
+		},
+		None => 0,
+	};
+
+	let best = client.info().best_number;
+	if next <= best {
+		log::info!(target: LOG_TARGET, "Indexing blocks #{next} to #{best}");
+	}
+	while next <= best {
+		let hash = client
+			.hash(next)
+			.map_err(|e| e.to_string())?
+			.ok_or_else(|| format!("Block #{next} is missing"))?;
+		index_block(client, database, next, hash)?;
+		next += 1;
+	}
+
+	Ok(())
+}
+
+fn index_block(
+	client: &FullClient,
+	database: &Database,
+	number: BlockNumber,
+	hash: Hash,
+) -> Result<(), String> {
+	// Catching up and following imports may both hand over the same block.
+	if database.block_hash(number).map_err(|e| e.to_string())? == Some(hash) {
+		return Ok(());
+	}
+
+	let events = block_events(client, hash)?
+		.into_iter()
+		.filter_map(indexed_event)
+		.collect::<Vec<_>>();
+	database.insert_block(number, hash, &events).map_err(|e| e.to_string())
+}
+
+fn indexed_event(record: EventRecord) -> Option<IndexedEvent> {
+	let extrinsic_index = match record.phase {
+		frame_system::Phase::ApplyExtrinsic(index) => Some(index),
+		_ => None,
+	};
+
+	let (game, account, event, asset_ids) = match record.event {
+		RuntimeEvent::SageCasinoJam(pallet_sage::Event::TransitionExecuted { account, id }) =>
+			(Game::CasinoJam, account, transition_executed(id), Vec::new()),
+		RuntimeEvent::SageBattleMogs(pallet_sage::Event::TransitionExecuted { account, id }) => {
+			let asset_ids = battle_mogs_action_assets(&id);
+			(Game::BattleMogs, account, transition_executed(id), asset_ids)
+		},
+		RuntimeEvent::SageCasinoJamGameEvents(pallet_sage_game_events::Event::GameEvent {
+			account,
+			event,
+		}) => {
+			let asset_ids = casino_jam_event_assets(&event);
+			(Game::CasinoJam, account, serde_json::to_value(event).ok()?, asset_ids)
+		},
+		RuntimeEvent::SageBattleMogsGameEvents(pallet_sage_game_events::Event::GameEvent {
+			account,
+			event,
+		}) => {
+			let asset_ids = battle_mogs_event_assets(&event);
+			(Game::BattleMogs, account, serde_json::to_value(event).ok()?, asset_ids)
+		},
+		_ => return None,
+	};
+
+	let (kind, data) = split_variant(event);
+	Some(IndexedEvent {
+		extrinsic_index,
+		game,
+		account: account.to_ss58check(),
+		kind,
+		data,
+		asset_ids,
+	})
+}
+
+fn transition_executed(action: impl serde::Serialize) -> serde_json::Value {
+	serde_json::json!({ "TransitionExecuted": { "action": action } })
+}
+
+fn casino_jam_event_assets(event: &CasinoJamEvent) -> Vec<u64> {
+	match event {
+		CasinoJamEvent::Gambled { player_id, machine_id, .. } =>
+			vec![*player_id as u64, *machine_id as u64],
+	}
+}
+
+fn battle_mogs_event_assets(event: &BattleMogsEvent) -> Vec<u64> {
+	match event {
+		BattleMogsEvent::MogwaiHatched { mogwai_id, .. } |
+		BattleMogsEvent::MogwaiSacrificed { mogwai_id } |
+		BattleMogsEvent::MogwaiMorphed { mogwai_id, .. } |
+		BattleMogsEvent::StudOffered { mogwai_id, .. } |
+		BattleMogsEvent::StudRevoked { mogwai_id } |
+		BattleMogsEvent::StudFeesClaimed { mogwai_id, .. } |
+		BattleMogsEvent::ExpeditionSent { mogwai_id, .. } |
+		BattleMogsEvent::MogwaiSkipped { mogwai_id, .. } => vec![*mogwai_id],
+		BattleMogsEvent::ExpeditionCompleted { mogwai_id, loot, .. } =>
+			[*mogwai_id].into_iter().chain(*loot).collect(),
+		BattleMogsEvent::MogwaiSacrificedInto { mogwai_id, into_id, .. } =>
+			vec![*mogwai_id, *into_id],
+		BattleMogsEvent::MogwaiBred { mogwai_id_1, mogwai_id_2, offspring_id, .. } =>
+			vec![*mogwai_id_1, *mogwai_id_2, *offspring_id],
+		BattleMogsEvent::AchievementCompleted { player_id, .. } |
+		BattleMogsEvent::PlayerLeveledUp { player_id, .. } => vec![*player_id],
+		BattleMogsEvent::ItemCrafted { item_id, .. } => vec![*item_id],
+		BattleMogsEvent::ItemEquipped { item_id, mogwai_id } |
+		BattleMogsEvent::ItemUnequipped { item_id, mogwai_id } => vec![*item_id, *mogwai_id],
+	}
+}
+
+fn battle_mogs_action_assets(action: &BattleMogsAction) -> Vec<u64> {
+	match action {
+		BattleMogsAction::RegisterPlayer | BattleMogsAction::CreateMogwai => Vec::new(),
+		BattleMogsAction::Remove { mogwai } |
+		BattleMogsAction::Hatch { mogwai } |
+		BattleMogsAction::Sacrifice { mogwai } |
+		BattleMogsAction::Morph { mogwai } |
+		BattleMogsAction::OfferStud { mogwai, .. } |
+		BattleMogsAction::RevokeStud { mogwai } |
+		BattleMogsAction::ClaimStudFees { mogwai } |
+		BattleMogsAction::SendExpedition { mogwai, .. } |
+		BattleMogsAction::CompleteExpedition { mogwai } => vec![*mogwai],
+		BattleMogsAction::SacrificeInto { mogwai, into } => vec![*mogwai, *into],
+		BattleMogsAction::Breed { mogwai_1, mogwai_2 } => vec![*mogwai_1, *mogwai_2],
+		BattleMogsAction::Showcase { mogwai } => mogwai.iter().copied().collect(),
+		BattleMogsAction::Craft { ingredients } => ingredients.to_vec(),
+		BattleMogsAction::HatchMany { mogwais, .. } |
+		BattleMogsAction::SacrificeMany { mogwais, .. } |
+		BattleMogsAction::RemoveMany { mogwais, .. } => mogwais.to_vec(),
+		BattleMogsAction::Equip { item, mogwai } => vec![*item, *mogwai],
+		BattleMogsAction::Unequip { item } => vec![*item],
+	}
+}
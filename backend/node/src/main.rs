@@ -18,8 +18,14 @@ mod benchmarking;
 mod chain_spec;
 mod cli;
 mod command;
+mod events;
+mod indexer;
+mod metrics;
 mod rpc;
+mod sage;
 mod service;
+#[cfg(feature = "try-runtime")]
+mod try_runtime;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
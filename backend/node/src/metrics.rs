@@ -0,0 +1,210 @@
+//! Prometheus metrics of the SAGE games, exported along the standard Substrate metrics.
+//!
+//! Transitions and their errors are counted from the events of the imported best blocks,
+//! while asset counts, locked funds and jackpots are taken from the state of the best block
+//! through the [`SageMetricsApi`].
+
+use crate::{
+	events::{block_events, split_variant, EventRecord},
+	service::FullClient,
+};
+use futures::StreamExt;
+use parity_scale_codec::{Decode, Encode};
+use sage_playground_runtime::{
+	configs::{
+		sage::metrics::{GameMetrics, SageMetricsApi},
+		SageGame,
+	},
+	opaque::Hash,
+	RuntimeCall, RuntimeEvent, UncheckedExtrinsic,
+};
+use sc_client_api::{BlockBackend, BlockchainEvents};
+use sp_api::ProvideRuntimeApi;
+use sp_runtime::DispatchError;
+use std::sync::Arc;
+use substrate_prometheus_endpoint::{
+	register, CounterVec, GaugeVec, Opts, PrometheusError, Registry, F64, U64,
+};
+
+const LOG_TARGET: &str = "sage-metrics";
+
+/// The `sage_*` metrics.
+pub struct SageMetrics {
+	transitions: CounterVec<U64>,
+	transition_errors: CounterVec<U64>,
+	assets: GaugeVec<U64>,
+	locked_funds: GaugeVec<F64>,
+	bandit_jackpots: GaugeVec<U64>,
+}
+
+impl SageMetrics {
+	/// Registers the metrics in `registry`.
+	pub fn register(registry: &Registry) -> Result<Self, PrometheusError> {
+		Ok(Self {
+			transitions: register(
+				CounterVec::new(
+					Opts::new("sage_transitions_total", "Executed transitions by game and action"),
+					&["game", "action"],
+				)?,
+				registry,
+			)?,
+			transition_errors: register(
+				CounterVec::new(
+					Opts::new(
+						"sage_transition_errors_total",
+						"Failed transitions by game and dispatch error, module errors are given \
+						 as `<pallet index>:<hex encoded error>`",
+					),
+					&["game", "error"],
+				)?,
+				registry,
+			)?,
+			assets: register(
+				GaugeVec::new(
+					Opts::new("sage_assets", "Number of assets by game and variant"),
+					&["game", "variant"],
+				)?,
+				registry,
+			)?,
+			locked_funds: register(
+				GaugeVec::new(
+					Opts::new("sage_locked_funds", "Native funds held by CasinoJam assets"),
+					&["holder"],
+				)?,
+				registry,
+			)?,
+			bandit_jackpots: register(
+				GaugeVec::new(
+					Opts::new("sage_bandit_jackpot", "Jackpot of every CasinoJam bandit"),
+					&["machine_id"],
+				)?,
+				registry,
+			)?,
+		})
+	}
+
+	fn observe_events(&self, extrinsics: &[UncheckedExtrinsic], records: Vec<EventRecord>) {
+		for record in records {
+			match record.event {
+				RuntimeEvent::SageCasinoJam(pallet_sage::Event::TransitionExecuted {
+					id, ..
+				}) => self.observe_transition(SageGame::CasinoJam, id),
+				RuntimeEvent::SageBattleMogs(pallet_sage::Event::TransitionExecuted {
+					id, ..
+				}) => self.observe_transition(SageGame::BattleMogs, id),
+				RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+					dispatch_error,
+					..
+				}) => {
+					let frame_system::Phase::ApplyExtrinsic(index) = record.phase else {
+						continue;
+					};
+					let game = match extrinsics.get(index as usize).map(|xt| &xt.function) {
+						Some(RuntimeCall::SageCasinoJam(_)) => SageGame::CasinoJam,
+						Some(RuntimeCall::SageBattleMogs(_)) => SageGame::BattleMogs,
+						_ => continue,
+					};
+					let error = error_label(dispatch_error);
+					self.transition_errors
+						.with_label_values(&[game_label(game), error.as_str()])
+						.inc();
+				},
+				_ => {},
+			}
+		}
+	}
+
+	fn observe_transition(&self, game: SageGame, action: impl serde::Serialize) {
+		let action = serde_json::to_value(action)
+			.map(|action| split_variant(action).0)
+			.unwrap_or_else(|_| "Unknown".into());
+		self.transitions.with_label_values(&[game_label(game), action.as_str()]).inc();
+	}
+
+	fn observe_state(&self, metrics: GameMetrics) {
+		self.assets.reset();
+		for (game, variant, count) in metrics.assets {
+			let variant = String::from_utf8_lossy(&variant);
+			self.assets
+				.with_label_values(&[game_label(game), variant.as_ref()])
+				.set(count.into());
+		}
+
+		self.locked_funds
+			.with_label_values(&["machine"])
+			.set(metrics.machine_funds as f64);
+		self.locked_funds.with_label_values(&["seat"]).set(metrics.seat_funds as f64);
+
+		// Bandits may have been consumed since the last block.
+		self.bandit_jackpots.reset();
+		for (machine_id, jackpot) in metrics.jackpots {
+			let machine_id = machine_id.to_string();
+			self.bandit_jackpots
+				.with_label_values(&[machine_id.as_str()])
+				.set(jackpot.into());
+		}
+	}
+}
+
+fn game_label(game: SageGame) -> &'static str {
+	match game {
+		SageGame::CasinoJam => "casino_jam",
+		SageGame::BattleMogs => "battle_mogs",
+	}
+}
+
+fn error_label(error: DispatchError) -> String {
+	match error {
+		DispatchError::Module(error) => format!("{}:{}", error.index, hex(&error.error)),
+		other => <&'static str>::from(other).to_string(),
+	}
+}
+
+fn hex(bytes: &[u8]) -> String {
+	bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Updates `metrics` on every new best block until the node shuts down.
+pub async fn run(client: Arc<FullClient>, metrics: SageMetrics) {
+	let mut imports = client.import_notification_stream();
+
+	while let Some(notification) = imports.next().await {
+		if !notification.is_new_best {
+			continue;
+		}
+
+		let enacted = notification
+			.tree_route
+			.iter()
+			.flat_map(|route| route.enacted().iter().map(|block| block.hash));
+		for hash in enacted.chain(std::iter::once(notification.hash)) {
+			if let Err(error) = observe_block(&client, &metrics, hash) {
+				log::warn!(target: LOG_TARGET, "Failed to observe block {hash}: {error}");
+			}
+		}
+
+		match client.runtime_api().game_metrics(notification.hash) {
+			Ok(state) => metrics.observe_state(state),
+			Err(error) => log::warn!(target: LOG_TARGET, "Failed to query game metrics: {error}"),
+		}
+	}
+}
+
+fn observe_block(client: &FullClient, metrics: &SageMetrics, hash: Hash) -> Result<(), String> {
+	let records = block_events(client, hash)?;
+	if records.is_empty() {
+		return Ok(());
+	}
+
+	let extrinsics = client
+		.block_body(hash)
+		.map_err(|e| e.to_string())?
+		.unwrap_or_default()
+		.into_iter()
+		.map(|xt| UncheckedExtrinsic::decode(&mut &xt.encode()[..]))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|e| format!("Undecodable extrinsics: {e}"))?;
+
+	metrics.observe_events(&extrinsics, records);
+	Ok(())
+}
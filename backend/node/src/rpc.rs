@@ -5,10 +5,23 @@
 
 #![warn(missing_docs)]
 
+pub mod assets;
+pub mod indexer;
+pub mod sealing;
+
 use std::sync::Arc;
 
+use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
-use sage_playground_runtime::{opaque::Block, AccountId, Balance, Nonce};
+use sage_playground_runtime::{
+	opaque::{Block, Hash},
+	AccountId, Balance, Nonce,
+};
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use sc_consensus_manual_seal::{
+	rpc::{ManualSeal, ManualSealApiServer},
+	EngineCommand,
+};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
@@ -20,6 +33,10 @@ pub struct FullDeps<C, P> {
 	pub client: Arc<C>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
+	/// Channel to the manual seal authorship task, when running with `--sealing`.
+	pub command_sink: Option<mpsc::Sender<EngineCommand<Hash>>>,
+	/// The game history database, when running with `--sage-indexer`.
+	pub sage_indexer: Option<Arc<crate::indexer::Database>>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -29,20 +46,36 @@ pub fn create_full<C, P>(
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, crate::service::FullBackend>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
 	P: TransactionPool + 'static,
 {
+	use assets::{SageAssets, SageAssetsApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool } = deps;
+	let FullDeps { client, pool, command_sink, sage_indexer } = deps;
 
 	module.merge(System::new(client.clone(), pool).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(SageAssets::new(client).into_rpc())?;
+
+	if let Some(command_sink) = command_sink {
+		use sealing::{Sealing, SealingApiServer};
+
+		module.merge(ManualSeal::new(command_sink.clone()).into_rpc())?;
+		module.merge(Sealing::new(command_sink).into_rpc())?;
+	}
+
+	if let Some(database) = sage_indexer {
+		use indexer::{SageIndexer, SageIndexerApiServer};
+
+		module.merge(SageIndexer::new(database).into_rpc())?;
+	}
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
@@ -0,0 +1,276 @@
+//! RPC pushing the changes to the game assets of an account, so that frontends don't have to
+//! poll them after every transition.
+
+use crate::{indexer::Game, service::FullBackend};
+use frame_support::storage::StoragePrefixedMap;
+use futures::{
+	future::{self, Either},
+	stream::{self, BoxStream},
+	StreamExt,
+};
+use jsonrpsee::{
+	core::{async_trait, SubscriptionResult},
+	proc_macros::rpc,
+	types::ErrorObjectOwned,
+	PendingSubscriptionSink, SubscriptionMessage,
+};
+use parity_scale_codec::Decode;
+use sage_playground_runtime::{
+	configs::sage::{
+		battle_mogs::{BattleMogsAssetFor, SageBattleMogsInstance},
+		casino_jam::{CasinoJamAsset, SageCasinoJamInstance},
+	},
+	opaque::{Block, Hash},
+	AccountId, BlockNumber, Runtime,
+};
+use sc_client_api::{
+	BlockchainEvents, FinalityNotification, StorageEventStream, StorageNotification,
+	StorageProvider,
+};
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::{StorageData, StorageKey};
+use sp_runtime::traits::Header as _;
+use std::{collections::HashMap, sync::Arc};
+
+/// Error code returned when a subscription can't be set up.
+const ASSETS_ERROR: i32 = 6200;
+
+#[rpc(server)]
+pub trait SageAssetsApi {
+	/// Pushes the assets of `account` in `game` that were minted, mutated or consumed, once
+	/// for every best block changing any of them, or for every finalized one if `finalized`
+	/// is `true`.
+	#[subscription(
+		name = "sage_subscribeAssets" => "sage_assets",
+		unsubscribe = "sage_unsubscribeAssets",
+		item = AssetChanges
+	)]
+	async fn subscribe_assets(
+		&self,
+		game: Game,
+		account: AccountId,
+		finalized: Option<bool>,
+	) -> SubscriptionResult;
+}
+
+/// The changes to the assets of an account in a block.
+#[derive(Clone, Debug, Serialize)]
+pub struct AssetChanges {
+	pub block: Hash,
+	pub changes: Vec<AssetChange>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetChange {
+	/// The account received a new asset.
+	Minted { asset_id: u64, asset: serde_json::Value },
+	/// An asset of the account changed.
+	Mutated { asset_id: u64, asset: serde_json::Value },
+	/// An asset of the account was consumed, or now belongs to another account.
+	Consumed { asset_id: u64 },
+}
+
+/// Implements [`SageAssetsApiServer`] on top of the storage change notifications of the client.
+pub struct SageAssets<C> {
+	client: Arc<C>,
+}
+
+impl<C> SageAssets<C> {
+	/// Creates a new instance following the blocks imported by `client`.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+fn assets_error(message: impl ToString) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(ASSETS_ERROR, message.to_string(), None::<()>)
+}
+
+enum Notification {
+	Storage(StorageNotification<Hash>),
+	Finalized(FinalityNotification<Block>),
+}
+
+#[async_trait]
+impl<C> SageAssetsApiServer for SageAssets<C>
+where
+	C: BlockchainEvents<Block> + StorageProvider<Block, FullBackend> + HeaderBackend<Block>,
+	C: Send + Sync + 'static,
+{
+	async fn subscribe_assets(
+		&self,
+		pending: PendingSubscriptionSink,
+		game: Game,
+		account: AccountId,
+		finalized: Option<bool>,
+	) -> SubscriptionResult {
+		let finalized = finalized.unwrap_or(false);
+		let (mut tracker, mut notifications) = match self.follow(game, account, finalized) {
+			Ok(follow) => follow,
+			Err(error) => {
+				pending.reject(assets_error(error)).await;
+				return Ok(());
+			},
+		};
+		let sink = pending.accept().await?;
+
+		loop {
+			let notification =
+				match future::select(Box::pin(sink.closed()), notifications.next()).await {
+					Either::Right((Some(notification), _)) => notification,
+					_ => break,
+				};
+
+			let changes = match notification {
+				Notification::Storage(notification) => {
+					let changes = tracker.apply(&notification);
+					if finalized {
+						let number = self.client.number(notification.block).ok().flatten();
+						tracker.unfinalized.push((number, notification.block, changes));
+						continue;
+					}
+					vec![AssetChanges { block: notification.block, changes }]
+				},
+				Notification::Finalized(notification) => tracker.finalize(&notification),
+			};
+
+			for changes in changes.into_iter().filter(|changes| !changes.changes.is_empty()) {
+				let message = SubscriptionMessage::from_json(&changes)?;
+				if sink.send(message).await.is_err() {
+					return Ok(());
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl<C> SageAssets<C>
+where
+	C: BlockchainEvents<Block> + StorageProvider<Block, FullBackend> + HeaderBackend<Block>,
+{
+	/// Reads the assets `account` owns at the best block, and subscribes to the blocks after it.
+	fn follow(
+		&self,
+		game: Game,
+		account: AccountId,
+		finalized: bool,
+	) -> Result<(AssetTracker, BoxStream<'static, Notification>), String> {
+		// Subscribe first, so that no block is missed while reading the owned assets.
+		let storage: StorageEventStream<Hash> = self
+			.client
+			.storage_changes_notification_stream(None, None)
+			.map_err(|e| e.to_string())?;
+		let notifications = if finalized {
+			stream::select(
+				storage.map(Notification::Storage),
+				self.client.finality_notification_stream().map(Notification::Finalized),
+			)
+			.boxed()
+		} else {
+			storage.map(Notification::Storage).boxed()
+		};
+
+		let mut tracker =
+			AssetTracker { game, account, owned: HashMap::new(), unfinalized: Vec::new() };
+		let best = self.client.info().best_hash;
+		let prefix = assets_prefix(game);
+		for (key, data) in self
+			.client
+			.storage_pairs(best, Some(&prefix), None)
+			.map_err(|e| e.to_string())?
+		{
+			if let Some((owner, asset_id, _)) = decode_asset(game, &data) {
+				if owner == tracker.account {
+					tracker.owned.insert(key.0, asset_id);
+				}
+			}
+		}
+
+		Ok((tracker, notifications))
+	}
+}
+
+/// Follows the assets owned by an account through the storage changes of the best blocks.
+struct AssetTracker {
+	game: Game,
+	account: AccountId,
+	/// Storage keys of the assets the account owns, with their ids.
+	owned: HashMap<Vec<u8>, u64>,
+	/// Changes of best blocks that have not been finalized yet.
+	unfinalized: Vec<(Option<BlockNumber>, Hash, Vec<AssetChange>)>,
+}
+
+impl AssetTracker {
+	/// Applies the storage changes of a new best block, returning how the assets changed.
+	fn apply(&mut self, notification: &StorageNotification<Hash>) -> Vec<AssetChange> {
+		let prefix = assets_prefix(self.game);
+		notification
+			.changes
+			.iter()
+			.filter(|(child, key, _)| child.is_none() && key.0.starts_with(&prefix.0))
+			.filter_map(|(_, key, data)| {
+				let owned = self.owned.get(&key.0).copied();
+				match data.and_then(|data| decode_asset(self.game, data)) {
+					Some((owner, asset_id, asset)) if owner == self.account => {
+						self.owned.insert(key.0.clone(), asset_id);
+						Some(match owned {
+							Some(_) => AssetChange::Mutated { asset_id, asset },
+							None => AssetChange::Minted { asset_id, asset },
+						})
+					},
+					_ => owned.map(|asset_id| {
+						self.owned.remove(&key.0);
+						AssetChange::Consumed { asset_id }
+					}),
+				}
+			})
+			.collect()
+	}
+
+	/// Takes the changes of the blocks finalized by `notification`, oldest first.
+	fn finalize(&mut self, notification: &FinalityNotification<Block>) -> Vec<AssetChanges> {
+		let finalized = notification.tree_route.iter().chain([&notification.hash]);
+		let changes = finalized
+			.filter_map(|hash| {
+				let index = self.unfinalized.iter().position(|(_, block, _)| block == hash)?;
+				let (_, block, changes) = self.unfinalized.remove(index);
+				Some(AssetChanges { block, changes })
+			})
+			.collect();
+
+		// Blocks at or below the finalized one that are left belong to abandoned forks.
+		let number = *notification.header.number();
+		self.unfinalized
+			.retain(|(block_number, ..)| block_number.is_some_and(|n| n > number));
+
+		changes
+	}
+}
+
+fn assets_prefix(game: Game) -> StorageKey {
+	StorageKey(match game {
+		Game::CasinoJam =>
+			pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::final_prefix().to_vec(),
+		Game::BattleMogs =>
+			pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::final_prefix().to_vec(),
+	})
+}
+
+/// Decodes the owner, id and JSON representation of an asset stored in `pallet_sage`.
+fn decode_asset(game: Game, data: &StorageData) -> Option<(AccountId, u64, serde_json::Value)> {
+	match game {
+		Game::CasinoJam => {
+			let (owner, asset) = <(AccountId, CasinoJamAsset)>::decode(&mut &data.0[..]).ok()?;
+			Some((owner, asset.id.into(), serde_json::to_value(asset).ok()?))
+		},
+		Game::BattleMogs => {
+			let (owner, asset) =
+				<(AccountId, BattleMogsAssetFor)>::decode(&mut &data.0[..]).ok()?;
+			Some((owner, asset.id, serde_json::to_value(asset).ok()?))
+		},
+	}
+}
@@ -0,0 +1,110 @@
+//! RPC serving the game history indexed with `--sage-indexer`.
+
+use crate::indexer::{Database, Game, HistoryEntry, Leaderboard};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, types::ErrorObjectOwned};
+use sage_playground_runtime::{AccountId, BlockNumber};
+use sp_core::crypto::Ss58Codec;
+use std::sync::Arc;
+
+/// Error code returned when querying the indexer database fails.
+const INDEXER_ERROR: i32 = 6100;
+
+/// The number of entries returned when no `limit` is given.
+pub const DEFAULT_ENTRIES_PER_CALL: u32 = 100;
+
+/// The highest number of entries returned by a single call.
+pub const MAX_ENTRIES_PER_CALL: u32 = 1_000;
+
+#[rpc(server)]
+pub trait SageIndexerApi {
+	/// Returns the game events of `account`, newest first, optionally restricted to a single
+	/// `game`. Pass the `id` of the last returned entry as `before` to fetch the next page.
+	#[method(name = "sage_accountHistory")]
+	fn account_history(
+		&self,
+		account: AccountId,
+		game: Option<Game>,
+		before: Option<u64>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry>>;
+
+	/// Returns the game events referring to the asset `asset_id` of `game`, newest first.
+	#[method(name = "sage_assetHistory")]
+	fn asset_history(
+		&self,
+		game: Game,
+		asset_id: u64,
+		before: Option<u64>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry>>;
+
+	/// Ranks the players of `game` by the events up to block `at`, or up to the last indexed
+	/// block if `at` is not given.
+	#[method(name = "sage_leaderboard")]
+	fn leaderboard(
+		&self,
+		game: Game,
+		at: Option<BlockNumber>,
+		limit: Option<u32>,
+	) -> RpcResult<Leaderboard>;
+}
+
+/// Implements [`SageIndexerApiServer`] on top of the indexer database.
+pub struct SageIndexer {
+	database: Arc<Database>,
+}
+
+impl SageIndexer {
+	/// Creates a new instance querying `database`.
+	pub fn new(database: Arc<Database>) -> Self {
+		Self { database }
+	}
+}
+
+fn indexer_error(message: impl ToString) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(INDEXER_ERROR, message.to_string(), None::<()>)
+}
+
+fn entries(limit: Option<u32>) -> RpcResult<u32> {
+	match limit.unwrap_or(DEFAULT_ENTRIES_PER_CALL) {
+		limit if limit > MAX_ENTRIES_PER_CALL => Err(indexer_error(format!(
+			"cannot return more than {MAX_ENTRIES_PER_CALL} entries per call"
+		))),
+		limit => Ok(limit),
+	}
+}
+
+impl SageIndexerApiServer for SageIndexer {
+	fn account_history(
+		&self,
+		account: AccountId,
+		game: Option<Game>,
+		before: Option<u64>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry>> {
+		self.database
+			.account_history(&account.to_ss58check(), game, before, entries(limit)?)
+			.map_err(indexer_error)
+	}
+
+	fn asset_history(
+		&self,
+		game: Game,
+		asset_id: u64,
+		before: Option<u64>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<HistoryEntry>> {
+		self.database
+			.asset_history(game, asset_id, before, entries(limit)?)
+			.map_err(indexer_error)
+	}
+
+	fn leaderboard(
+		&self,
+		game: Game,
+		at: Option<BlockNumber>,
+		limit: Option<u32>,
+	) -> RpcResult<Leaderboard> {
+		self.database.leaderboard(game, at, entries(limit)?).map_err(indexer_error)
+	}
+}
@@ -0,0 +1,77 @@
+//! RPC to seal several blocks at once when running with `--sealing`.
+//!
+//! `engine_createBlock` from manual seal only seals a single block per call, which makes
+//! fast forwarding through time based game mechanics tedious.
+
+use futures::{
+	channel::{mpsc, oneshot},
+	SinkExt,
+};
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::ErrorObjectOwned,
+};
+use sage_playground_runtime::opaque::Hash;
+use sc_consensus_manual_seal::EngineCommand;
+
+/// Error code returned when sealing fails.
+const SEALING_ERROR: i32 = 6000;
+
+/// The highest number of blocks that can be sealed by a single call.
+pub const MAX_BLOCKS_PER_CALL: u32 = 100_000;
+
+#[rpc(server)]
+pub trait SealingApi {
+	/// Seals `count` blocks on top of the best block, even if there are no transactions to
+	/// include, and returns their hashes. Blocks are finalized unless `finalize` is `false`.
+	#[method(name = "engine_createBlocks")]
+	async fn create_blocks(&self, count: u32, finalize: Option<bool>) -> RpcResult<Vec<Hash>>;
+}
+
+/// Implements [`SealingApiServer`] on top of the manual seal command stream.
+pub struct Sealing {
+	command_sink: mpsc::Sender<EngineCommand<Hash>>,
+}
+
+impl Sealing {
+	/// Creates a new instance sending its commands to the manual seal authorship task.
+	pub fn new(command_sink: mpsc::Sender<EngineCommand<Hash>>) -> Self {
+		Self { command_sink }
+	}
+}
+
+fn sealing_error(message: impl ToString) -> ErrorObjectOwned {
+	ErrorObjectOwned::owned(SEALING_ERROR, message.to_string(), None::<()>)
+}
+
+#[async_trait]
+impl SealingApiServer for Sealing {
+	async fn create_blocks(&self, count: u32, finalize: Option<bool>) -> RpcResult<Vec<Hash>> {
+		if count > MAX_BLOCKS_PER_CALL {
+			return Err(sealing_error(format!(
+				"cannot seal more than {MAX_BLOCKS_PER_CALL} blocks per call"
+			)));
+		}
+
+		let mut sink = self.command_sink.clone();
+		let mut hashes = Vec::with_capacity(count as usize);
+
+		for _ in 0..count {
+			let (sender, receiver) = oneshot::channel();
+			sink.send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize: finalize.unwrap_or(true),
+				parent_hash: None,
+				sender: Some(sender),
+			})
+			.await
+			.map_err(sealing_error)?;
+
+			let created = receiver.await.map_err(sealing_error)?.map_err(sealing_error)?;
+			hashes.push(created.hash);
+		}
+
+		Ok(hashes)
+	}
+}
@@ -0,0 +1,220 @@
+use crate::chain_spec::{
+	authority_keys_from_seed, parse_account_id, testnet_genesis, ChainSpec, GameGenesis,
+	PaymentAsset,
+};
+use sage_playground_runtime::{configs::SageAssetId, AccountId, Balance, BlockNumber, WASM_BINARY};
+
+use sc_service::ChainType;
+use serde::Deserialize;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_core::{crypto::Ss58Codec, ed25519, sr25519};
+use std::{collections::BTreeSet, path::PathBuf};
+
+/// The `sage build-game-spec` command.
+///
+/// Builds a raw chain spec from a JSON file describing the organizers, first seasons and
+/// payment assets of the games, together with the authorities and endowed accounts:
+///
+/// ```json
+/// {
+///   "name": "SAGE Staging",
+///   "id": "sage_staging",
+///   "authorities": ["Alice", { "aura": "5Grw...", "grandpa": "5FA9..." }],
+///   "sudo": "Alice",
+///   "endowed": [["Alice", 1000000000000000]],
+///   "casino_jam": { "organizer": "Alice", "season": { "id": 0, "duration": 14400 } },
+///   "battle_mogs": { "organizer": "Bob", "season": { "id": 0, "duration": 100800 } },
+///   "payment_assets": [{
+///     "id": 1, "owner": "Alice", "name": "Jam Token", "symbol": "JAM", "decimals": 12,
+///     "min_balance": 1, "balances": [["Bob", 1000000000000]]
+///   }]
+/// }
+/// ```
+///
+/// Accounts are given either as SS58 address or as dev seed, e.g. `Alice` for `//Alice`.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct BuildGameSpecCmd {
+	/// Path to the JSON file describing the chain.
+	#[arg(long)]
+	pub config: PathBuf,
+
+	/// Write the chain spec to this file instead of stdout.
+	#[arg(long)]
+	pub output: Option<PathBuf>,
+
+	/// Emit the genesis config patch instead of the raw storage.
+	#[arg(long)]
+	pub plain: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameSpecConfig {
+	name: String,
+	id: String,
+	#[serde(default = "live")]
+	chain_type: ChainType,
+	protocol_id: Option<String>,
+	authorities: Vec<AuthorityConfig>,
+	sudo: String,
+	endowed: Vec<(String, Balance)>,
+	casino_jam: GameConfig,
+	battle_mogs: GameConfig,
+	#[serde(default)]
+	payment_assets: Vec<PaymentAssetConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum AuthorityConfig {
+	Seed(String),
+	Keys { aura: String, grandpa: String },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameConfig {
+	organizer: String,
+	season: SeasonConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SeasonConfig {
+	id: u32,
+	duration: BlockNumber,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PaymentAssetConfig {
+	id: SageAssetId,
+	owner: String,
+	name: String,
+	symbol: String,
+	decimals: u8,
+	min_balance: Balance,
+	#[serde(default = "sufficient")]
+	is_sufficient: bool,
+	#[serde(default)]
+	balances: Vec<(String, Balance)>,
+}
+
+fn live() -> ChainType {
+	ChainType::Live
+}
+
+fn sufficient() -> bool {
+	true
+}
+
+impl BuildGameSpecCmd {
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let config = std::fs::read(&self.config)?;
+		let config: GameSpecConfig = serde_json::from_slice(&config)
+			.map_err(|e| format!("Invalid game spec config {}: {e}", self.config.display()))?;
+
+		let spec = game_spec(config)?.as_json(!self.plain)?;
+		match &self.output {
+			Some(path) => std::fs::write(path, spec)?,
+			None => println!("{spec}"),
+		}
+
+		Ok(())
+	}
+}
+
+fn game_spec(config: GameSpecConfig) -> Result<ChainSpec, String> {
+	let authorities =
+		config.authorities.iter().map(authority_keys).collect::<Result<Vec<_>, _>>()?;
+	if authorities.is_empty() {
+		return Err("At least one authority is required".into());
+	}
+
+	let casino_jam = game_genesis(config.casino_jam)?;
+	let battle_mogs = game_genesis(config.battle_mogs)?;
+
+	let mut asset_ids = BTreeSet::new();
+	let payment_assets = config
+		.payment_assets
+		.into_iter()
+		.map(|asset| {
+			if !asset_ids.insert(asset.id) {
+				return Err(format!("Payment asset {} is defined more than once", asset.id));
+			}
+			if asset.min_balance == 0 {
+				return Err(format!("Payment asset {} needs a non-zero min_balance", asset.id));
+			}
+			Ok(PaymentAsset {
+				id: asset.id,
+				owner: parse_account_id(&asset.owner),
+				name: asset.name,
+				symbol: asset.symbol,
+				decimals: asset.decimals,
+				min_balance: asset.min_balance,
+				is_sufficient: asset.is_sufficient,
+				balances: balances(asset.balances)?,
+			})
+		})
+		.collect::<Result<Vec<_>, _>>()?;
+
+	let mut builder = ChainSpec::builder(
+		WASM_BINARY.ok_or_else(|| "Wasm binary not available".to_string())?,
+		None,
+	)
+	.with_name(&config.name)
+	.with_id(&config.id)
+	.with_chain_type(config.chain_type)
+	.with_genesis_config_patch(testnet_genesis(
+		authorities,
+		parse_account_id(&config.sudo),
+		balances(config.endowed)?,
+		casino_jam,
+		battle_mogs,
+		payment_assets,
+	));
+	if let Some(protocol_id) = &config.protocol_id {
+		builder = builder.with_protocol_id(protocol_id);
+	}
+
+	Ok(builder.build())
+}
+
+fn authority_keys(authority: &AuthorityConfig) -> Result<(AuraId, GrandpaId), String> {
+	match authority {
+		AuthorityConfig::Seed(seed) => Ok(authority_keys_from_seed(seed)),
+		AuthorityConfig::Keys { aura, grandpa } => {
+			let aura = sr25519::Public::from_ss58check(aura)
+				.map_err(|e| format!("Invalid aura key {aura}: {e:?}"))?;
+			let grandpa = ed25519::Public::from_ss58check(grandpa)
+				.map_err(|e| format!("Invalid grandpa key {grandpa}: {e:?}"))?;
+			Ok((aura.into(), grandpa.into()))
+		},
+	}
+}
+
+fn game_genesis(game: GameConfig) -> Result<GameGenesis, String> {
+	if game.season.duration == 0 {
+		return Err(format!("Season {} needs a non-zero duration", game.season.id));
+	}
+
+	Ok(GameGenesis {
+		organizer: parse_account_id(&game.organizer),
+		season_id: game.season.id,
+		season_duration: game.season.duration,
+	})
+}
+
+/// Genesis patches are plain JSON, whose numbers can't exceed `u64` without losing precision.
+fn balances(balances: Vec<(String, Balance)>) -> Result<Vec<(AccountId, Balance)>, String> {
+	balances
+		.into_iter()
+		.map(|(account, balance)| {
+			if balance > u64::MAX as Balance {
+				return Err(format!("Balance {balance} of {account} exceeds {}", u64::MAX));
+			}
+			Ok((parse_account_id(&account), balance))
+		})
+		.collect()
+}
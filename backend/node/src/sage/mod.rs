@@ -0,0 +1,37 @@
+//! Tooling around the SAGE games that runs on the native runtime, without starting a node.
+
+mod build_game_spec;
+mod simulate;
+mod snapshot;
+
+pub use build_game_spec::BuildGameSpecCmd;
+pub use simulate::SimulateCmd;
+pub use snapshot::{ExportSnapshotCmd, ImportSnapshotCmd};
+
+use crate::cli::Cli;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum SageSubcommand {
+	/// Build a raw chain spec with custom organizers, seasons and payment assets for the games.
+	BuildGameSpec(BuildGameSpecCmd),
+
+	/// Replay a script of game actions on top of a chain spec and print the outcome as JSON.
+	Simulate(SimulateCmd),
+
+	/// Export the state of the games in a chain spec or exported state as a JSON snapshot.
+	ExportSnapshot(ExportSnapshotCmd),
+
+	/// Import a JSON snapshot of the games into a chain spec and emit it as raw chain spec.
+	ImportSnapshot(ImportSnapshotCmd),
+}
+
+impl SageSubcommand {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		match self {
+			Self::BuildGameSpec(cmd) => cmd.run(),
+			Self::Simulate(cmd) => cmd.run(cli),
+			Self::ExportSnapshot(cmd) => cmd.run(cli),
+			Self::ImportSnapshot(cmd) => cmd.run(cli),
+		}
+	}
+}
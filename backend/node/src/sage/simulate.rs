@@ -0,0 +1,237 @@
+use crate::{chain_spec::parse_account_id, cli::Cli};
+use sage_playground_runtime::{
+	configs::sage::{
+		battle_mogs::{BattleMogsAssetFor, BattleMogsAssetId, SageBattleMogsEngine},
+		casino_jam::{CasinoJamAsset, CasinoJamAssetId, SageCasinoJamEngine},
+	},
+	AccountId, Balance, Balances, BlockNumber, RuntimeEvent, RuntimeOrigin, SageBattleMogs,
+	SageBattleMogsSeasons, SageBattleMogsTournament, SageCasinoJam, SageCasinoJamSeasons,
+	SageCasinoJamTournament, System,
+};
+
+use ajuna_primitives::{payment_handler::NativeId, sage_api::SageApi};
+use frame_support::traits::{OnFinalize, OnInitialize};
+use game_battle_mogs::{prelude::BattleMogsEvent, BattleMogsAction};
+use game_casino_jam::prelude::{CasinoAction, CasinoJamEvent};
+use sc_cli::SubstrateCli;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::Ss58Codec;
+use sp_runtime::BuildStorage;
+use std::{collections::BTreeMap, fmt::Debug, path::PathBuf};
+
+/// The `sage simulate` command.
+///
+/// Loads the genesis state of a chain spec, which can also be a state exported with
+/// `export-state`, and replays a script of game transitions on it with the native runtime.
+/// Every step is executed as its own extrinsic would be, so a failing step is reported and
+/// doesn't affect the following ones.
+///
+/// The script is a JSON array of steps, for example:
+///
+/// ```json
+/// [
+///   { "casino_jam": { "account": "Bob", "action": { "Create": "Player" } } },
+///   { "battle_mogs": { "account": "Bob", "action": "RegisterPlayer" } },
+///   { "advance_blocks": 10 }
+/// ]
+/// ```
+///
+/// Accounts are given either as SS58 address or as dev seed, e.g. `Alice` for `//Alice`.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct SimulateCmd {
+	/// The chain spec or exported state to start from, `dev` and `local` are built in.
+	#[arg(long, default_value = "dev")]
+	pub chain: String,
+
+	/// Path to the JSON script to replay.
+	#[arg(long)]
+	pub script: PathBuf,
+
+	/// Pretty print the resulting JSON.
+	#[arg(long)]
+	pub pretty: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum Step {
+	CasinoJam(GameCall<CasinoAction, CasinoJamAssetId>),
+	BattleMogs(GameCall<BattleMogsAction, BattleMogsAssetId>),
+	AdvanceBlocks(BlockNumber),
+}
+
+#[derive(Debug, Deserialize)]
+struct GameCall<Action, AssetId> {
+	account: String,
+	action: Action,
+	#[serde(default)]
+	assets: Vec<AssetId>,
+}
+
+#[derive(Serialize)]
+struct Report {
+	steps: Vec<StepReport>,
+	accounts: BTreeMap<String, AccountReport>,
+}
+
+#[derive(Serialize)]
+struct StepReport {
+	block: BlockNumber,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<String>,
+	events: Vec<GameEvent>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum GameEvent {
+	CasinoJam(CasinoJamEvent),
+	BattleMogs(BattleMogsEvent),
+}
+
+#[derive(Serialize)]
+struct AccountReport {
+	address: String,
+	free_balance: Balance,
+	casino_jam: Vec<AssetReport<CasinoJamAssetId, CasinoJamAsset>>,
+	battle_mogs: Vec<AssetReport<BattleMogsAssetId, BattleMogsAssetFor>>,
+}
+
+#[derive(Serialize)]
+struct AssetReport<AssetId, Asset> {
+	id: AssetId,
+	asset: Asset,
+	funds: Balance,
+}
+
+/// Pallets whose hooks drive the game lifecycle. The remaining pallets expect inherents and
+/// consensus digests, which we don't provide outside of a block.
+type GamePallets = (
+	SageCasinoJamSeasons,
+	SageCasinoJamTournament,
+	SageBattleMogsSeasons,
+	SageBattleMogsTournament,
+);
+
+impl SimulateCmd {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		let script = std::fs::read(&self.script)?;
+		let script: Vec<Step> = serde_json::from_slice(&script)
+			.map_err(|e| format!("Invalid script {}: {e}", self.script.display()))?;
+		let storage = cli.load_spec(&self.chain)?.build_storage()?;
+
+		let report = sp_io::TestExternalities::new(storage).execute_with(|| {
+			// Events are not recorded in the genesis block.
+			if System::block_number() == 0 {
+				advance_blocks(1);
+			}
+
+			let mut accounts = BTreeMap::new();
+			let steps = script
+				.into_iter()
+				.map(|step| {
+					System::reset_events();
+					let error = match step {
+						Step::CasinoJam(call) => {
+							let account = parse_account_id(&call.account);
+							accounts.insert(call.account, account.clone());
+							error_of(SageCasinoJam::state_transition(
+								RuntimeOrigin::signed(account),
+								call.action,
+								call.assets,
+								None,
+							))
+						},
+						Step::BattleMogs(call) => {
+							let account = parse_account_id(&call.account);
+							accounts.insert(call.account, account.clone());
+							error_of(SageBattleMogs::state_transition(
+								RuntimeOrigin::signed(account),
+								call.action,
+								call.assets,
+								None,
+							))
+						},
+						Step::AdvanceBlocks(blocks) => {
+							advance_blocks(blocks);
+							None
+						},
+					};
+					StepReport { block: System::block_number(), error, events: game_events() }
+				})
+				.collect();
+
+			let accounts = accounts
+				.into_iter()
+				.map(|(name, account)| (name, account_report(&account)))
+				.collect();
+
+			Report { steps, accounts }
+		});
+
+		let json = if self.pretty {
+			serde_json::to_string_pretty(&report)
+		} else {
+			serde_json::to_string(&report)
+		}
+		.map_err(|e| e.to_string())?;
+		println!("{json}");
+
+		Ok(())
+	}
+}
+
+fn error_of<T, E: Debug>(result: Result<T, E>) -> Option<String> {
+	result.err().map(|e| format!("{e:?}"))
+}
+
+fn advance_blocks(blocks: BlockNumber) {
+	for _ in 0..blocks {
+		let current = System::block_number();
+		if current > 0 {
+			GamePallets::on_finalize(current);
+		}
+		System::set_block_number(current + 1);
+		GamePallets::on_initialize(current + 1);
+	}
+}
+
+fn game_events() -> Vec<GameEvent> {
+	System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::SageCasinoJamGameEvents(pallet_sage_game_events::Event::GameEvent {
+				event,
+				..
+			}) => Some(GameEvent::CasinoJam(event)),
+			RuntimeEvent::SageBattleMogsGameEvents(pallet_sage_game_events::Event::GameEvent {
+				event,
+				..
+			}) => Some(GameEvent::BattleMogs(event)),
+			_ => None,
+		})
+		.collect()
+}
+
+fn account_report(account: &AccountId) -> AccountReport {
+	AccountReport {
+		address: account.to_ss58check(),
+		free_balance: Balances::free_balance(account),
+		casino_jam: assets_of::<SageCasinoJamEngine>(account),
+		battle_mogs: assets_of::<SageBattleMogsEngine>(account),
+	}
+}
+
+fn assets_of<Sage>(account: &AccountId) -> Vec<AssetReport<Sage::AssetId, Sage::Asset>>
+where
+	Sage: SageApi<AccountId = AccountId, Balance = Balance>,
+	Sage::FungiblesAssetId: NativeId,
+{
+	let native = Sage::FungiblesAssetId::get_native_id();
+	Sage::iter_assets_from(account)
+		.map(|(id, asset)| {
+			let funds = Sage::inspect_asset_funds(&id, &native);
+			AssetReport { id, asset, funds }
+		})
+		.collect()
+}
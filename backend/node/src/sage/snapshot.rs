@@ -0,0 +1,414 @@
+use crate::{chain_spec::parse_account_id, cli::Cli};
+use sage_playground_runtime::{
+	configs::{
+		sage::{
+			battle_mogs::{BattleMogsAssetFor, SageBattleMogsEngine, SageBattleMogsInstance},
+			casino_jam::{CasinoJamAsset, SageCasinoJamEngine, SageCasinoJamInstance},
+		},
+		SageAssetId,
+	},
+	AccountId, Balance, Balances, BlockNumber, Runtime, SageAssets, SageBattleMogs, SageCasinoJam,
+	System,
+};
+
+use ajuna_primitives::{payment_handler::WithdrawKind, sage_api::SageApi};
+use frame_support::{
+	storage::StoragePrefixedMap,
+	traits::{
+		fungible::{self, NativeOrWithId},
+		fungibles::{self, InspectEnumerable},
+		PalletInfoAccess,
+	},
+};
+use game_battle_mogs::prelude::BattleMogsTransitionConfig;
+use game_casino_jam::prelude::CasinoJamTransitionConfig;
+use parity_scale_codec::Encode;
+use sc_cli::SubstrateCli;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sp_core::{crypto::Ss58Codec, hashing::twox_128, storage::Storage, Bytes};
+use sp_runtime::BuildStorage;
+use std::{collections::BTreeMap, path::PathBuf};
+
+/// The `sage export-snapshot` command.
+///
+/// Reads the `pallet_sage` instances of both games from a chain spec, usually a state exported
+/// from a live node with `export-state`, and writes them as JSON: the organizer, the transition
+/// config, every asset with its owner and funds, and the balances of the owners. Funds and
+/// balances are listed for the native currency and every `pallet_assets` asset. The remaining
+/// storage items of an instance are kept as raw hex encoded key/value pairs.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ExportSnapshotCmd {
+	/// The chain spec or exported state to read, `dev` and `local` are built in.
+	#[arg(long, default_value = "dev")]
+	pub chain: String,
+
+	/// Write the snapshot to this file instead of stdout.
+	#[arg(long)]
+	pub output: Option<PathBuf>,
+}
+
+/// The `sage import-snapshot` command.
+///
+/// Replaces the `pallet_sage` instances of a chain spec, the development one by default, with
+/// the state of a snapshot written by `sage export-snapshot`, and emits the result as raw chain
+/// spec. Asset funds are minted anew, and owners are endowed up to their snapshot balances. The
+/// `pallet_assets` assets of the snapshot have to exist in the chain spec.
+///
+/// Block numbers in the assets, e.g. of seat reservations, are imported as they are, so they
+/// refer to the blocks of the exported chain.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct ImportSnapshotCmd {
+	/// Path to the snapshot to import.
+	#[arg(long)]
+	pub snapshot: PathBuf,
+
+	/// The chain spec to import the snapshot into, `dev` and `local` are built in.
+	#[arg(long, default_value = "dev")]
+	pub chain: String,
+
+	/// Write the chain spec to this file instead of stdout.
+	#[arg(long)]
+	pub output: Option<PathBuf>,
+}
+
+type FungiblesAssetId = WithdrawKind<NativeOrWithId<SageAssetId>>;
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Snapshot {
+	/// The block the snapshot was taken at.
+	block: BlockNumber,
+	casino_jam: GameSnapshot<CasinoJamAsset, CasinoJamTransitionConfig>,
+	battle_mogs: GameSnapshot<BattleMogsAssetFor, BattleMogsTransitionConfig>,
+	/// Free balances of every asset owner.
+	owners: BTreeMap<String, Vec<Funds>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GameSnapshot<Asset, Config> {
+	organizer: Option<String>,
+	/// The transition config, unless the instance uses the default one without storing it.
+	transition_config: Option<ConfigSnapshot<Config>>,
+	assets: Vec<AssetSnapshot<Asset>>,
+	storage: BTreeMap<Bytes, Bytes>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigSnapshot<Config> {
+	/// Storage key the config is stored at.
+	key: Bytes,
+	config: Config,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct AssetSnapshot<Asset> {
+	owner: String,
+	/// Funds held by the asset.
+	funds: Vec<Funds>,
+	asset: Asset,
+}
+
+/// An amount of the native currency, or of the `pallet_assets` asset `asset`.
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Funds {
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	asset: Option<SageAssetId>,
+	amount: Balance,
+}
+
+impl Funds {
+	/// Non-zero funds in the native currency and every `pallet_assets` asset, as read by
+	/// `amount_of`.
+	fn all(amount_of: impl Fn(Option<SageAssetId>) -> Balance) -> Vec<Self> {
+		std::iter::once(None)
+			.chain(SageAssets::asset_ids().map(Some))
+			.filter_map(|asset| {
+				let amount = amount_of(asset);
+				(amount > 0).then_some(Self { asset, amount })
+			})
+			.collect()
+	}
+
+	fn of_account(account: &AccountId) -> Vec<Self> {
+		Self::all(|asset| Self::balance_of(asset, account))
+	}
+
+	fn fungible_id(asset: Option<SageAssetId>) -> FungiblesAssetId {
+		WithdrawKind::Payment(asset.map_or(NativeOrWithId::Native, NativeOrWithId::WithId))
+	}
+
+	fn balance_of(asset: Option<SageAssetId>, account: &AccountId) -> Balance {
+		match asset {
+			None => Balances::free_balance(account),
+			Some(id) => <SageAssets as fungibles::Inspect<_>>::balance(id, account),
+		}
+	}
+
+	fn mint_into(&self, account: &AccountId, amount: Balance) -> Result<(), String> {
+		match self.asset {
+			None => <Balances as fungible::Mutate<_>>::mint_into(account, amount),
+			Some(id) => <SageAssets as fungibles::Mutate<_>>::mint_into(id, account, amount),
+		}
+		.map(|_| ())
+		.map_err(|e| format!("Could not mint {:?} for {account}: {e:?}", self.asset))
+	}
+
+	/// Mints the part of the funds `account` is missing.
+	fn endow(&self, account: &AccountId) -> Result<(), String> {
+		let missing = self.amount.saturating_sub(Self::balance_of(self.asset, account));
+		if missing > 0 {
+			self.mint_into(account, missing)?;
+		}
+		Ok(())
+	}
+}
+
+impl ExportSnapshotCmd {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		let storage = cli.load_spec(&self.chain)?.build_storage()?;
+
+		let snapshot = sp_io::TestExternalities::new(storage).execute_with(|| {
+			let casino_jam = CasinoJam::export();
+			let battle_mogs = BattleMogs::export();
+			let owners = casino_jam
+				.assets
+				.iter()
+				.map(|asset| &asset.owner)
+				.chain(battle_mogs.assets.iter().map(|asset| &asset.owner))
+				.map(|owner| (owner.clone(), Funds::of_account(&parse_account_id(owner))))
+				.collect();
+
+			Snapshot { block: System::block_number(), casino_jam, battle_mogs, owners }
+		});
+
+		let json = serde_json::to_string_pretty(&snapshot).map_err(|e| e.to_string())?;
+		match &self.output {
+			Some(path) => std::fs::write(path, json)?,
+			None => println!("{json}"),
+		}
+
+		Ok(())
+	}
+}
+
+impl ImportSnapshotCmd {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		let snapshot = std::fs::read(&self.snapshot)?;
+		let snapshot: Snapshot = serde_json::from_slice(&snapshot)
+			.map_err(|e| format!("Invalid snapshot {}: {e}", self.snapshot.display()))?;
+
+		let mut spec = cli.load_spec(&self.chain)?;
+		let storage = spec.build_storage()?;
+		let children_default = storage.children_default.clone();
+
+		let top = sp_io::TestExternalities::new(storage).execute_with(|| {
+			for (owner, balances) in &snapshot.owners {
+				let owner = parse_account_id(owner);
+				for funds in balances {
+					funds.endow(&owner)?;
+				}
+			}
+
+			CasinoJam::import(snapshot.casino_jam)?;
+			BattleMogs::import(snapshot.battle_mogs)?;
+
+			Ok::<_, String>(storage_with_prefix(&[]))
+		})?;
+
+		spec.set_storage(Storage {
+			top: top.into_iter().map(|(key, value)| (key.0, value.0)).collect(),
+			children_default,
+		});
+		let json = sc_service::chain_ops::build_spec(&*spec, true)?;
+		match &self.output {
+			Some(path) => std::fs::write(path, json)?,
+			None => println!("{json}"),
+		}
+
+		Ok(())
+	}
+}
+
+/// A `pallet_sage` instance whose state is part of the snapshot.
+trait SnapshotGame {
+	type Pallet: PalletInfoAccess;
+	type Sage: SageApi<
+		AccountId = AccountId,
+		Balance = Balance,
+		FungiblesAssetId = FungiblesAssetId,
+		TransitionConfig = Self::Config,
+	>;
+	type Asset: Serialize + DeserializeOwned;
+	type Config: Encode + Serialize + DeserializeOwned;
+
+	fn assets() -> Vec<(<Self::Sage as SageApi>::AssetId, AccountId, Self::Asset)>;
+
+	fn insert_asset(owner: AccountId, asset: Self::Asset) -> <Self::Sage as SageApi>::AssetId;
+
+	fn organizer() -> Option<AccountId>;
+
+	fn set_organizer(organizer: Option<AccountId>);
+
+	/// Storage keys of the items exported in readable form.
+	fn readable_prefixes() -> Vec<Vec<u8>>;
+
+	fn export() -> GameSnapshot<Self::Asset, Self::Config> {
+		let assets = Self::assets()
+			.into_iter()
+			.map(|(id, owner, asset)| AssetSnapshot {
+				owner: owner.to_ss58check(),
+				funds: Funds::all(|fund| {
+					Self::Sage::inspect_asset_funds(&id, &Funds::fungible_id(fund))
+				}),
+				asset,
+			})
+			.collect();
+
+		let readable = Self::readable_prefixes();
+		let mut storage = storage_with_prefix(&pallet_prefix::<Self::Pallet>());
+		storage.retain(|key, _| !readable.iter().any(|prefix| key.0.starts_with(prefix)));
+
+		// The config is only known by its type, so its storage item is found by its value.
+		let config = Self::Sage::get_transition_config();
+		let encoded_config = config.encode();
+		let transition_config = storage
+			.iter()
+			.find(|(_, value)| value.0 == encoded_config)
+			.map(|(key, _)| key.clone())
+			.map(|key| {
+				storage.remove(&key);
+				ConfigSnapshot { key, config }
+			});
+
+		GameSnapshot {
+			organizer: Self::organizer().map(|o| o.to_ss58check()),
+			transition_config,
+			assets,
+			storage,
+		}
+	}
+
+	fn import(snapshot: GameSnapshot<Self::Asset, Self::Config>) -> Result<(), String> {
+		let _ = sp_io::storage::clear_prefix(&pallet_prefix::<Self::Pallet>(), None);
+		for (key, value) in snapshot.storage {
+			sp_io::storage::set(&key, &value);
+		}
+		if let Some(ConfigSnapshot { key, config }) = snapshot.transition_config {
+			sp_io::storage::set(&key, &config.encode());
+		}
+		Self::set_organizer(snapshot.organizer.as_deref().map(parse_account_id));
+
+		for AssetSnapshot { owner, funds, asset } in snapshot.assets {
+			let owner = parse_account_id(&owner);
+			let id = Self::insert_asset(owner.clone(), asset);
+			for funds in funds {
+				funds.mint_into(&owner, funds.amount)?;
+				Self::Sage::deposit_funds_to_asset(
+					&id,
+					&owner,
+					Funds::fungible_id(funds.asset),
+					funds.amount,
+				)
+				.map_err(|e| format!("Could not fund asset of {owner}: {e:?}"))?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+struct CasinoJam;
+
+impl SnapshotGame for CasinoJam {
+	type Pallet = SageCasinoJam;
+	type Sage = SageCasinoJamEngine;
+	type Asset = CasinoJamAsset;
+	type Config = CasinoJamTransitionConfig;
+
+	fn assets() -> Vec<(<Self::Sage as SageApi>::AssetId, AccountId, Self::Asset)> {
+		pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::iter()
+			.map(|(id, (owner, asset))| (id, owner, asset))
+			.collect()
+	}
+
+	fn insert_asset(owner: AccountId, asset: Self::Asset) -> <Self::Sage as SageApi>::AssetId {
+		let id = asset.id;
+		pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::insert(id, (owner, asset));
+		id
+	}
+
+	fn organizer() -> Option<AccountId> {
+		pallet_sage::Organizer::<Runtime, SageCasinoJamInstance>::get()
+	}
+
+	fn set_organizer(organizer: Option<AccountId>) {
+		pallet_sage::Organizer::<Runtime, SageCasinoJamInstance>::set(organizer)
+	}
+
+	fn readable_prefixes() -> Vec<Vec<u8>> {
+		vec![
+			pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::final_prefix().to_vec(),
+			pallet_sage::Organizer::<Runtime, SageCasinoJamInstance>::hashed_key().to_vec(),
+		]
+	}
+}
+
+struct BattleMogs;
+
+impl SnapshotGame for BattleMogs {
+	type Pallet = SageBattleMogs;
+	type Sage = SageBattleMogsEngine;
+	type Asset = BattleMogsAssetFor;
+	type Config = BattleMogsTransitionConfig;
+
+	fn assets() -> Vec<(<Self::Sage as SageApi>::AssetId, AccountId, Self::Asset)> {
+		pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::iter()
+			.map(|(id, (owner, asset))| (id, owner, asset))
+			.collect()
+	}
+
+	fn insert_asset(owner: AccountId, asset: Self::Asset) -> <Self::Sage as SageApi>::AssetId {
+		let id = asset.id;
+		pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::insert(id, (owner, asset));
+		id
+	}
+
+	fn organizer() -> Option<AccountId> {
+		pallet_sage::Organizer::<Runtime, SageBattleMogsInstance>::get()
+	}
+
+	fn set_organizer(organizer: Option<AccountId>) {
+		pallet_sage::Organizer::<Runtime, SageBattleMogsInstance>::set(organizer)
+	}
+
+	fn readable_prefixes() -> Vec<Vec<u8>> {
+		vec![
+			pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::final_prefix().to_vec(),
+			pallet_sage::Organizer::<Runtime, SageBattleMogsInstance>::hashed_key().to_vec(),
+		]
+	}
+}
+
+fn pallet_prefix<Pallet: PalletInfoAccess>() -> [u8; 16] {
+	twox_128(Pallet::name().as_bytes())
+}
+
+/// All storage under `prefix`, read from the current externalities.
+fn storage_with_prefix(prefix: &[u8]) -> BTreeMap<Bytes, Bytes> {
+	let mut storage = BTreeMap::new();
+	let mut key = prefix.to_vec();
+	if let Some(value) = sp_io::storage::get(&key) {
+		storage.insert(Bytes(key.clone()), Bytes(value.to_vec()));
+	}
+	while let Some(next) = sp_io::storage::next_key(&key).filter(|next| next.starts_with(prefix)) {
+		if let Some(value) = sp_io::storage::get(&next) {
+			storage.insert(Bytes(next.clone()), Bytes(value.to_vec()));
+		}
+		key = next;
+	}
+	storage
+}
@@ -1,23 +1,33 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
-use futures::FutureExt;
-use sage_playground_runtime::{self, apis::RuntimeApi, opaque::Block};
+use crate::cli::Sealing;
+use futures::{channel::mpsc, FutureExt, Stream, StreamExt};
+use sage_playground_runtime::{
+	self,
+	apis::RuntimeApi,
+	opaque::{Block, Hash},
+};
 use sc_client_api::{Backend, BlockBackend};
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
+use sc_consensus_manual_seal::{
+	consensus::{aura::AuraConsensusDataProvider, timestamp::SlotTimestampProvider},
+	EngineCommand, ManualSealParams,
+};
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncConfig};
 use sc_telemetry::{Telemetry, TelemetryWorker};
-use sc_transaction_pool_api::OffchainTransactionPoolFactory;
+use sc_transaction_pool_api::{OffchainTransactionPoolFactory, TransactionPool};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
-use std::{sync::Arc, time::Duration};
+use std::{pin::Pin, sync::Arc, time::Duration};
 
 pub(crate) type FullClient = sc_service::TFullClient<
 	Block,
 	RuntimeApi,
 	sc_executor::WasmExecutor<sp_io::SubstrateHostFunctions>,
 >;
-type FullBackend = sc_service::TFullBackend<Block>;
+pub(crate) type FullBackend = sc_service::TFullBackend<Block>;
 type FullSelectChain = sc_consensus::LongestChain<FullBackend, Block>;
+type FullPool = sc_transaction_pool::FullPool<Block, FullClient>;
 
 /// The minimum period of blocks on which justifications will be
 /// imported and generated.
@@ -28,7 +38,7 @@ pub type Service = sc_service::PartialComponents<
 	FullBackend,
 	FullSelectChain,
 	sc_consensus::DefaultImportQueue<Block>,
-	sc_transaction_pool::FullPool<Block, FullClient>,
+	FullPool,
 	(
 		sc_consensus_grandpa::GrandpaBlockImport<FullBackend, Block, FullClient, FullSelectChain>,
 		sc_consensus_grandpa::LinkHalf<Block, FullClient, FullSelectChain>,
@@ -36,7 +46,10 @@ pub type Service = sc_service::PartialComponents<
 	),
 >;
 
-pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
+pub fn new_partial(
+	config: &Configuration,
+	sealing: Option<Sealing>,
+) -> Result<Service, ServiceError> {
 	let telemetry = config
 		.telemetry_endpoints
 		.clone()
@@ -80,8 +93,14 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 		telemetry.as_ref().map(|x| x.handle()),
 	)?;
 
-	let cidp_client = client.clone();
-	let import_queue =
+	let import_queue = if sealing.is_some() {
+		sc_consensus_manual_seal::import_queue(
+			Box::new(client.clone()),
+			&task_manager.spawn_essential_handle(),
+			config.prometheus_registry(),
+		)
+	} else {
+		let cidp_client = client.clone();
 		sc_consensus_aura::import_queue::<AuraPair, _, _, _, _, _>(ImportQueueParams {
 			block_import: grandpa_block_import.clone(),
 			justification_import: Some(Box::new(grandpa_block_import.clone())),
@@ -109,7 +128,8 @@ pub fn new_partial(config: &Configuration) -> Result<Service, ServiceError> {
 			check_for_equivocation: Default::default(),
 			telemetry: telemetry.as_ref().map(|x| x.handle()),
 			compatibility_mode: Default::default(),
-		})?;
+		})?
+	};
 
 	Ok(sc_service::PartialComponents {
 		client,
@@ -128,6 +148,8 @@ pub fn new_full<
 	N: sc_network::NetworkBackend<Block, <Block as sp_runtime::traits::Block>::Hash>,
 >(
 	config: Configuration,
+	sealing: Option<Sealing>,
+	sage_indexer: bool,
 ) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
@@ -138,7 +160,7 @@ pub fn new_full<
 		select_chain,
 		transaction_pool,
 		other: (block_import, grandpa_link, mut telemetry),
-	} = new_partial(&config)?;
+	} = new_partial(&config, sealing)?;
 
 	let mut net_config = sc_network::config::FullNetworkConfiguration::<
 		Block,
@@ -201,6 +223,32 @@ pub fn new_full<
 		);
 	}
 
+	if let Some(registry) = config.prometheus_registry() {
+		let metrics = crate::metrics::SageMetrics::register(registry)
+			.map_err(|e| ServiceError::Other(format!("Failed to register sage metrics: {e}")))?;
+		task_manager.spawn_handle().spawn_blocking(
+			"sage-metrics",
+			None,
+			crate::metrics::run(client.clone(), metrics),
+		);
+	}
+
+	let sage_indexer = if sage_indexer {
+		let path = config.base_path.config_dir(config.chain_spec.id()).join("sage-indexer.sqlite3");
+		let database = crate::indexer::Database::open(&path)
+			.map_err(|e| ServiceError::Other(format!("Failed to open {}: {e}", path.display())))?;
+		let database = Arc::new(database);
+
+		task_manager.spawn_handle().spawn_blocking(
+			"sage-indexer",
+			None,
+			crate::indexer::run(client.clone(), database.clone()),
+		);
+		Some(database)
+	} else {
+		None
+	};
+
 	let role = config.role;
 	let force_authoring = config.force_authoring;
 	let backoff_authoring_blocks: Option<()> = None;
@@ -208,12 +256,25 @@ pub fn new_full<
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let (command_sink, commands) = match sealing {
+		Some(_) => {
+			let (sink, commands) = mpsc::channel(1024);
+			(Some(sink), Some(commands))
+		},
+		None => (None, None),
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
 
 		Box::new(move |_| {
-			let deps = crate::rpc::FullDeps { client: client.clone(), pool: pool.clone() };
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				command_sink: command_sink.clone(),
+				sage_indexer: sage_indexer.clone(),
+			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
 	};
@@ -233,6 +294,54 @@ pub fn new_full<
 		telemetry: telemetry.as_mut(),
 	})?;
 
+	if let (Some(sealing), Some(commands)) = (sealing, commands) {
+		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
+			task_manager.spawn_handle(),
+			client.clone(),
+			transaction_pool.clone(),
+			prometheus_registry.as_ref(),
+			telemetry.as_ref().map(|x| x.handle()),
+		);
+
+		// Timestamps advance by one slot per block, regardless of how fast blocks are sealed,
+		// so that the runtime always sees a valid Aura slot.
+		let create_inherent_data_providers = {
+			let client = client.clone();
+			move |_, ()| {
+				let client = client.clone();
+				async move {
+					let timestamp = SlotTimestampProvider::new_aura(client)?;
+					let slot =
+						sp_consensus_aura::inherents::InherentDataProvider::new(timestamp.slot());
+
+					Ok((slot, timestamp))
+				}
+			}
+		};
+
+		let manual_seal = sc_consensus_manual_seal::run_manual_seal(ManualSealParams {
+			block_import: client.clone(),
+			env: proposer_factory,
+			client: client.clone(),
+			commands_stream: manual_seal_commands(sealing, commands, &transaction_pool),
+			pool: transaction_pool,
+			select_chain,
+			consensus_data_provider: Some(Box::new(AuraConsensusDataProvider::new(client))),
+			create_inherent_data_providers,
+		});
+
+		// the manual seal authoring task is considered essential, i.e. if it
+		// fails we take down the service with it.
+		task_manager.spawn_essential_handle().spawn_blocking(
+			"manual-seal",
+			Some("block-authoring"),
+			manual_seal,
+		);
+
+		network_starter.start_network();
+		return Ok(task_manager);
+	}
+
 	if role.is_authority() {
 		let proposer_factory = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
@@ -329,3 +438,37 @@ pub fn new_full<
 	network_starter.start_network();
 	Ok(task_manager)
 }
+
+/// Commands for the manual seal authorship task: blocks are sealed according to `sealing`, and
+/// whenever requested through `commands`.
+///
+/// Grandpa is not running along manual seal, so sealed blocks are finalized right away.
+fn manual_seal_commands(
+	sealing: Sealing,
+	commands: mpsc::Receiver<EngineCommand<Hash>>,
+	transaction_pool: &FullPool,
+) -> Pin<Box<dyn Stream<Item = EngineCommand<Hash>> + Send>> {
+	let seal_new_block = |create_empty| EngineCommand::SealNewBlock {
+		create_empty,
+		finalize: true,
+		parent_hash: None,
+		sender: None,
+	};
+
+	match sealing {
+		Sealing::Instant => Box::pin(futures::stream::select(
+			transaction_pool
+				.import_notification_stream()
+				.map(move |_| seal_new_block(false)),
+			commands,
+		)),
+		Sealing::Manual => Box::pin(commands),
+		Sealing::Interval(millis) => Box::pin(futures::stream::select(
+			futures::stream::unfold((), move |()| async move {
+				futures_timer::Delay::new(Duration::from_millis(millis)).await;
+				Some((seal_new_block(true), ()))
+			}),
+			commands,
+		)),
+	}
+}
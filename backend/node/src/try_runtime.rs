@@ -0,0 +1,82 @@
+//! Try-runtime checks on the native runtime, against the state of a chain spec.
+//!
+//! The state of a live chain is checked by exporting it with `export-state` first, which
+//! writes it into a chain spec.
+
+use crate::cli::Cli;
+use sage_playground_runtime::{
+	configs::RuntimeBlockWeights, AllPalletsWithSystem, Executive, System,
+};
+
+use frame_support::traits::{TryState, TryStateSelect, UpgradeCheckSelect};
+use sc_cli::SubstrateCli;
+use sp_runtime::BuildStorage;
+
+#[derive(Debug, clap::Subcommand)]
+pub enum TryRuntimeCmd {
+	/// Run the migrations of this runtime on the state, as a runtime upgrade would.
+	OnRuntimeUpgrade(OnRuntimeUpgradeCmd),
+
+	/// Run the `try_state` hooks of the pallets on the state, without upgrading it.
+	TryState(TryStateCmd),
+}
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct OnRuntimeUpgradeCmd {
+	/// The chain spec or exported state to check, `dev` and `local` are built in.
+	#[arg(long, default_value = "dev")]
+	pub chain: String,
+
+	/// The checks to run around the upgrade: `none`, `all`, `pre-and-post` or `try-state`.
+	#[arg(long, default_value = "all")]
+	pub checks: UpgradeCheckSelect,
+}
+
+#[derive(Debug, Clone, clap::Parser)]
+pub struct TryStateCmd {
+	/// The chain spec or exported state to check, `dev` and `local` are built in.
+	#[arg(long, default_value = "dev")]
+	pub chain: String,
+
+	/// The pallets to check: `all`, `none`, `rr-<n>` for `n` pallets in round robin, or a
+	/// comma separated list of pallet names.
+	#[arg(long, default_value = "all")]
+	pub select: TryStateSelect,
+}
+
+impl TryRuntimeCmd {
+	pub fn run(&self, cli: &Cli) -> sc_cli::Result<()> {
+		match self {
+			Self::OnRuntimeUpgrade(cmd) => {
+				let storage = cli.load_spec(&cmd.chain)?.build_storage()?;
+				let weight = sp_io::TestExternalities::new(storage)
+					.execute_with(|| Executive::try_runtime_upgrade(cmd.checks))
+					.map_err(|e| format!("Runtime upgrade failed: {e:?}"))?;
+
+				let max_block = RuntimeBlockWeights::get().max_block;
+				println!(
+					"Runtime upgrade consumed {} of {} ref time and {} of {} proof size",
+					weight.ref_time(),
+					max_block.ref_time(),
+					weight.proof_size(),
+					max_block.proof_size(),
+				);
+				if weight.any_gt(max_block) {
+					return Err("Runtime upgrade exceeds the maximum block weight".into());
+				}
+			},
+			Self::TryState(cmd) => {
+				let storage = cli.load_spec(&cmd.chain)?.build_storage()?;
+				sp_io::TestExternalities::new(storage)
+					.execute_with(|| {
+						AllPalletsWithSystem::try_state(System::block_number(), cmd.select.clone())
+					})
+					.map_err(|e| format!("Try-state checks failed: {e:?}"))?;
+
+				println!("Try-state checks passed");
+			},
+		}
+
+		Ok(())
+	}
+}
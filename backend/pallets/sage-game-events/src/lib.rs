@@ -0,0 +1,63 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Sage Game Events Pallet
+//!
+//! SAGE transitions only report the assets they minted, mutated or consumed. Games describe
+//! the outcome of a transition on top of that with their own event type, which this pallet
+//! deposits as a runtime event on behalf of the account executing the transition.
+//!
+//! Each game uses its own instance of this pallet, next to its `pallet_sage` instance.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The events emitted by the game's transitions.
+		type GameEvent: Parameter + Member;
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		/// A transition executed by `account` resulted in a game event.
+		GameEvent { account: T::AccountId, event: T::GameEvent },
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Deposits `event` as a runtime event of the transition executed by `account`.
+		pub fn deposit_game_event(account: T::AccountId, event: T::GameEvent) {
+			Self::deposit_event(Event::GameEvent { account, event });
+		}
+	}
+}
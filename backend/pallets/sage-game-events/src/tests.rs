@@ -0,0 +1,29 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+
+#[test]
+fn deposit_game_event_works() {
+	new_test_ext().execute_with(|| {
+		SageGameEvents::deposit_game_event(ALICE, 7);
+
+		System::assert_last_event(RuntimeEvent::SageGameEvents(Event::GameEvent {
+			account: ALICE,
+			event: 7,
+		}));
+	});
+}
@@ -0,0 +1,201 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Sage Seat Reclaimer Pallet
+//!
+//! Frees up the seats of a SAGE game whose reservation or rent has expired. Without it,
+//! those seats linger until another player kicks the reserving player, or until the owner
+//! returns the seat to its machine.
+//!
+//! An off-chain worker looks for expired seats and submits them with an unsigned
+//! `reclaim_seats` transaction. To keep the transaction pool free of spam, unsigned
+//! transactions are only valid if every seat in them can be reclaimed, and only one of them
+//! is accepted every `UnsignedInterval` blocks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+use frame_support::pallet_prelude::{DispatchResult, MaxEncodedLen, Member, Parameter};
+use sp_std::vec::Vec;
+
+const LOG_TARGET: &str = "runtime::sage-seat-reclaimer";
+
+/// Finds and reclaims the expired seats of a game.
+pub trait SeatReclaimer {
+	type SeatId: Parameter + Member + MaxEncodedLen + Copy;
+
+	/// Seats that can be reclaimed at the current block, at most `limit` of them.
+	fn expired_seats(limit: u32) -> Vec<Self::SeatId>;
+
+	/// Whether `seat_id` can be reclaimed at the current block.
+	fn is_expired(seat_id: &Self::SeatId) -> bool;
+
+	/// Releases the player of `seat_id`, and returns the seat to its machine if its rent
+	/// has expired.
+	fn reclaim(seat_id: &Self::SeatId) -> DispatchResult;
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, storage::with_storage_layer};
+	use frame_system::{
+		offchain::{SendTransactionTypes, SubmitTransaction},
+		pallet_prelude::*,
+	};
+	use sp_runtime::traits::Saturating;
+
+	pub type SeatIdOf<T> = <<T as Config>::Reclaimer as SeatReclaimer>::SeatId;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		type Reclaimer: SeatReclaimer;
+
+		/// The maximum number of seats reclaimed by a single transaction.
+		#[pallet::constant]
+		type MaxSeatsPerCall: Get<u32>;
+
+		/// The number of blocks between two unsigned transactions.
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+
+		/// The priority of unsigned transactions in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The block from which the next unsigned transaction is accepted.
+	#[pallet::storage]
+	pub type NextUnsignedAt<T: Config> = StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		SeatReclaimed {
+			seat_id: SeatIdOf<T>,
+		},
+		/// The seat was expired, but reclaiming it failed.
+		SeatReclaimFailed {
+			seat_id: SeatIdOf<T>,
+			error: DispatchError,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// At least one seat needs to be given.
+		NoSeats,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn offchain_worker(now: BlockNumberFor<T>) {
+			if NextUnsignedAt::<T>::get() > now {
+				return;
+			}
+
+			let seats = T::Reclaimer::expired_seats(T::MaxSeatsPerCall::get());
+			if seats.is_empty() {
+				return;
+			}
+
+			let call = Call::reclaim_seats { seats: BoundedVec::truncate_from(seats) };
+			if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+				log::warn!(target: LOG_TARGET, "Failed to submit expired seats at {now:?}");
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Reclaims the given seats, skipping the ones that are not expired.
+		///
+		/// Usually submitted unsigned by the off-chain worker, but any account can pay for
+		/// reclaiming seats as well.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::reclaim_seats(seats.len() as u32))]
+		pub fn reclaim_seats(
+			origin: OriginFor<T>,
+			seats: BoundedVec<SeatIdOf<T>, T::MaxSeatsPerCall>,
+		) -> DispatchResult {
+			let is_unsigned = ensure_none(origin.clone()).is_ok();
+			if !is_unsigned {
+				ensure_signed(origin)?;
+			}
+			ensure!(!seats.is_empty(), Error::<T>::NoSeats);
+
+			for seat_id in seats {
+				if !T::Reclaimer::is_expired(&seat_id) {
+					continue;
+				}
+
+				match with_storage_layer(|| T::Reclaimer::reclaim(&seat_id)) {
+					Ok(()) => Self::deposit_event(Event::SeatReclaimed { seat_id }),
+					Err(error) => Self::deposit_event(Event::SeatReclaimFailed { seat_id, error }),
+				}
+			}
+
+			if is_unsigned {
+				let now = <frame_system::Pallet<T>>::block_number();
+				NextUnsignedAt::<T>::put(now.saturating_add(T::UnsignedInterval::get()));
+			}
+
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::reclaim_seats { seats } = call else {
+				return InvalidTransaction::Call.into();
+			};
+
+			let next_unsigned_at = NextUnsignedAt::<T>::get();
+			if next_unsigned_at > <frame_system::Pallet<T>>::block_number() {
+				return InvalidTransaction::Stale.into();
+			}
+			if seats.is_empty() || !seats.iter().all(T::Reclaimer::is_expired) {
+				return InvalidTransaction::Call.into();
+			}
+
+			ValidTransaction::with_tag_prefix("SageSeatReclaimer")
+				.priority(T::UnsignedPriority::get())
+				.and_provides(next_unsigned_at)
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+}
@@ -0,0 +1,108 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{self as pallet_sage_seat_reclaimer, SeatReclaimer};
+
+use frame_support::{derive_impl, pallet_prelude::DispatchResult, parameter_types};
+use sp_runtime::{testing::TestXt, BuildStorage, DispatchError};
+
+pub type MockAccountId = u64;
+pub type MockSeatId = u32;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+pub const ALICE: MockAccountId = 1;
+pub const UNSIGNED_INTERVAL: u64 = 10;
+/// Expired seat that fails to be reclaimed.
+pub const BROKEN_SEAT: MockSeatId = 99;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		SageSeatReclaimer: pallet_sage_seat_reclaimer,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountId = MockAccountId;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Test
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+parameter_types! {
+	pub static ExpiredSeats: Vec<MockSeatId> = vec![];
+	pub static ReclaimedSeats: Vec<MockSeatId> = vec![];
+}
+
+/// Treats the seats in [`ExpiredSeats`] as expired, and records the reclaimed ones.
+pub struct MockReclaimer;
+
+impl SeatReclaimer for MockReclaimer {
+	type SeatId = MockSeatId;
+
+	fn expired_seats(limit: u32) -> Vec<MockSeatId> {
+		ExpiredSeats::get().into_iter().take(limit as usize).collect()
+	}
+
+	fn is_expired(seat_id: &MockSeatId) -> bool {
+		ExpiredSeats::get().contains(seat_id)
+	}
+
+	fn reclaim(seat_id: &MockSeatId) -> DispatchResult {
+		if *seat_id == BROKEN_SEAT {
+			return Err(DispatchError::Other("broken seat"));
+		}
+
+		ExpiredSeats::set(ExpiredSeats::get().into_iter().filter(|id| id != seat_id).collect());
+		ReclaimedSeats::set([ReclaimedSeats::get(), vec![*seat_id]].concat());
+		Ok(())
+	}
+}
+
+parameter_types! {
+	pub const MaxSeatsPerCall: u32 = 3;
+	pub const UnsignedInterval: u64 = UNSIGNED_INTERVAL;
+	pub const UnsignedPriority: u64 = 100;
+}
+
+impl pallet_sage_seat_reclaimer::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Reclaimer = MockReclaimer;
+	type MaxSeatsPerCall = MaxSeatsPerCall;
+	type UnsignedInterval = UnsignedInterval;
+	type UnsignedPriority = UnsignedPriority;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext(expired_seats: Vec<MockSeatId>) -> sp_io::TestExternalities {
+	ExpiredSeats::set(expired_seats);
+	ReclaimedSeats::set(vec![]);
+
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
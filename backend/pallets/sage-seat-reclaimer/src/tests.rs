@@ -0,0 +1,181 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{
+		InvalidTransaction, TransactionSource, TransactionValidity, ValidateUnsigned,
+	},
+	traits::Hooks,
+	BoundedVec,
+};
+use parity_scale_codec::Decode;
+use sp_core::offchain::{testing::TestTransactionPoolExt, TransactionPoolExt};
+use sp_runtime::DispatchError;
+
+fn seats(ids: &[MockSeatId]) -> BoundedVec<MockSeatId, MaxSeatsPerCall> {
+	BoundedVec::truncate_from(ids.to_vec())
+}
+
+mod reclaim_seats {
+	use super::*;
+
+	#[test]
+	fn reclaims_expired_seats_unsigned() {
+		new_test_ext(vec![1, 2]).execute_with(|| {
+			assert_ok!(SageSeatReclaimer::reclaim_seats(RuntimeOrigin::none(), seats(&[1, 2])));
+
+			assert_eq!(ReclaimedSeats::get(), vec![1, 2]);
+			assert_eq!(NextUnsignedAt::<Test>::get(), 1 + UNSIGNED_INTERVAL);
+			System::assert_has_event(RuntimeEvent::SageSeatReclaimer(Event::SeatReclaimed {
+				seat_id: 1,
+			}));
+			System::assert_last_event(RuntimeEvent::SageSeatReclaimer(Event::SeatReclaimed {
+				seat_id: 2,
+			}));
+		});
+	}
+
+	#[test]
+	fn skips_seats_that_are_not_expired() {
+		new_test_ext(vec![2]).execute_with(|| {
+			assert_ok!(SageSeatReclaimer::reclaim_seats(
+				RuntimeOrigin::signed(ALICE),
+				seats(&[1, 2])
+			));
+
+			assert_eq!(ReclaimedSeats::get(), vec![2]);
+			// Signed transactions don't hold back the off-chain worker.
+			assert_eq!(NextUnsignedAt::<Test>::get(), 0);
+		});
+	}
+
+	#[test]
+	fn reports_failed_seats_and_continues() {
+		new_test_ext(vec![BROKEN_SEAT, 1]).execute_with(|| {
+			assert_ok!(SageSeatReclaimer::reclaim_seats(
+				RuntimeOrigin::none(),
+				seats(&[BROKEN_SEAT, 1])
+			));
+
+			assert_eq!(ReclaimedSeats::get(), vec![1]);
+			System::assert_has_event(RuntimeEvent::SageSeatReclaimer(Event::SeatReclaimFailed {
+				seat_id: BROKEN_SEAT,
+				error: DispatchError::Other("broken seat"),
+			}));
+		});
+	}
+
+	#[test]
+	fn rejects_empty_seats() {
+		new_test_ext(vec![]).execute_with(|| {
+			assert_noop!(
+				SageSeatReclaimer::reclaim_seats(RuntimeOrigin::none(), seats(&[])),
+				Error::<Test>::NoSeats
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_root() {
+		new_test_ext(vec![1]).execute_with(|| {
+			assert_noop!(
+				SageSeatReclaimer::reclaim_seats(RuntimeOrigin::root(), seats(&[1])),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+}
+
+mod validate_unsigned {
+	use super::*;
+
+	fn validate(ids: &[MockSeatId]) -> TransactionValidity {
+		SageSeatReclaimer::validate_unsigned(
+			TransactionSource::Local,
+			&Call::reclaim_seats { seats: seats(ids) },
+		)
+	}
+
+	#[test]
+	fn accepts_expired_seats() {
+		new_test_ext(vec![1, 2]).execute_with(|| {
+			let valid = validate(&[1, 2]).unwrap();
+			assert_eq!(valid.priority, UnsignedPriority::get());
+			assert_eq!(valid.provides.len(), 1);
+			assert!(valid.propagate);
+		});
+	}
+
+	#[test]
+	fn rejects_seats_that_are_not_expired() {
+		new_test_ext(vec![1]).execute_with(|| {
+			assert_eq!(validate(&[1, 2]), InvalidTransaction::Call.into());
+			assert_eq!(validate(&[]), InvalidTransaction::Call.into());
+		});
+	}
+
+	#[test]
+	fn rejects_transactions_within_the_interval() {
+		new_test_ext(vec![1, 2]).execute_with(|| {
+			assert_ok!(SageSeatReclaimer::reclaim_seats(RuntimeOrigin::none(), seats(&[1])));
+			assert_eq!(validate(&[2]), InvalidTransaction::Stale.into());
+
+			System::set_block_number(1 + UNSIGNED_INTERVAL);
+			assert_ok!(validate(&[2]));
+		});
+	}
+}
+
+mod offchain_worker {
+	use super::*;
+
+	fn submitted_calls(expired_seats: Vec<MockSeatId>, block: u64) -> Vec<Call<Test>> {
+		let (pool, pool_state) = TestTransactionPoolExt::new();
+		let mut ext = new_test_ext(expired_seats);
+		ext.register_extension(TransactionPoolExt::new(pool));
+
+		ext.execute_with(|| SageSeatReclaimer::offchain_worker(block));
+
+		let transactions = pool_state.read().transactions.clone();
+		transactions
+			.into_iter()
+			.map(|tx| {
+				let tx = Extrinsic::decode(&mut &tx[..]).unwrap();
+				assert_eq!(tx.signature, None);
+				match tx.call {
+					RuntimeCall::SageSeatReclaimer(call) => call,
+					call => panic!("unexpected call {call:?}"),
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn submits_expired_seats() {
+		assert_eq!(
+			submitted_calls(vec![1, 2, 3, 4], 1),
+			vec![Call::reclaim_seats { seats: seats(&[1, 2, 3]) }]
+		);
+	}
+
+	#[test]
+	fn submits_nothing_without_expired_seats() {
+		assert_eq!(submitted_calls(vec![], 1), vec![]);
+	}
+}
@@ -0,0 +1,56 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+pub trait WeightInfo {
+	fn reclaim_seats(n: u32) -> Weight;
+}
+
+/// Weights for `pallet_sage_seat_reclaimer` using a runtime's database weights.
+///
+/// Reclaiming a seat executes a full game transition, so the per seat component is sized
+/// after the `state_transition` of the SAGE pallet rather than this pallet's own work.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn reclaim_seats(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 1_493)
+			.saturating_add(Weight::from_parts(120_000_000, 15_000).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn reclaim_seats(n: u32) -> Weight {
+		Weight::from_parts(8_000_000, 1_493)
+			.saturating_add(Weight::from_parts(120_000_000, 15_000).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((8_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(n.into())))
+	}
+}
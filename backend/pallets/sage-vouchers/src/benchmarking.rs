@@ -0,0 +1,64 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Bounded, One, Saturating};
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn issue_vouchers() -> Result<(), BenchmarkError> {
+		let origin =
+			T::IssuerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("target", 0, 0);
+		let expires_at = <frame_system::Pallet<T>>::block_number().saturating_add(One::one());
+		Vouchers::<T, I>::insert(
+			&account,
+			Voucher { amount: T::Balance::one(), expires_at: BlockNumberFor::<T>::max_value() },
+		);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account.clone(), T::Balance::one(), expires_at);
+
+		assert!(Vouchers::<T, I>::contains_key(&account));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn revoke_vouchers() -> Result<(), BenchmarkError> {
+		let origin =
+			T::IssuerOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let account: T::AccountId = account("target", 0, 0);
+		Vouchers::<T, I>::insert(
+			&account,
+			Voucher { amount: T::Balance::one(), expires_at: BlockNumberFor::<T>::max_value() },
+		);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, account.clone());
+
+		assert!(!Vouchers::<T, I>::contains_key(&account));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,197 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! # Sage Vouchers Pallet
+//!
+//! Keeps track of vouchers issued by the organizer of a SAGE game. Vouchers are a
+//! per-account balance with an expiry block, which can be spent instead of regular
+//! funds whenever a transition is paid with `WithdrawKind::Voucher`.
+//!
+//! Each game uses its own instance of this pallet, so vouchers issued for one game
+//! can never be spent in another one.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+#[cfg(test)]
+mod mock;
+pub mod runtime_api;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+pub use weights::WeightInfo;
+
+use frame_support::pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo};
+
+/// Voucher balance held by an account, spendable until `expires_at` is reached.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct Voucher<Balance, BlockNumber> {
+	pub amount: Balance,
+	pub expires_at: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use ajuna_payment_handler::VoucherHandler;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{AtLeast32BitUnsigned, CheckedSub, Saturating, Zero};
+
+	pub type VoucherOf<T, I> = Voucher<<T as Config<I>>::Balance, BlockNumberFor<T>>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T, I = ()>(_);
+
+	#[pallet::config]
+	pub trait Config<I: 'static = ()>: frame_system::Config {
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The unit in which vouchers are accounted, matching the fee handler's balance.
+		type Balance: Member + Parameter + AtLeast32BitUnsigned + MaxEncodedLen + Copy + Default;
+
+		/// The origin allowed to issue and revoke vouchers, usually the game organizer.
+		type IssuerOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::storage]
+	pub type Vouchers<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, VoucherOf<T, I>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config<I>, I: 'static = ()> {
+		VouchersIssued {
+			account: T::AccountId,
+			amount: T::Balance,
+			total: T::Balance,
+			expires_at: BlockNumberFor<T>,
+		},
+		VouchersRevoked {
+			account: T::AccountId,
+			amount: T::Balance,
+		},
+		VouchersConsumed {
+			account: T::AccountId,
+			amount: T::Balance,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// Vouchers can only be issued with a non-zero amount.
+		ZeroVoucherAmount,
+		/// The expiry block of the vouchers has already been reached.
+		ExpiryInThePast,
+		/// The account holds no vouchers.
+		NoVouchers,
+		/// The vouchers of the account have expired.
+		VouchersExpired,
+		/// The account does not hold enough vouchers to cover the amount.
+		InsufficientVouchers,
+	}
+
+	#[pallet::call]
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Issues `amount` vouchers to `account`, valid until `expires_at`.
+		///
+		/// Vouchers the account still holds are kept and share the new expiry,
+		/// while expired ones are replaced.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::issue_vouchers())]
+		pub fn issue_vouchers(
+			origin: OriginFor<T>,
+			account: T::AccountId,
+			amount: T::Balance,
+			expires_at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::IssuerOrigin::ensure_origin(origin)?;
+			ensure!(!amount.is_zero(), Error::<T, I>::ZeroVoucherAmount);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(expires_at > now, Error::<T, I>::ExpiryInThePast);
+
+			let total = Vouchers::<T, I>::mutate(&account, |maybe_voucher| {
+				let total = match maybe_voucher {
+					Some(voucher) if voucher.expires_at > now =>
+						voucher.amount.saturating_add(amount),
+					_ => amount,
+				};
+				*maybe_voucher = Some(Voucher { amount: total, expires_at });
+				total
+			});
+
+			Self::deposit_event(Event::VouchersIssued { account, amount, total, expires_at });
+			Ok(())
+		}
+
+		/// Removes all vouchers held by `account`, regardless of their expiry.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::revoke_vouchers())]
+		pub fn revoke_vouchers(origin: OriginFor<T>, account: T::AccountId) -> DispatchResult {
+			T::IssuerOrigin::ensure_origin(origin)?;
+
+			let voucher = Vouchers::<T, I>::take(&account).ok_or(Error::<T, I>::NoVouchers)?;
+
+			Self::deposit_event(Event::VouchersRevoked { account, amount: voucher.amount });
+			Ok(())
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Returns the vouchers held by `account`, including expired ones.
+		pub fn vouchers_of(account: &T::AccountId) -> Option<VoucherOf<T, I>> {
+			Vouchers::<T, I>::get(account)
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> VoucherHandler for Pallet<T, I> {
+		type AccountId = T::AccountId;
+		type Balance = T::Balance;
+
+		fn consume_vouchers_from(
+			account: &Self::AccountId,
+			amount: Self::Balance,
+		) -> Result<(), DispatchError> {
+			let now = <frame_system::Pallet<T>>::block_number();
+
+			Vouchers::<T, I>::try_mutate_exists(account, |maybe_voucher| {
+				let voucher = maybe_voucher.as_mut().ok_or(Error::<T, I>::NoVouchers)?;
+				ensure!(voucher.expires_at > now, Error::<T, I>::VouchersExpired);
+
+				voucher.amount = voucher
+					.amount
+					.checked_sub(&amount)
+					.ok_or(Error::<T, I>::InsufficientVouchers)?;
+				if voucher.amount.is_zero() {
+					*maybe_voucher = None;
+				}
+
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::VouchersConsumed { account: account.clone(), amount });
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,80 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate as pallet_sage_vouchers;
+
+use frame_support::{derive_impl, traits::EnsureOrigin};
+use frame_system::RawOrigin;
+use sp_runtime::BuildStorage;
+
+pub type MockAccountId = u64;
+pub type MockBalance = u64;
+
+pub const ORGANIZER: MockAccountId = 1;
+pub const ALICE: MockAccountId = 2;
+pub const BOB: MockAccountId = 3;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+frame_support::construct_runtime!(
+	pub enum Test {
+		System: frame_system,
+		SageVouchers: pallet_sage_vouchers,
+	}
+);
+
+#[derive_impl(frame_system::config_preludes::TestDefaultConfig)]
+impl frame_system::Config for Test {
+	type Block = Block;
+	type AccountId = MockAccountId;
+	type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+}
+
+pub struct EnsureOrganizer;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureOrganizer {
+	type Success = MockAccountId;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who) if who == ORGANIZER => Ok(who),
+			r => Err(RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(ORGANIZER))
+	}
+}
+
+impl pallet_sage_vouchers::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = MockBalance;
+	type IssuerOrigin = EnsureOrganizer;
+	type WeightInfo = ();
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let storage = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	let mut ext = sp_io::TestExternalities::new(storage);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+pub fn run_to_block(n: u64) {
+	System::set_block_number(n);
+}
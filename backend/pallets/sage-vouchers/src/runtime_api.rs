@@ -0,0 +1,32 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::Voucher;
+use parity_scale_codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	/// Exposes the vouchers held by accounts across the SAGE games of a runtime.
+	pub trait SageVouchersApi<GameId, AccountId, Balance, BlockNumber>
+	where
+		GameId: Codec,
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// Returns the vouchers `account` holds in `game`, including expired ones.
+		fn vouchers_of(game: GameId, account: AccountId) -> Option<Voucher<Balance, BlockNumber>>;
+	}
+}
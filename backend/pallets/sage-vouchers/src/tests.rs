@@ -0,0 +1,223 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+use crate::{mock::*, *};
+
+use ajuna_payment_handler::VoucherHandler;
+use frame_support::{assert_noop, assert_ok};
+use sp_runtime::DispatchError;
+
+mod issue_vouchers {
+	use super::*;
+
+	#[test]
+	fn works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+
+			assert_eq!(
+				SageVouchers::vouchers_of(&ALICE),
+				Some(Voucher { amount: 100, expires_at: 10 })
+			);
+			System::assert_last_event(RuntimeEvent::SageVouchers(Event::VouchersIssued {
+				account: ALICE,
+				amount: 100,
+				total: 100,
+				expires_at: 10,
+			}));
+		});
+	}
+
+	#[test]
+	fn tops_up_active_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				50,
+				20
+			));
+
+			assert_eq!(
+				SageVouchers::vouchers_of(&ALICE),
+				Some(Voucher { amount: 150, expires_at: 20 })
+			);
+		});
+	}
+
+	#[test]
+	fn replaces_expired_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+			run_to_block(10);
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				50,
+				20
+			));
+
+			assert_eq!(
+				SageVouchers::vouchers_of(&ALICE),
+				Some(Voucher { amount: 50, expires_at: 20 })
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_non_organizer() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				SageVouchers::issue_vouchers(RuntimeOrigin::signed(ALICE), ALICE, 100, 10),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_invalid_parameters() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				SageVouchers::issue_vouchers(RuntimeOrigin::signed(ORGANIZER), ALICE, 0, 10),
+				Error::<Test>::ZeroVoucherAmount
+			);
+			assert_noop!(
+				SageVouchers::issue_vouchers(RuntimeOrigin::signed(ORGANIZER), ALICE, 100, 1),
+				Error::<Test>::ExpiryInThePast
+			);
+		});
+	}
+}
+
+mod revoke_vouchers {
+	use super::*;
+
+	#[test]
+	fn works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+			assert_ok!(SageVouchers::revoke_vouchers(RuntimeOrigin::signed(ORGANIZER), ALICE));
+
+			assert_eq!(SageVouchers::vouchers_of(&ALICE), None);
+			System::assert_last_event(RuntimeEvent::SageVouchers(Event::VouchersRevoked {
+				account: ALICE,
+				amount: 100,
+			}));
+		});
+	}
+
+	#[test]
+	fn rejects_account_without_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				SageVouchers::revoke_vouchers(RuntimeOrigin::signed(ORGANIZER), BOB),
+				Error::<Test>::NoVouchers
+			);
+			assert_noop!(
+				SageVouchers::revoke_vouchers(RuntimeOrigin::signed(BOB), BOB),
+				DispatchError::BadOrigin
+			);
+		});
+	}
+}
+
+mod consume_vouchers {
+	use super::*;
+
+	#[test]
+	fn works() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+
+			assert_ok!(SageVouchers::consume_vouchers_from(&ALICE, 40));
+			assert_eq!(
+				SageVouchers::vouchers_of(&ALICE),
+				Some(Voucher { amount: 60, expires_at: 10 })
+			);
+			System::assert_last_event(RuntimeEvent::SageVouchers(Event::VouchersConsumed {
+				account: ALICE,
+				amount: 40,
+			}));
+
+			assert_ok!(SageVouchers::consume_vouchers_from(&ALICE, 60));
+			assert_eq!(SageVouchers::vouchers_of(&ALICE), None);
+		});
+	}
+
+	#[test]
+	fn rejects_insufficient_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(SageVouchers::consume_vouchers_from(&BOB, 1), Error::<Test>::NoVouchers);
+
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+			assert_noop!(
+				SageVouchers::consume_vouchers_from(&ALICE, 101),
+				Error::<Test>::InsufficientVouchers
+			);
+		});
+	}
+
+	#[test]
+	fn rejects_expired_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageVouchers::issue_vouchers(
+				RuntimeOrigin::signed(ORGANIZER),
+				ALICE,
+				100,
+				10
+			));
+			run_to_block(9);
+			assert_ok!(SageVouchers::consume_vouchers_from(&ALICE, 10));
+
+			run_to_block(10);
+			assert_noop!(
+				SageVouchers::consume_vouchers_from(&ALICE, 10),
+				Error::<Test>::VouchersExpired
+			);
+		});
+	}
+}
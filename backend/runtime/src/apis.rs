@@ -15,7 +15,7 @@
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
 // External crates imports
-use alloc::{vec, vec::Vec};
+use alloc::vec::Vec;
 use frame_support::{
 	genesis_builder_helper::{build_state, get_preset},
 	weights::Weight,
@@ -33,8 +33,10 @@ use sp_version::RuntimeVersion;
 
 // Local module imports
 use super::{
-	AccountId, Aura, Balance, Block, Executive, Grandpa, InherentDataExt, Nonce, Runtime,
-	RuntimeCall, RuntimeGenesisConfig, SessionKeys, System, TransactionPayment, VERSION,
+	configs::{sage::metrics, SageGame},
+	migrations, AccountId, Aura, Balance, Block, BlockNumber, Executive, Grandpa, InherentDataExt,
+	Nonce, Runtime, RuntimeCall, RuntimeGenesisConfig, SageBattleMogsVouchers,
+	SageCasinoJamVouchers, SessionKeys, System, TransactionPayment, VERSION,
 };
 
 impl_runtime_apis! {
@@ -205,6 +207,24 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl metrics::SageMetricsApi<Block> for Runtime {
+		fn game_metrics() -> metrics::GameMetrics {
+			metrics::GameMetrics::collect()
+		}
+	}
+
+	impl pallet_sage_vouchers::runtime_api::SageVouchersApi<Block, SageGame, AccountId, Balance, BlockNumber> for Runtime {
+		fn vouchers_of(
+			game: SageGame,
+			account: AccountId,
+		) -> Option<pallet_sage_vouchers::Voucher<Balance, BlockNumber>> {
+			match game {
+				SageGame::CasinoJam => SageCasinoJamVouchers::vouchers_of(&account),
+				SageGame::BattleMogs => SageBattleMogsVouchers::vouchers_of(&account),
+			}
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -272,15 +292,17 @@ impl_runtime_apis! {
 
 	impl sp_genesis_builder::GenesisBuilder<Block> for Runtime {
 		fn build_state(config: Vec<u8>) -> sp_genesis_builder::Result {
-			build_state::<RuntimeGenesisConfig>(config)
+			build_state::<RuntimeGenesisConfig>(config)?;
+			migrations::record_genesis_layouts();
+			Ok(())
 		}
 
 		fn get_preset(id: &Option<sp_genesis_builder::PresetId>) -> Option<Vec<u8>> {
-			get_preset::<RuntimeGenesisConfig>(id, |_| None)
+			get_preset::<RuntimeGenesisConfig>(id, crate::genesis_config_presets::get_preset)
 		}
 
 		fn preset_names() -> Vec<sp_genesis_builder::PresetId> {
-			vec![]
+			crate::genesis_config_presets::preset_names()
 		}
 	}
 }
@@ -14,11 +14,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::fee_handler::*;
+use super::{fee_handler::*, EnsureSageOrganizer};
 use crate::{
-	configs::SageAssetId, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent,
-	SageAssets, SageBattleMogs, SageBattleMogsAffiliates, SageBattleMogsSeasons,
-	SageBattleMogsTournament, SageRandom,
+	configs::SageAssetId, migrations::GameAssetLayout, AccountId, Balance, Balances, BlockNumber,
+	Runtime, RuntimeEvent, SageAssets, SageBattleMogs, SageBattleMogsAffiliates,
+	SageBattleMogsGameEvents, SageBattleMogsSeasons, SageBattleMogsTournament,
+	SageBattleMogsVouchers, SageRandom,
 };
 
 use ajuna_payment_handler::{
@@ -31,11 +32,13 @@ use ajuna_primitives::{
 	season_manager::{SeasonConfig, SeasonManager},
 };
 use game_battle_mogs::prelude::*;
+use game_primitives::GameEventHandler;
 use pallet_ajuna_affiliates::traits::AffiliateUnlockRules;
 use pallet_ajuna_tournament::EntityRank;
 use pallet_sage::*;
 
 use frame_support::{
+	ensure,
 	pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo},
 	parameter_types,
 	traits::fungible::NativeOrWithId,
@@ -53,7 +56,7 @@ pub type BattleMogsFeeHandler = SageFeeHandler<
 	SageBattleMogsAffiliates,
 	AffiliateMaxLevel,
 	SageBattleMogsTournament,
-	DummyVoucherHandler,
+	SageBattleMogsVouchers,
 >;
 
 pub struct SageBattleMogsEngine;
@@ -107,8 +110,54 @@ parameter_types! {
 }
 
 pub type BattleMogsAssetFor = BattleMogsAsset<BlockNumberFor<Runtime>>;
-pub type BattleMogsGameTransition =
-	BattleMogsTransition<AccountId, BlockNumberFor<Runtime>, SageBattleMogsEngine>;
+pub type BattleMogsGameTransition = BattleMogsTransition<
+	AccountId,
+	BlockNumberFor<Runtime>,
+	SageBattleMogsEngine,
+	BattleMogsEventHandler,
+	BattleMogsPaymentFungibles,
+>;
+
+/// Deposits the events of the game transitions through [`pallet_sage_game_events`].
+pub struct BattleMogsEventHandler;
+
+impl GameEventHandler<AccountId, BattleMogsEvent> for BattleMogsEventHandler {
+	fn deposit_game_event(account: &AccountId, event: BattleMogsEvent) {
+		SageBattleMogsGameEvents::deposit_game_event(account.clone(), event);
+	}
+}
+
+parameter_types! {
+	/// Sage asset BattleMogs transitions can be paid in besides the native currency.
+	pub const BattleMogsPaymentAssetId: SageAssetId = 1;
+}
+
+/// Lets consumed mogwais settle the funds they hold in [`BattleMogsPaymentAssetId`] along
+/// with the native currency, whichever asset the consuming transition is paid in.
+pub struct BattleMogsPaymentFungibles;
+
+impl PaymentFungibles<FungiblesAssetId> for BattleMogsPaymentFungibles {
+	fn payment_fungibles() -> PaymentFungibleList<FungiblesAssetId> {
+		let payment_asset =
+			WithdrawKind::Payment(NativeOrWithId::WithId(BattleMogsPaymentAssetId::get()));
+		PaymentFungibleList::truncate_from(vec![payment_asset])
+	}
+}
+
+/// Hooks the asset layouts of the game crate into [`MigrateGameAssets`].
+///
+/// [`MigrateGameAssets`]: crate::migrations::MigrateGameAssets
+pub struct BattleMogsAssetLayout;
+
+impl GameAssetLayout for BattleMogsAssetLayout {
+	type Asset = BattleMogsAssetFor;
+
+	const VERSION: u16 = game_battle_mogs::migration::ASSET_LAYOUT_VERSION;
+
+	fn upgrade_asset_from(version: u16, encoded: &[u8]) -> Option<Self::Asset> {
+		game_battle_mogs::migration::upgrade_asset_from(version, encoded)
+	}
+}
 
 pub type BattleMogsAssetFilter = GameFilter<BlockNumberFor<Runtime>>;
 #[cfg(feature = "runtime-benchmarks")]
@@ -117,7 +166,7 @@ pub type BattleMogsBenchmarkHelper = GameBenchmarkHelper<BlockNumberFor<Runtime>
 type FungiblesAssetId = WithdrawKind<NativeOrWithId<SageAssetId>>;
 type BattleMogsAssets = NativeAndAssets<SageAssets, SageAssetId>;
 type TransferWithdraw =
-	WithdrawCreditOrVoucher<WithdrawFungibles<AccountId, BattleMogsAssets>, DummyVoucherHandler>;
+	WithdrawCreditOrVoucher<WithdrawFungibles<AccountId, BattleMogsAssets>, SageBattleMogsVouchers>;
 
 pub type SageBattleMogsInstance = pallet_sage::Instance2;
 impl pallet_sage::Config<SageBattleMogsInstance> for Runtime {
@@ -136,6 +185,20 @@ impl pallet_sage::Config<SageBattleMogsInstance> for Runtime {
 	type BenchmarkHelper = BattleMogsBenchmarkHelper;
 }
 
+pub type GameEventsBattleMogsInstance = pallet_sage_game_events::Instance2;
+impl pallet_sage_game_events::Config<GameEventsBattleMogsInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type GameEvent = BattleMogsEvent;
+}
+
+pub type VouchersBattleMogsInstance = pallet_sage_vouchers::Instance2;
+impl pallet_sage_vouchers::Config<VouchersBattleMogsInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type IssuerOrigin = EnsureSageOrganizer<SageBattleMogsInstance>;
+	type WeightInfo = ();
+}
+
 pub type BattleMogsSeasonId = u32;
 
 pub type SeasonsBattleMogsInstance = pallet_ajuna_seasons::Instance2;
@@ -152,6 +215,7 @@ impl pallet_ajuna_seasons::Config<SeasonsBattleMogsInstance> for Runtime {
 
 parameter_types! {
 	pub const AffiliateMaxLevel: u32 = 2;
+	pub const BattleMogsAffiliatorMinAchievements: u8 = 1;
 }
 
 pub type BattleMogsRuleIdentifier = AffiliateMethods<BattleMogsAction>;
@@ -160,26 +224,54 @@ pub type BattleMogsRuleIdentifier = AffiliateMethods<BattleMogsAction>;
 pub struct AffiliatesBattleMogsBenchmarkHelper;
 
 #[cfg(feature = "runtime-benchmarks")]
-impl pallet_ajuna_affiliates::BenchmarkHelper<BattleMogsRuleIdentifier, ()>
+impl pallet_ajuna_affiliates::BenchmarkHelper<BattleMogsRuleIdentifier, BattleMogsUnlockParameters>
 	for AffiliatesBattleMogsBenchmarkHelper
 {
 	fn create_rule_id(_id: u32) -> BattleMogsRuleIdentifier {
 		AffiliateMethods::StateTransition(BattleMogsAction::CreateMogwai)
 	}
 
-	fn create_params(_id: u32) {}
+	fn create_params(_id: u32) -> BattleMogsUnlockParameters {
+		BattleMogsUnlockParameters { min_completed_achievements: 0 }
+	}
 }
 
-pub struct MockAffiliateUnlockRules;
+/// Requirements an account has to fulfill before it can become an affiliator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct BattleMogsUnlockParameters {
+	pub min_completed_achievements: u8,
+}
 
-impl AffiliateUnlockRules for MockAffiliateUnlockRules {
+impl Default for BattleMogsUnlockParameters {
+	fn default() -> Self {
+		Self { min_completed_achievements: BattleMogsAffiliatorMinAchievements::get() }
+	}
+}
+
+/// Allows an account to become an affiliator once it has completed at least
+/// `min_completed_achievements` entries of its achievement table.
+pub struct BattleMogsAffiliateUnlockRules;
+
+impl AffiliateUnlockRules for BattleMogsAffiliateUnlockRules {
 	type AccountId = AccountId;
-	type UnlockParameters = ();
+	type UnlockParameters = BattleMogsUnlockParameters;
 
 	fn execute_unlock_rule_for(
-		_account: &Self::AccountId,
-		_params: Self::UnlockParameters,
+		account: &Self::AccountId,
+		params: Self::UnlockParameters,
 	) -> Result<(), DispatchError> {
+		let completed = SageBattleMogsEngine::iter_assets_from(account)
+			.find_map(|(_, asset)| match asset.variant {
+				BattleMogsVariant::Player(player) => Some(player.achievements.completed_count()),
+				_ => None,
+			})
+			.ok_or(DispatchError::Other("Account is not a registered player"))?;
+
+		ensure!(
+			completed >= params.min_completed_achievements,
+			DispatchError::Other("Not enough completed achievements to become an affiliator")
+		);
+
 		Ok(())
 	}
 }
@@ -192,8 +284,8 @@ impl pallet_ajuna_affiliates::Config<AffiliatesBattleMogsInstance> for Runtime {
 	type AccountManager = SageBattleMogs;
 	type RuleIdentifier = BattleMogsRuleIdentifier;
 	type AffiliateMaxLevel = AffiliateMaxLevel;
-	type UnlockParameters = ();
-	type AffiliatesUnlockRules = MockAffiliateUnlockRules;
+	type UnlockParameters = BattleMogsUnlockParameters;
+	type AffiliatesUnlockRules = BattleMogsAffiliateUnlockRules;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = AffiliatesBattleMogsBenchmarkHelper;
@@ -206,23 +298,34 @@ parameter_types! {
 
 pub type CasinoTournamentCategoryId = u32;
 
+/// Ranks hatched mogwais by rarity first and generation second.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub struct BattleMogsEntityRanker;
 
+impl BattleMogsEntityRanker {
+	fn score_of(asset: &BattleMogsAssetFor) -> Option<(u8, u8)> {
+		match asset.variant {
+			BattleMogsVariant::Mogwai(mogwai) if mogwai.phase != PhaseType::Bred =>
+				Some((mogwai.rarity as u8, mogwai.generation as u8)),
+			_ => None,
+		}
+	}
+}
+
 impl EntityRank for BattleMogsEntityRanker {
 	type EntityId = BattleMogsAssetId;
 	type Entity = BattleMogsAssetFor;
 
-	fn can_rank(&self, _: (&Self::EntityId, &Self::Entity)) -> bool {
-		true
+	fn can_rank(&self, (_, entity): (&Self::EntityId, &Self::Entity)) -> bool {
+		Self::score_of(entity).is_some()
 	}
 
 	fn rank_against(
 		&self,
-		_: (&Self::EntityId, &Self::Entity),
-		_: (&Self::EntityId, &Self::Entity),
+		(_, entity): (&Self::EntityId, &Self::Entity),
+		(_, other): (&Self::EntityId, &Self::Entity),
 	) -> Ordering {
-		Ordering::Equal
+		Self::score_of(entity).cmp(&Self::score_of(other))
 	}
 }
 
@@ -269,7 +372,7 @@ impl
 	}
 }
 
-type TournamentBattleMogsInstance = pallet_ajuna_tournament::Instance2;
+pub type TournamentBattleMogsInstance = pallet_ajuna_tournament::Instance2;
 impl pallet_ajuna_tournament::Config<TournamentBattleMogsInstance> for Runtime {
 	type PalletId = TournamentPalletId1;
 	type RuntimeEvent = RuntimeEvent;
@@ -14,11 +14,12 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-use super::fee_handler::*;
+use super::{fee_handler::*, EnsureSageOrganizer};
 use crate::{
-	configs::SageAssetId, AccountId, Balance, Balances, BlockNumber, Runtime, RuntimeEvent,
-	SageAssets, SageCasinoJam, SageCasinoJamAffiliates, SageCasinoJamSeasons,
-	SageCasinoJamTournament, SageRandom,
+	configs::SageAssetId, migrations::GameAssetLayout, AccountId, Balance, Balances, BlockNumber,
+	Runtime, RuntimeEvent, RuntimeOrigin, SageAssets, SageCasinoJam, SageCasinoJamAffiliates,
+	SageCasinoJamGameEvents, SageCasinoJamSeasons, SageCasinoJamTournament, SageCasinoJamVouchers,
+	SageRandom, System,
 };
 
 use ajuna_payment_handler::{
@@ -31,11 +32,14 @@ use ajuna_primitives::{
 	season_manager::{SeasonConfig, SeasonManager},
 };
 use game_casino_jam::prelude::*;
+use game_primitives::GameEventHandler;
 use pallet_ajuna_affiliates::traits::AffiliateUnlockRules;
 use pallet_ajuna_tournament::EntityRank;
 use pallet_sage::*;
+use pallet_sage_seat_reclaimer::SeatReclaimer;
 
 use frame_support::{
+	ensure,
 	pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo},
 	parameter_types,
 	traits::fungible::NativeOrWithId,
@@ -43,7 +47,10 @@ use frame_support::{
 };
 use frame_system::pallet_prelude::BlockNumberFor;
 use sp_core::H256;
-use sp_runtime::DispatchError;
+use sp_runtime::{
+	traits::AccountIdConversion, transaction_validity::TransactionPriority, DispatchError,
+	DispatchResult,
+};
 use sp_std::{cmp::Ordering, prelude::*};
 
 pub type CasinoJamFeeHandler = SageFeeHandler<
@@ -52,7 +59,7 @@ pub type CasinoJamFeeHandler = SageFeeHandler<
 	SageCasinoJamAffiliates,
 	AffiliateMaxLevel,
 	SageCasinoJamTournament,
-	DummyVoucherHandler,
+	SageCasinoJamVouchers,
 >;
 
 pub struct SageCasinoJamEngine;
@@ -106,8 +113,36 @@ parameter_types! {
 
 pub type CasinoJamAssetId = game_casino_jam::asset::AssetId;
 pub type CasinoJamAsset = Asset<BlockNumberFor<Runtime>>;
-pub type CasinoJamGameTransition =
-	CasinoJamTransition<AccountId, BlockNumberFor<Runtime>, SageCasinoJamEngine>;
+pub type CasinoJamGameTransition = CasinoJamTransition<
+	AccountId,
+	BlockNumberFor<Runtime>,
+	SageCasinoJamEngine,
+	CasinoJamEventHandler,
+>;
+
+/// Deposits the events of the game transitions through [`pallet_sage_game_events`].
+pub struct CasinoJamEventHandler;
+
+impl GameEventHandler<AccountId, CasinoJamEvent> for CasinoJamEventHandler {
+	fn deposit_game_event(account: &AccountId, event: CasinoJamEvent) {
+		SageCasinoJamGameEvents::deposit_game_event(account.clone(), event);
+	}
+}
+
+/// Hooks the asset layouts of the game crate into [`MigrateGameAssets`].
+///
+/// [`MigrateGameAssets`]: crate::migrations::MigrateGameAssets
+pub struct CasinoJamAssetLayout;
+
+impl GameAssetLayout for CasinoJamAssetLayout {
+	type Asset = CasinoJamAsset;
+
+	const VERSION: u16 = game_casino_jam::migration::ASSET_LAYOUT_VERSION;
+
+	fn upgrade_asset_from(version: u16, encoded: &[u8]) -> Option<Self::Asset> {
+		game_casino_jam::migration::upgrade_asset_from(version, encoded)
+	}
+}
 
 pub type CasinoJamAssetFilter = GameFilter<BlockNumberFor<Runtime>>;
 #[cfg(feature = "runtime-benchmarks")]
@@ -116,7 +151,7 @@ pub type CasinoJamBenchmarkHelper = GameBenchmarkHelper<BlockNumberFor<Runtime>>
 type FungiblesAssetId = WithdrawKind<NativeOrWithId<SageAssetId>>;
 type CasinoJamAssets = NativeAndAssets<SageAssets, SageAssetId>;
 type TransferWithdraw =
-	WithdrawCreditOrVoucher<WithdrawFungibles<AccountId, CasinoJamAssets>, DummyVoucherHandler>;
+	WithdrawCreditOrVoucher<WithdrawFungibles<AccountId, CasinoJamAssets>, SageCasinoJamVouchers>;
 
 pub type SageCasinoJamInstance = pallet_sage::Instance1;
 impl pallet_sage::Config<SageCasinoJamInstance> for Runtime {
@@ -135,6 +170,77 @@ impl pallet_sage::Config<SageCasinoJamInstance> for Runtime {
 	type BenchmarkHelper = CasinoJamBenchmarkHelper;
 }
 
+pub type GameEventsCasinoJamInstance = pallet_sage_game_events::Instance1;
+impl pallet_sage_game_events::Config<GameEventsCasinoJamInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type GameEvent = CasinoJamEvent;
+}
+
+pub type VouchersCasinoJamInstance = pallet_sage_vouchers::Instance1;
+impl pallet_sage_vouchers::Config<VouchersCasinoJamInstance> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = Balance;
+	type IssuerOrigin = EnsureSageOrganizer<SageCasinoJamInstance>;
+	type WeightInfo = ();
+}
+
+parameter_types! {
+	pub const SeatReclaimerId: PalletId = PalletId(*b"sage/rcl");
+	pub const MaxSeatsPerReclaim: u32 = 16;
+	pub const ReclaimUnsignedInterval: BlockNumber = 10;
+	pub const ReclaimUnsignedPriority: TransactionPriority = TransactionPriority::MAX / 2;
+}
+
+/// Reclaims CasinoJam seats whose reservation or rent has expired, on behalf of the
+/// [`SeatReclaimerId`] account.
+pub struct CasinoJamSeatReclaimer;
+
+impl SeatReclaimer for CasinoJamSeatReclaimer {
+	type SeatId = CasinoJamAssetId;
+
+	fn expired_seats(limit: u32) -> Vec<Self::SeatId> {
+		let now = System::block_number();
+		pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::iter()
+			.filter(|(_, (_, asset))| asset.is_reclaimable_seat_at(now))
+			.map(|(seat_id, _)| seat_id)
+			.take(limit as usize)
+			.collect()
+	}
+
+	fn is_expired(seat_id: &Self::SeatId) -> bool {
+		pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::get(seat_id)
+			.is_some_and(|(_, asset)| asset.is_reclaimable_seat_at(System::block_number()))
+	}
+
+	fn reclaim(seat_id: &Self::SeatId) -> DispatchResult {
+		let (_, seat) = pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::get(seat_id)
+			.ok_or(DispatchError::Other("Seat does not exist"))?;
+		let AssetVariant::Seat(variant) = seat.variant else {
+			return Err(DispatchError::Other("Asset is not a seat"));
+		};
+		let machine_id = variant.machine_id.ok_or(DispatchError::Other("Seat has no machine"))?;
+
+		let mut asset_ids = vec![*seat_id, machine_id];
+		asset_ids.extend(variant.player_id);
+
+		SageCasinoJam::state_transition(
+			RuntimeOrigin::signed(SeatReclaimerId::get().into_account_truncating()),
+			CasinoAction::Reclaim,
+			asset_ids,
+			None,
+		)
+	}
+}
+
+impl pallet_sage_seat_reclaimer::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Reclaimer = CasinoJamSeatReclaimer;
+	type MaxSeatsPerCall = MaxSeatsPerReclaim;
+	type UnsignedInterval = ReclaimUnsignedInterval;
+	type UnsignedPriority = ReclaimUnsignedPriority;
+	type WeightInfo = pallet_sage_seat_reclaimer::weights::SubstrateWeight<Runtime>;
+}
+
 pub type CasinoJamSeasonId = u32;
 
 pub type SeasonsCasinoJamInstance = pallet_ajuna_seasons::Instance1;
@@ -151,6 +257,7 @@ impl pallet_ajuna_seasons::Config<SeasonsCasinoJamInstance> for Runtime {
 
 parameter_types! {
 	pub const AffiliateMaxLevel: u32 = 2;
+	pub const CasinoJamAffiliatorMinGambles: u32 = 10;
 }
 
 pub type CasinoJamRuleIdentifier = AffiliateMethods<CasinoAction>;
@@ -159,26 +266,54 @@ pub type CasinoJamRuleIdentifier = AffiliateMethods<CasinoAction>;
 pub struct AffiliatesCasinoJamBenchmarkHelper;
 
 #[cfg(feature = "runtime-benchmarks")]
-impl pallet_ajuna_affiliates::BenchmarkHelper<CasinoJamRuleIdentifier, ()>
+impl pallet_ajuna_affiliates::BenchmarkHelper<CasinoJamRuleIdentifier, CasinoJamUnlockParameters>
 	for AffiliatesCasinoJamBenchmarkHelper
 {
 	fn create_rule_id(_id: u32) -> CasinoJamRuleIdentifier {
 		AffiliateMethods::StateTransition(CasinoAction::Create(AssetType::Player))
 	}
 
-	fn create_params(_id: u32) {}
+	fn create_params(_id: u32) -> CasinoJamUnlockParameters {
+		CasinoJamUnlockParameters { min_gambles: 0 }
+	}
+}
+
+/// Requirements an account has to fulfill before it can become an affiliator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub struct CasinoJamUnlockParameters {
+	pub min_gambles: u32,
+}
+
+impl Default for CasinoJamUnlockParameters {
+	fn default() -> Self {
+		Self { min_gambles: CasinoJamAffiliatorMinGambles::get() }
+	}
 }
 
-pub struct MockAffiliateUnlockRules;
+/// Allows an account to become an affiliator once its tracker has recorded
+/// at least `min_gambles` gambles.
+pub struct CasinoJamAffiliateUnlockRules;
 
-impl AffiliateUnlockRules for MockAffiliateUnlockRules {
+impl AffiliateUnlockRules for CasinoJamAffiliateUnlockRules {
 	type AccountId = AccountId;
-	type UnlockParameters = ();
+	type UnlockParameters = CasinoJamUnlockParameters;
 
 	fn execute_unlock_rule_for(
-		_account: &Self::AccountId,
-		_params: Self::UnlockParameters,
+		account: &Self::AccountId,
+		params: Self::UnlockParameters,
 	) -> Result<(), DispatchError> {
+		let gamble_count = SageCasinoJamEngine::iter_assets_from(account)
+			.find_map(|(_, asset)| match asset.variant {
+				AssetVariant::Player(PlayerVariant::Tracker(tracker)) => Some(tracker.gamble_count),
+				_ => None,
+			})
+			.ok_or(DispatchError::Other("Account has no tracker asset"))?;
+
+		ensure!(
+			gamble_count >= params.min_gambles,
+			DispatchError::Other("Not enough gambles to become an affiliator")
+		);
+
 		Ok(())
 	}
 }
@@ -191,8 +326,8 @@ impl pallet_ajuna_affiliates::Config<AffiliatesCasinoJamInstance> for Runtime {
 	type AccountManager = SageCasinoJam;
 	type RuleIdentifier = CasinoJamRuleIdentifier;
 	type AffiliateMaxLevel = AffiliateMaxLevel;
-	type UnlockParameters = ();
-	type AffiliatesUnlockRules = MockAffiliateUnlockRules;
+	type UnlockParameters = CasinoJamUnlockParameters;
+	type AffiliatesUnlockRules = CasinoJamAffiliateUnlockRules;
 	type WeightInfo = ();
 	#[cfg(feature = "runtime-benchmarks")]
 	type BenchmarkHelper = AffiliatesCasinoJamBenchmarkHelper;
@@ -205,23 +340,33 @@ parameter_types! {
 
 pub type CasinoTournamentCategoryId = u32;
 
+/// Ranks player trackers by the number of gambles they have performed.
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
 pub struct CasinoJamEntityRanker;
 
+impl CasinoJamEntityRanker {
+	fn gamble_count_of(asset: &CasinoJamAsset) -> Option<u32> {
+		match asset.variant {
+			AssetVariant::Player(PlayerVariant::Tracker(tracker)) => Some(tracker.gamble_count),
+			_ => None,
+		}
+	}
+}
+
 impl EntityRank for CasinoJamEntityRanker {
 	type EntityId = AssetId;
 	type Entity = CasinoJamAsset;
 
-	fn can_rank(&self, _: (&Self::EntityId, &Self::Entity)) -> bool {
-		true
+	fn can_rank(&self, (_, entity): (&Self::EntityId, &Self::Entity)) -> bool {
+		Self::gamble_count_of(entity).is_some()
 	}
 
 	fn rank_against(
 		&self,
-		_: (&Self::EntityId, &Self::Entity),
-		_: (&Self::EntityId, &Self::Entity),
+		(_, entity): (&Self::EntityId, &Self::Entity),
+		(_, other): (&Self::EntityId, &Self::Entity),
 	) -> Ordering {
-		Ordering::Equal
+		Self::gamble_count_of(entity).cmp(&Self::gamble_count_of(other))
 	}
 }
 
@@ -268,7 +413,7 @@ impl
 	}
 }
 
-type TournamentCasinoJamInstance = pallet_ajuna_tournament::Instance1;
+pub type TournamentCasinoJamInstance = pallet_ajuna_tournament::Instance1;
 impl pallet_ajuna_tournament::Config<TournamentCasinoJamInstance> for Runtime {
 	type PalletId = TournamentPalletId1;
 	type RuntimeEvent = RuntimeEvent;
@@ -0,0 +1,115 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Aggregated game state, exported by the node as `sage` Prometheus metrics.
+
+use super::{
+	battle_mogs::{BattleMogsAssetFor, SageBattleMogsInstance},
+	casino_jam::{CasinoJamAsset, CasinoJamAssetId, SageCasinoJamEngine, SageCasinoJamInstance},
+	SageGame,
+};
+use crate::{Balance, Runtime};
+
+use ajuna_primitives::{payment_handler::NativeId, sage_api::SageApi};
+use frame_support::pallet_prelude::{Decode, Encode, TypeInfo};
+use game_battle_mogs::prelude::BattleMogsVariant;
+use game_casino_jam::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+sp_api::decl_runtime_apis! {
+	/// Exposes aggregated state of the SAGE games, to be polled for monitoring.
+	pub trait SageMetricsApi {
+		/// Returns the current asset counts, locked funds and jackpots of the games.
+		fn game_metrics() -> GameMetrics;
+	}
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct GameMetrics {
+	/// Number of assets per game and variant, e.g. `human` or `mogwai`.
+	pub assets: Vec<(SageGame, Vec<u8>, u32)>,
+	/// Native funds held by CasinoJam machines.
+	pub machine_funds: Balance,
+	/// Native funds held by CasinoJam seats.
+	pub seat_funds: Balance,
+	/// Jackpot of every CasinoJam bandit.
+	pub jackpots: Vec<(CasinoJamAssetId, u32)>,
+}
+
+impl GameMetrics {
+	/// Iterates the assets of both games, which is expensive and only meant to be called
+	/// off-chain.
+	pub fn collect() -> Self {
+		let mut metrics = GameMetrics::default();
+		let native = <SageCasinoJamEngine as SageApi>::FungiblesAssetId::get_native_id();
+
+		let mut casino_jam_assets = BTreeMap::<&'static str, u32>::new();
+		for (_, asset) in pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::iter_values() {
+			*casino_jam_assets.entry(casino_jam_variant(&asset)).or_default() += 1;
+
+			match &asset.variant {
+				AssetVariant::Machine(machine) => {
+					let funds = SageCasinoJamEngine::inspect_asset_funds(&asset.id, &native);
+					metrics.machine_funds = metrics.machine_funds.saturating_add(funds);
+					let MachineSubVariant::Bandit(bandit) = machine.sub_variant;
+					metrics.jackpots.push((asset.id, bandit.jackpot));
+				},
+				AssetVariant::Seat(_) => {
+					let funds = SageCasinoJamEngine::inspect_asset_funds(&asset.id, &native);
+					metrics.seat_funds = metrics.seat_funds.saturating_add(funds);
+				},
+				AssetVariant::Player(_) => {},
+			}
+		}
+
+		let mut battle_mogs_assets = BTreeMap::<&'static str, u32>::new();
+		for (_, asset) in pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::iter_values() {
+			*battle_mogs_assets.entry(battle_mogs_variant(&asset)).or_default() += 1;
+		}
+
+		metrics.assets = casino_jam_assets
+			.into_iter()
+			.map(|(variant, count)| (SageGame::CasinoJam, variant, count))
+			.chain(
+				battle_mogs_assets
+					.into_iter()
+					.map(|(variant, count)| (SageGame::BattleMogs, variant, count)),
+			)
+			.map(|(game, variant, count)| (game, variant.as_bytes().to_vec(), count))
+			.collect();
+
+		metrics
+	}
+}
+
+fn casino_jam_variant(asset: &CasinoJamAsset) -> &'static str {
+	match &asset.variant {
+		AssetVariant::Player(PlayerVariant::Human(_)) => "human",
+		AssetVariant::Player(PlayerVariant::Tracker(_)) => "tracker",
+		AssetVariant::Machine(machine) => match machine.sub_variant {
+			MachineSubVariant::Bandit(_) => "bandit",
+		},
+		AssetVariant::Seat(_) => "seat",
+	}
+}
+
+fn battle_mogs_variant(asset: &BattleMogsAssetFor) -> &'static str {
+	match &asset.variant {
+		BattleMogsVariant::Mogwai(_) => "mogwai",
+		BattleMogsVariant::Player(_) => "player",
+		BattleMogsVariant::Item(_) => "item",
+	}
+}
@@ -14,19 +14,59 @@
 // You should have received a copy of the GNU Affero General Public License
 // along with this program.  If not, see <http://www.gnu.org/licenses/>.
 
-mod battle_mogs;
-mod casino_jam;
+pub mod battle_mogs;
+pub mod casino_jam;
+pub mod metrics;
+
+use crate::{AccountId, Runtime, RuntimeOrigin};
+
+use frame_support::{
+	pallet_prelude::{Decode, Encode, MaxEncodedLen, TypeInfo},
+	traits::EnsureOrigin,
+};
+use frame_system::RawOrigin;
+use sp_std::marker::PhantomData;
+
+/// Identifies the SAGE games of this runtime in runtime API queries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum SageGame {
+	CasinoJam,
+	BattleMogs,
+}
+
+/// Ensures that the origin is signed by the organizer of the SAGE instance `I`.
+pub struct EnsureSageOrganizer<I>(PhantomData<I>);
+
+impl<I: 'static> EnsureOrigin<RuntimeOrigin> for EnsureSageOrganizer<I>
+where
+	Runtime: pallet_sage::Config<I>,
+{
+	type Success = AccountId;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		o.into().and_then(|o| match o {
+			RawOrigin::Signed(who)
+				if pallet_sage::Organizer::<Runtime, I>::get().as_ref() == Some(&who) =>
+				Ok(who),
+			r => Err(RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		pallet_sage::Organizer::<Runtime, I>::get().map(RuntimeOrigin::signed).ok_or(())
+	}
+}
 
 mod fee_handler {
-	use crate::{AccountId, Balance, Balances};
+	use crate::{AccountId, Balances};
 
 	use ajuna_payment_handler::{
-		AllowAllAssets, AssetGameFeeHandler, VoucherHandler, WithdrawCreditOrVoucher,
-		WithdrawFungibles, WithdrawWhitelistedCredit,
+		AllowAllAssets, AssetGameFeeHandler, WithdrawCreditOrVoucher, WithdrawFungibles,
+		WithdrawWhitelistedCredit,
 	};
 
 	use frame_support::traits::fungible::{NativeFromLeft, NativeOrWithId, UnionOf};
-	use sp_runtime::DispatchError;
 
 	pub type NativeAndAssets<Assets, AssetId> =
 		UnionOf<Balances, Assets, NativeFromLeft, NativeOrWithId<AssetId>, AccountId>;
@@ -53,23 +93,4 @@ mod fee_handler {
 		AffiliateMaxLevel,
 		Tournament,
 	>;
-
-	/*pub type FungiblesAssetId = WithdrawKind<NativeOrWithId<SageAssetId>>;
-	pub type SageNativeAndAssets = NativeAndAssets<SageAssets, SageAssetId>;
-	pub type TransferWithdraw =
-	WithdrawCreditOrVoucher<WithdrawFungibles<AccountId, SageNativeAndAssets>, DummyVoucherHandler>;*/
-
-	pub struct DummyVoucherHandler;
-
-	impl VoucherHandler for DummyVoucherHandler {
-		type AccountId = AccountId;
-		type Balance = Balance;
-
-		fn consume_vouchers_from(
-			_account: &Self::AccountId,
-			_amount: Self::Balance,
-		) -> Result<(), DispatchError> {
-			Ok(())
-		}
-	}
 }
@@ -0,0 +1,148 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Genesis presets of the runtime, mirroring the development and local testnet chain specs of
+//! the node.
+
+use crate::{
+	AccountId, AuraConfig, BalancesConfig, BlockNumber, GrandpaConfig, RuntimeGenesisConfig,
+	SageBattleMogsConfig, SageBattleMogsSeasonsConfig, SageCasinoJamConfig,
+	SageCasinoJamSeasonsConfig, SudoConfig,
+};
+
+use alloc::{vec, vec::Vec};
+use serde_json::Value;
+use sp_consensus_aura::sr25519::AuthorityId as AuraId;
+use sp_consensus_grandpa::AuthorityId as GrandpaId;
+use sp_genesis_builder::PresetId;
+use sp_keyring::{Ed25519Keyring, Sr25519Keyring};
+
+/// Season the games start with at genesis.
+pub const GENESIS_SEASON_ID: u32 = 0;
+/// Duration of the genesis season, ~4 hours.
+pub const GENESIS_SEASON_DURATION: BlockNumber = 10 * 60 * 4;
+
+/// Configure initial storage state for FRAME modules, with `organizer` running the first
+/// season of both games.
+fn testnet_genesis(
+	initial_authorities: Vec<(AuraId, GrandpaId)>,
+	endowed_accounts: Vec<AccountId>,
+	root: AccountId,
+	organizer: AccountId,
+) -> Value {
+	let config = RuntimeGenesisConfig {
+		balances: BalancesConfig {
+			balances: endowed_accounts.into_iter().map(|account| (account, 1 << 60)).collect(),
+		},
+		aura: AuraConfig { authorities: initial_authorities.iter().map(|x| x.0.clone()).collect() },
+		grandpa: GrandpaConfig {
+			authorities: initial_authorities.iter().map(|x| (x.1.clone(), 1)).collect(),
+			..Default::default()
+		},
+		sudo: SudoConfig { key: Some(root) },
+		sage_casino_jam: SageCasinoJamConfig {
+			organizer: Some(organizer.clone()),
+			season: Some(GENESIS_SEASON_ID),
+			..Default::default()
+		},
+		sage_casino_jam_seasons: SageCasinoJamSeasonsConfig {
+			season: Some((GENESIS_SEASON_ID, GENESIS_SEASON_DURATION)),
+			..Default::default()
+		},
+		sage_battle_mogs: SageBattleMogsConfig {
+			organizer: Some(organizer),
+			season: Some(GENESIS_SEASON_ID),
+			..Default::default()
+		},
+		sage_battle_mogs_seasons: SageBattleMogsSeasonsConfig {
+			season: Some((GENESIS_SEASON_ID, GENESIS_SEASON_DURATION)),
+			..Default::default()
+		},
+		..Default::default()
+	};
+
+	serde_json::to_value(config).expect("Could not build genesis config.")
+}
+
+fn authority_keys(keyring: (Sr25519Keyring, Ed25519Keyring)) -> (AuraId, GrandpaId) {
+	(keyring.0.public().into(), keyring.1.public().into())
+}
+
+/// Return the development genesis config, with Alice as authority, sudo and organizer.
+pub fn development_config_genesis() -> Value {
+	testnet_genesis(
+		vec![authority_keys((Sr25519Keyring::Alice, Ed25519Keyring::Alice))],
+		vec![
+			Sr25519Keyring::Alice.to_account_id(),
+			Sr25519Keyring::Bob.to_account_id(),
+			Sr25519Keyring::AliceStash.to_account_id(),
+			Sr25519Keyring::BobStash.to_account_id(),
+		],
+		Sr25519Keyring::Alice.to_account_id(),
+		Sr25519Keyring::Alice.to_account_id(),
+	)
+}
+
+/// Return the local testnet genesis config, with Alice and Bob as authorities.
+pub fn local_config_genesis() -> Value {
+	testnet_genesis(
+		vec![
+			authority_keys((Sr25519Keyring::Alice, Ed25519Keyring::Alice)),
+			authority_keys((Sr25519Keyring::Bob, Ed25519Keyring::Bob)),
+		],
+		[
+			Sr25519Keyring::Alice,
+			Sr25519Keyring::Bob,
+			Sr25519Keyring::Charlie,
+			Sr25519Keyring::Dave,
+			Sr25519Keyring::Eve,
+			Sr25519Keyring::Ferdie,
+			Sr25519Keyring::AliceStash,
+			Sr25519Keyring::BobStash,
+			Sr25519Keyring::CharlieStash,
+			Sr25519Keyring::DaveStash,
+			Sr25519Keyring::EveStash,
+			Sr25519Keyring::FerdieStash,
+		]
+		.into_iter()
+		.map(|keyring| keyring.to_account_id())
+		.collect(),
+		Sr25519Keyring::Alice.to_account_id(),
+		Sr25519Keyring::Alice.to_account_id(),
+	)
+}
+
+/// Provides the JSON representation of predefined genesis config for given `id`.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match id.as_ref() {
+		sp_genesis_builder::DEV_RUNTIME_PRESET => development_config_genesis(),
+		sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET => local_config_genesis(),
+		_ => return None,
+	};
+	Some(
+		serde_json::to_string(&patch)
+			.expect("serialization to json is expected to work. qed.")
+			.into_bytes(),
+	)
+}
+
+/// List of supported presets.
+pub fn preset_names() -> Vec<PresetId> {
+	vec![
+		PresetId::from(sp_genesis_builder::DEV_RUNTIME_PRESET),
+		PresetId::from(sp_genesis_builder::LOCAL_TESTNET_RUNTIME_PRESET),
+	]
+}
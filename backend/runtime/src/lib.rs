@@ -23,10 +23,16 @@ pub mod apis;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarks;
 pub mod configs;
+pub mod genesis_config_presets;
+pub mod migrations;
 
 extern crate alloc;
 
 use alloc::vec::Vec;
+use configs::sage::{
+	battle_mogs::{BattleMogsAssetLayout, SageBattleMogsInstance},
+	casino_jam::{CasinoJamAssetLayout, SageCasinoJamInstance},
+};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{BlakeTwo256, IdentifyAccount, Verify},
@@ -178,7 +184,10 @@ pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
 #[allow(unused_parens)]
-type Migrations = ();
+type Migrations = (
+	migrations::MigrateGameAssets<SageCasinoJamInstance, CasinoJamAssetLayout>,
+	migrations::MigrateGameAssets<SageBattleMogsInstance, BattleMogsAssetLayout>,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
@@ -242,6 +251,12 @@ mod runtime {
 	pub type SageCasinoJamSeasons = pallet_ajuna_seasons<Instance1>;
 	#[runtime::pallet_index(13)]
 	pub type SageCasinoJamTournament = pallet_ajuna_tournament<Instance1>;
+	#[runtime::pallet_index(14)]
+	pub type SageCasinoJamVouchers = pallet_sage_vouchers<Instance1>;
+	#[runtime::pallet_index(15)]
+	pub type SageCasinoJamGameEvents = pallet_sage_game_events<Instance1>;
+	#[runtime::pallet_index(16)]
+	pub type SageCasinoJamSeatReclaimer = pallet_sage_seat_reclaimer;
 
 	#[runtime::pallet_index(20)]
 	pub type SageBattleMogs = pallet_sage<Instance2>;
@@ -251,4 +266,8 @@ mod runtime {
 	pub type SageBattleMogsSeasons = pallet_ajuna_seasons<Instance2>;
 	#[runtime::pallet_index(23)]
 	pub type SageBattleMogsTournament = pallet_ajuna_tournament<Instance2>;
+	#[runtime::pallet_index(24)]
+	pub type SageBattleMogsVouchers = pallet_sage_vouchers<Instance2>;
+	#[runtime::pallet_index(25)]
+	pub type SageBattleMogsGameEvents = pallet_sage_game_events<Instance2>;
 }
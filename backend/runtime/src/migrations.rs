@@ -0,0 +1,202 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+//! Upgrades of the game assets stored by the SAGE instances.
+//!
+//! The games encode their assets into `pallet_sage` storage as is, so whenever a game crate
+//! changes its asset layout, the stored assets have to be translated. Each game crate keeps
+//! its previous layouts in a `migration` module, which is hooked up here through
+//! [`GameAssetLayout`].
+
+use crate::{
+	configs::sage::{
+		battle_mogs::{BattleMogsAssetLayout, SageBattleMogsInstance},
+		casino_jam::{CasinoJamAssetLayout, SageCasinoJamInstance},
+	},
+	AccountId, Runtime,
+};
+
+use core::marker::PhantomData;
+use frame_support::{
+	storage::unhashed,
+	storage_alias,
+	traits::{Get, OnRuntimeUpgrade},
+	weights::Weight,
+};
+use parity_scale_codec::{Decode, Encode};
+
+#[cfg(feature = "try-runtime")]
+use alloc::vec::Vec;
+
+const LOG_TARGET: &str = "runtime::migrate-game-assets";
+
+/// Asset layouts of a game, as described by the `migration` module of its crate.
+pub trait GameAssetLayout {
+	type Asset: Encode;
+
+	/// Layout version of the assets as currently defined by the game.
+	const VERSION: u16;
+
+	/// Decodes an asset of the given layout `version` and upgrades it to the current layout.
+	fn upgrade_asset_from(version: u16, encoded: &[u8]) -> Option<Self::Asset>;
+}
+
+/// Layout version of the assets stored by the SAGE instance `I`.
+#[storage_alias]
+pub type AssetLayoutVersion<T: pallet_sage::Config<I>, I: 'static> =
+	StorageValue<pallet_sage::Pallet<T, I>, u16, ValueQuery>;
+
+/// Records the current asset layout of every game on a new chain, whose assets never need
+/// upgrading.
+pub fn record_genesis_layouts() {
+	MigrateGameAssets::<SageCasinoJamInstance, CasinoJamAssetLayout>::on_genesis();
+	MigrateGameAssets::<SageBattleMogsInstance, BattleMogsAssetLayout>::on_genesis();
+}
+
+/// Weight of upgrading `asset_count` assets with [`MigrateGameAssets`] at most, reading and
+/// rewriting each of them besides the layout version.
+pub fn upgrade_weight_for(asset_count: u64) -> Weight {
+	let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+	db_weight.reads_writes(asset_count.saturating_add(1), asset_count.saturating_add(1))
+}
+
+/// Number of assets of a single SAGE instance [`MigrateGameAssets`] can upgrade within the
+/// weight limit of a block.
+pub fn max_upgraded_assets() -> u64 {
+	let max_block = <Runtime as frame_system::Config>::BlockWeights::get().max_block;
+	let per_asset = upgrade_weight_for(1).saturating_sub(upgrade_weight_for(0));
+	max_block
+		.saturating_sub(upgrade_weight_for(0))
+		.checked_div_per_component(&per_asset)
+		.unwrap_or(u64::MAX)
+}
+
+/// Upgrades all assets of the SAGE instance `I` to the current layout of the game.
+///
+/// Assets are decoded with the layout recorded in [`AssetLayoutVersion`] only, so bytes that
+/// happen to match a newer layout as well are never misread. Assets that do not match the
+/// recorded layout are left as they are and logged.
+///
+/// All assets are upgraded at once in the block enacting the runtime upgrade, as the games
+/// cannot read assets of mixed layouts. This limits an instance to [`max_upgraded_assets`]
+/// assets when changing its layout, which `try-runtime` checks before the upgrade. Instances
+/// holding more assets need a multi-block migration that suspends their transitions instead.
+pub struct MigrateGameAssets<I, Layout>(PhantomData<(I, Layout)>);
+
+impl<I: 'static, Layout: GameAssetLayout> MigrateGameAssets<I, Layout>
+where
+	Runtime: pallet_sage::Config<I>,
+{
+	/// Splits a raw `(owner, asset)` entry, returning the encoded asset.
+	fn encoded_asset(raw: &[u8]) -> Option<(AccountId, &[u8])> {
+		let input = &mut &raw[..];
+		let owner = AccountId::decode(input).ok()?;
+		Some((owner, *input))
+	}
+
+	/// Records the current layout, see [`record_genesis_layouts`].
+	pub fn on_genesis() {
+		AssetLayoutVersion::<Runtime, I>::put(Layout::VERSION);
+	}
+}
+
+impl<I: 'static, Layout: GameAssetLayout> OnRuntimeUpgrade for MigrateGameAssets<I, Layout>
+where
+	Runtime: pallet_sage::Config<I>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let on_chain_version = AssetLayoutVersion::<Runtime, I>::get();
+		if on_chain_version >= Layout::VERSION {
+			return db_weight.reads(1);
+		}
+
+		let (mut reads, mut writes, mut undecodable) = (1_u64, 1_u64, 0_u64);
+		for asset_id in pallet_sage::Assets::<Runtime, I>::iter_keys() {
+			reads += 1;
+			let key = pallet_sage::Assets::<Runtime, I>::hashed_key_for(&asset_id);
+			let Some(raw) = unhashed::get_raw(&key) else { continue };
+
+			let upgraded = Self::encoded_asset(&raw).and_then(|(owner, encoded)| {
+				Layout::upgrade_asset_from(on_chain_version, encoded).map(|asset| (owner, asset))
+			});
+			match upgraded {
+				Some(entry) => {
+					let entry = entry.encode();
+					if entry != raw {
+						unhashed::put_raw(&key, &entry);
+						writes += 1;
+					}
+				},
+				None => undecodable += 1,
+			}
+		}
+
+		if undecodable > 0 {
+			log::error!(
+				target: LOG_TARGET,
+				"{undecodable} assets do not match layout version {on_chain_version}"
+			);
+		}
+		if reads - 1 > max_upgraded_assets() {
+			log::error!(
+				target: LOG_TARGET,
+				"Upgraded {} assets, exceeding the {} that fit a block",
+				reads - 1,
+				max_upgraded_assets()
+			);
+		}
+		AssetLayoutVersion::<Runtime, I>::put(Layout::VERSION);
+		db_weight.reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, sp_runtime::TryRuntimeError> {
+		let asset_count = pallet_sage::Assets::<Runtime, I>::iter_keys().count() as u64;
+		if AssetLayoutVersion::<Runtime, I>::get() < Layout::VERSION {
+			frame_support::ensure!(
+				asset_count <= max_upgraded_assets(),
+				"Too many assets to upgrade within a block"
+			);
+		}
+		Ok(asset_count.encode())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), sp_runtime::TryRuntimeError> {
+		let asset_count = u64::decode(&mut &state[..])
+			.map_err(|_| "Could not decode the pre-upgrade asset count")?;
+
+		frame_support::ensure!(
+			AssetLayoutVersion::<Runtime, I>::get() == Layout::VERSION,
+			"Asset layout version has not been updated"
+		);
+
+		let mut migrated = 0_u64;
+		for asset_id in pallet_sage::Assets::<Runtime, I>::iter_keys() {
+			let key = pallet_sage::Assets::<Runtime, I>::hashed_key_for(&asset_id);
+			let raw = unhashed::get_raw(&key).ok_or("Asset disappeared during the upgrade")?;
+			let (_, encoded) =
+				Self::encoded_asset(&raw).ok_or("Asset owner could not be decoded")?;
+			Layout::upgrade_asset_from(Layout::VERSION, encoded)
+				.ok_or("Asset does not match the current layout")?;
+			migrated += 1;
+		}
+
+		frame_support::ensure!(migrated == asset_count, "Asset count changed during the upgrade");
+		Ok(())
+	}
+}
@@ -0,0 +1,1219 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use sage_playground_runtime::{
+	configs::sage::battle_mogs::{
+		BattleMogsAssetFor, BattleMogsAssetId, BattleMogsEntityRanker, BattleMogsPaymentAssetId,
+		SageBattleMogsEngine, SageBattleMogsInstance, TournamentBattleMogsInstance,
+	},
+	AccountId, Balance, BlockNumber, Runtime, RuntimeEvent, RuntimeOrigin, SageAssets,
+	SageBattleMogs, SageBattleMogsAffiliates, SageBattleMogsTournament, UNIT,
+};
+
+use ajuna_primitives::{
+	payment_handler::{NativeId, WithdrawKind},
+	sage_api::SageApi,
+	trade_manager::{TradeManager, TransferManager},
+};
+use frame_support::{assert_ok, traits::fungible::NativeOrWithId};
+use game_battle_mogs::{
+	config::{Experience, Pricing, XP_PER_LEVEL},
+	lineage::ancestors,
+	prelude::*,
+	stats::Stats,
+	BattleMogsAction,
+};
+use pallet_ajuna_tournament::{EntityRank, TournamentConfig, TournamentRanker};
+
+/// Blocks a freshly created mogwai has to wait before it can hatch.
+const HATCH_DELAY: u32 = 100;
+/// Smallest unit of the pairing prices.
+const UNIT_PRICE: Balance = game_battle_mogs::config::MILLIARD as Balance;
+
+fn transition(account: &AccountId, action: BattleMogsAction, asset_ids: Vec<BattleMogsAssetId>) {
+	assert_ok!(SageBattleMogs::state_transition(
+		RuntimeOrigin::signed(account.clone()),
+		action.clone(),
+		asset_ids,
+		None,
+	));
+	assert_eq!(
+		events().last(),
+		Some(&RuntimeEvent::SageBattleMogs(pallet_sage::Event::TransitionExecuted {
+			account: account.clone(),
+			id: action,
+		}))
+	);
+}
+
+fn game_events_of(account: &AccountId) -> Vec<BattleMogsEvent> {
+	events()
+		.into_iter()
+		.filter_map(|event| match event {
+			RuntimeEvent::SageBattleMogsGameEvents(pallet_sage_game_events::Event::GameEvent {
+				account: who,
+				event,
+			}) if &who == account => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+fn assets_of(account: &AccountId) -> Vec<(BattleMogsAssetId, BattleMogsAssetFor)> {
+	SageBattleMogsEngine::iter_assets_from(account).collect()
+}
+
+fn player_of(account: &AccountId) -> (BattleMogsAssetId, Player) {
+	assets_of(account)
+		.into_iter()
+		.find_map(|(asset_id, asset)| match asset.variant {
+			BattleMogsVariant::Player(player) => Some((asset_id, player)),
+			_ => None,
+		})
+		.expect("player is registered")
+}
+
+fn mutate_player(player_id: BattleMogsAssetId, mutate: impl FnOnce(&mut Player)) {
+	pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::mutate(player_id, |maybe_entry| {
+		if let Some((_, asset)) = maybe_entry {
+			if let BattleMogsVariant::Player(player) = &mut asset.variant {
+				mutate(player);
+			}
+		}
+	});
+}
+
+fn mogwais_of(account: &AccountId) -> Vec<(BattleMogsAssetId, Mogwai<BlockNumber>)> {
+	assets_of(account)
+		.into_iter()
+		.filter_map(|(asset_id, asset)| match asset.variant {
+			BattleMogsVariant::Mogwai(mogwai) => Some((asset_id, mogwai)),
+			_ => None,
+		})
+		.collect()
+}
+
+fn items_of(account: &AccountId) -> Vec<(BattleMogsAssetId, Item)> {
+	assets_of(account)
+		.into_iter()
+		.filter_map(|(asset_id, asset)| match asset.variant {
+			BattleMogsVariant::Item(item) => Some((asset_id, item)),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn register_player_mints_player() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+
+		let (player_id, player) = player_of(&bob());
+		assert_eq!(player_id, Player::id_of(&bob()));
+		assert_eq!(player.achievements.completed_count(), 0);
+		assert_eq!((player.xp, player.level), (0, 1));
+		assert_eq!(player.mogwai_count, Some(0));
+		assert_eq!(player.showcase, None);
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			BattleMogsAction::RegisterPlayer,
+			vec![],
+			None,
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn full_mogwai_lifecycle() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let [(mogwai_id, mogwai)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly one mogwai");
+		};
+		assert_eq!(mogwai.phase, PhaseType::Bred);
+		assert_eq!(mogwai.generation, MogwaiGeneration::First);
+
+		let hatch = BattleMogsAction::Hatch { mogwai: mogwai_id };
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			hatch.clone(),
+			vec![],
+			None
+		)
+		.is_err());
+
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), hatch, vec![]);
+
+		let [(_, hatched)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly one mogwai");
+		};
+		assert_eq!(hatched.phase, PhaseType::Hatched);
+		assert_eq!(
+			player_of(&bob()).1.achievements.egg_hatcher,
+			AchievementState::InProgress {
+				current: 1,
+				target: BattleMogsTransitionConfig::default().target_egg_hatcher
+			}
+		);
+
+		transition(&bob(), BattleMogsAction::Remove { mogwai: mogwai_id }, vec![]);
+		assert!(mogwais_of(&bob()).is_empty());
+	});
+}
+
+#[test]
+fn hatch_emits_rarity_and_achievement_completion() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		let (player_id, _) = player_of(&bob());
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+
+		// Leave a single hatch to complete the egg hatcher achievement.
+		mutate_player(player_id, |player| {
+			player.achievements.egg_hatcher = AchievementState::new(1)
+		});
+
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), BattleMogsAction::Hatch { mogwai: mogwai_id }, vec![]);
+
+		let (_, hatched) = mogwais_of(&bob())[0];
+		assert_eq!(
+			game_events_of(&bob()),
+			vec![
+				BattleMogsEvent::AchievementCompleted {
+					player_id,
+					achievement: Achievement::EggHatcher
+				},
+				BattleMogsEvent::MogwaiHatched { mogwai_id, rarity: hatched.rarity },
+			]
+		);
+		let (_, player) = player_of(&bob());
+		assert!(player.achievements.egg_hatcher.is_completed());
+		assert_eq!(player.xp, Experience::ACHIEVEMENT + Experience::HATCH);
+	});
+}
+
+#[test]
+fn morph_and_breed_persist_achievement_progress() {
+	new_test_ext().execute_with(|| {
+		for account in [bob(), charlie()] {
+			transition(&account, BattleMogsAction::RegisterPlayer, vec![]);
+			transition(&account, BattleMogsAction::CreateMogwai, vec![]);
+		}
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+		let (foreign_id, _) = mogwais_of(&charlie())[0];
+
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), BattleMogsAction::Hatch { mogwai: mogwai_id }, vec![]);
+		transition(&charlie(), BattleMogsAction::Hatch { mogwai: foreign_id }, vec![]);
+
+		let target = BattleMogsTransitionConfig::default();
+		transition(&bob(), BattleMogsAction::Morph { mogwai: mogwai_id }, vec![]);
+		assert_eq!(
+			player_of(&bob()).1.achievements.morpheus,
+			AchievementState::InProgress { current: 1, target: target.target_morpheus }
+		);
+
+		transition(
+			&charlie(),
+			BattleMogsAction::OfferStud { mogwai: foreign_id, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		transition(
+			&bob(),
+			BattleMogsAction::Breed { mogwai_1: mogwai_id, mogwai_2: foreign_id },
+			vec![],
+		);
+		assert_eq!(
+			player_of(&bob()).1.achievements.promiscuous,
+			AchievementState::InProgress { current: 1, target: target.target_promiscuous }
+		);
+	});
+}
+
+#[test]
+fn sacrifice_into_applies_the_generation_jump() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let [(sacrificed_id, _), (into_id, _)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly two mogwais");
+		};
+
+		// Equal rarities make the jump the plain generation difference.
+		for (mogwai_id, generation) in
+			[(sacrificed_id, MogwaiGeneration::Fifth), (into_id, MogwaiGeneration::First)]
+		{
+			pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::mutate(
+				mogwai_id,
+				|maybe_entry| {
+					if let Some((_, asset)) = maybe_entry {
+						if let BattleMogsVariant::Mogwai(mogwai) = &mut asset.variant {
+							mogwai.phase = PhaseType::Hatched;
+							mogwai.rarity = RarityType::Rare;
+							mogwai.generation = generation;
+						}
+					}
+				},
+			);
+		}
+
+		// Morphing funds the sacrificed mogwai, which the jump moves over.
+		transition(&bob(), BattleMogsAction::Morph { mogwai: sacrificed_id }, vec![]);
+
+		transition(
+			&bob(),
+			BattleMogsAction::SacrificeInto { mogwai: sacrificed_id, into: into_id },
+			vec![],
+		);
+
+		let [(_, into)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly one mogwai");
+		};
+		assert_eq!(into.generation, MogwaiGeneration::Fifth);
+		assert!(game_events_of(&bob()).contains(&BattleMogsEvent::MogwaiSacrificedInto {
+			mogwai_id: sacrificed_id,
+			into_id,
+			generation_jump: 4,
+			generation: MogwaiGeneration::Fifth,
+		}));
+	});
+}
+
+#[test]
+fn mogwais_cannot_be_hatched_by_others() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&charlie(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+
+		run_to_block(1 + HATCH_DELAY);
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			BattleMogsAction::Hatch { mogwai: mogwai_id },
+			vec![],
+			None,
+		)
+		.is_err());
+		assert_eq!(mogwais_of(&bob())[0].1.phase, PhaseType::Bred);
+	});
+}
+
+#[test]
+fn players_count_their_mogwais_up_to_the_limit() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		let create = BattleMogsAction::CreateMogwai;
+
+		let max_mogwais = BattleMogsTransitionConfig::default().max_mogwais;
+		for _ in 0..max_mogwais {
+			transition(&bob(), create.clone(), vec![]);
+		}
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(max_mogwais));
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			create.clone(),
+			vec![],
+			None
+		)
+		.is_err());
+
+		let (mogwai, _) = mogwais_of(&bob())[0];
+		transition(&bob(), BattleMogsAction::Remove { mogwai }, vec![]);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(max_mogwais - 1));
+		transition(&bob(), create, vec![]);
+		assert_eq!(mogwais_of(&bob()).len(), max_mogwais as usize);
+	});
+}
+
+#[test]
+fn upgraded_players_count_their_mogwais_on_first_use() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		let (player_id, _) = player_of(&bob());
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+
+		// Players upgraded from achievement tables do not know their mogwais yet.
+		mutate_player(player_id, |player| player.mogwai_count = None);
+
+		let (mogwai, _) = mogwais_of(&bob())[0];
+		transition(&bob(), BattleMogsAction::Remove { mogwai }, vec![]);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(1));
+	});
+}
+
+#[test]
+fn players_level_up_with_experience() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		let (player_id, _) = player_of(&bob());
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let (mogwai, _) = mogwais_of(&bob())[0];
+
+		mutate_player(player_id, |player| player.xp = XP_PER_LEVEL - Experience::HATCH);
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), BattleMogsAction::Hatch { mogwai }, vec![]);
+
+		let (_, player) = player_of(&bob());
+		assert_eq!((player.xp, player.level), (XP_PER_LEVEL, 2));
+		assert!(game_events_of(&bob())
+			.contains(&BattleMogsEvent::PlayerLeveledUp { player_id, level: 2 }));
+	});
+}
+
+#[test]
+fn players_showcase_their_own_mogwais() {
+	new_test_ext().execute_with(|| {
+		for account in [bob(), charlie()] {
+			transition(&account, BattleMogsAction::RegisterPlayer, vec![]);
+			transition(&account, BattleMogsAction::CreateMogwai, vec![]);
+		}
+		let (mogwai, _) = mogwais_of(&bob())[0];
+		let showcase = BattleMogsAction::Showcase { mogwai: Some(mogwai) };
+
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			showcase.clone(),
+			vec![],
+			None
+		)
+		.is_err());
+		transition(&bob(), showcase, vec![]);
+		assert_eq!(player_of(&bob()).1.showcase, Some(mogwai));
+
+		transition(&bob(), BattleMogsAction::Remove { mogwai }, vec![]);
+		assert_eq!(player_of(&bob()).1.showcase, None);
+	});
+}
+
+#[test]
+fn players_craft_items_and_equip_them_on_mogwais() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		for _ in 0..4 {
+			transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		}
+		run_to_block(1 + HATCH_DELAY);
+		let mogwai_ids: Vec<_> = mogwais_of(&bob()).into_iter().map(|(id, _)| id).collect();
+		for &mogwai in &mogwai_ids {
+			transition(&bob(), BattleMogsAction::Hatch { mogwai }, vec![]);
+		}
+
+		let ingredients = [mogwai_ids[0], mogwai_ids[1], mogwai_ids[2]];
+		transition(&bob(), BattleMogsAction::Craft { ingredients }, vec![]);
+		assert_eq!(mogwais_of(&bob()).len(), 1);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(1));
+		let [(item_id, item)] = items_of(&bob())[..] else {
+			panic!("expected exactly one item");
+		};
+		assert_eq!(item.equipped_on, None);
+
+		let mogwai_id = mogwai_ids[3];
+		transition(&bob(), BattleMogsAction::Equip { item: item_id, mogwai: mogwai_id }, vec![]);
+		let item_asset = SageBattleMogsEngine::get_asset(&item_id).expect("item exists");
+		assert!(!GameFilter::<BlockNumber>::can_be_traded_using(&item_asset, &item.rarity));
+		let base_stats = Stats::from_dna(&mogwai(mogwai_id).dna);
+		assert_eq!(
+			base_stats.equipped_with(items_of(&bob()).iter().map(|(_, item)| item)),
+			base_stats.saturating_add(&item.bonus)
+		);
+
+		transition(&bob(), BattleMogsAction::Unequip { item: item_id }, vec![]);
+		transition(&bob(), BattleMogsAction::Equip { item: item_id, mogwai: mogwai_id }, vec![]);
+		transition(&bob(), BattleMogsAction::Remove { mogwai: mogwai_id }, vec![]);
+		assert_eq!(items_of(&bob()), vec![(item_id, item)]);
+		assert!(game_events_of(&bob())
+			.contains(&BattleMogsEvent::ItemUnequipped { item_id, mogwai_id }));
+	});
+}
+
+#[test]
+fn mogwais_return_from_expeditions_once_finished() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		run_to_block(1 + HATCH_DELAY);
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+		transition(&bob(), BattleMogsAction::Hatch { mogwai: mogwai_id }, vec![]);
+
+		let dungeon = Dungeon::Forest;
+		transition(&bob(), BattleMogsAction::SendExpedition { mogwai: mogwai_id, dungeon }, vec![]);
+		let expedition = mogwai(mogwai_id).expedition.expect("mogwai is away");
+		assert_eq!(expedition.until, 1 + HATCH_DELAY + 2 * 50);
+		assert_eq!(expedition.power, Stats::from_dna(&mogwai(mogwai_id).dna).total());
+
+		let complete = BattleMogsAction::CompleteExpedition { mogwai: mogwai_id };
+		for locked in [BattleMogsAction::Remove { mogwai: mogwai_id }, complete.clone()] {
+			assert!(SageBattleMogs::state_transition(
+				RuntimeOrigin::signed(bob()),
+				locked,
+				vec![],
+				None
+			)
+			.is_err());
+		}
+
+		run_to_block(expedition.until);
+		transition(&bob(), complete, vec![]);
+		let returned = mogwai(mogwai_id);
+		assert_eq!(returned.expedition, None);
+
+		let battles = player_of(&bob()).1.battles;
+		assert_eq!(battles.wins + battles.losses, 1);
+		let Some(BattleMogsEvent::ExpeditionCompleted { cleared, loot, injured, .. }) =
+			game_events_of(&bob())
+				.into_iter()
+				.find(|event| matches!(event, BattleMogsEvent::ExpeditionCompleted { .. }))
+		else {
+			panic!("expected the expedition to complete");
+		};
+		assert_eq!(cleared, battles.wins == 1);
+		assert_eq!(loot.is_some(), items_of(&bob()).len() == 1);
+		assert_eq!(injured, returned.injured_until.is_some());
+	});
+}
+
+#[test]
+fn items_stay_on_mogwais_away_on_expeditions() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		for _ in 0..5 {
+			transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		}
+		run_to_block(1 + HATCH_DELAY);
+		let mogwai_ids: Vec<_> = mogwais_of(&bob()).into_iter().map(|(id, _)| id).collect();
+		for &mogwai in &mogwai_ids {
+			transition(&bob(), BattleMogsAction::Hatch { mogwai }, vec![]);
+		}
+		let ingredients = [mogwai_ids[0], mogwai_ids[1], mogwai_ids[2]];
+		transition(&bob(), BattleMogsAction::Craft { ingredients }, vec![]);
+		let [(item_id, item)] = items_of(&bob())[..] else {
+			panic!("expected exactly one item");
+		};
+
+		let (explorer, other) = (mogwai_ids[3], mogwai_ids[4]);
+		transition(&bob(), BattleMogsAction::Equip { item: item_id, mogwai: explorer }, vec![]);
+		let dungeon = Dungeon::Forest;
+		transition(&bob(), BattleMogsAction::SendExpedition { mogwai: explorer, dungeon }, vec![]);
+
+		let power = Stats::from_dna(&mogwai(explorer).dna).saturating_add(&item.bonus).total();
+		assert_eq!(mogwai(explorer).expedition.expect("mogwai is away").power, power);
+		for locked in [
+			BattleMogsAction::Unequip { item: item_id },
+			BattleMogsAction::Equip { item: item_id, mogwai: other },
+		] {
+			assert!(SageBattleMogs::state_transition(
+				RuntimeOrigin::signed(bob()),
+				locked,
+				vec![],
+				None
+			)
+			.is_err());
+		}
+		assert_eq!(items_of(&bob())[0].1.equipped_on, Some(explorer));
+
+		let expedition = mogwai(explorer).expedition.expect("mogwai is away");
+		run_to_block(expedition.until);
+		transition(&bob(), BattleMogsAction::CompleteExpedition { mogwai: explorer }, vec![]);
+		transition(&bob(), BattleMogsAction::Unequip { item: item_id }, vec![]);
+		transition(&bob(), BattleMogsAction::Equip { item: item_id, mogwai: other }, vec![]);
+		assert!(items_of(&bob())
+			.iter()
+			.any(|(id, item)| *id == item_id && item.is_equipped_on(&other)));
+	});
+}
+
+#[test]
+fn removed_mogwais_refund_their_funds_to_the_owner() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+		let funds = native_funds_of(stud);
+		assert!(funds > 0);
+
+		let balance_before = free_balance(&bob());
+		transition(&bob(), BattleMogsAction::Remove { mogwai: stud }, vec![]);
+		assert_eq!(native_funds_of(stud), 0);
+		assert_eq!(free_balance(&bob()), balance_before + funds);
+	});
+}
+
+#[test]
+fn sacrificed_mogwais_settle_their_remaining_funds_to_the_configured_account() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+
+		let treasury = alice();
+		let config = BattleMogsTransitionConfig {
+			removed_funds: FundsDestination::Account(treasury.clone().into()),
+			..Default::default()
+		};
+		assert_ok!(SageBattleMogs::update_transition_config(
+			RuntimeOrigin::signed(alice()),
+			config
+		));
+
+		let funds = native_funds_of(stud);
+		let intrinsic_share = funds / Pricing::<Balance>::intrinsic_return(PhaseType::Hatched);
+		let (owner_before, treasury_before) = (free_balance(&bob()), free_balance(&treasury));
+		transition(&bob(), BattleMogsAction::Sacrifice { mogwai: stud }, vec![]);
+		assert_eq!(native_funds_of(stud), 0);
+		assert_eq!(free_balance(&bob()), owner_before + intrinsic_share);
+		assert_eq!(free_balance(&treasury), treasury_before + funds - intrinsic_share);
+	});
+}
+
+#[test]
+fn sacrificed_mogwais_return_only_their_intrinsic_share_to_the_owner() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+		assert_eq!(BattleMogsTransitionConfig::default().removed_funds, FundsDestination::Owner);
+
+		let funds = native_funds_of(stud);
+		let intrinsic_share = funds / Pricing::<Balance>::intrinsic_return(PhaseType::Hatched);
+		assert!(intrinsic_share < funds);
+		let owner_before = free_balance(&bob());
+		transition(&bob(), BattleMogsAction::Sacrifice { mogwai: stud }, vec![]);
+		assert!(mogwais_of(&bob()).is_empty());
+		assert_eq!(free_balance(&bob()), owner_before + intrinsic_share);
+	});
+}
+
+/// Creates the payment asset of BattleMogs with Alice as admin, minting Charlie some of it.
+fn create_payment_asset() -> u32 {
+	let asset_id = BattleMogsPaymentAssetId::get();
+	assert_ok!(SageAssets::force_create(
+		RuntimeOrigin::root(),
+		asset_id.into(),
+		alice().into(),
+		true,
+		1
+	));
+	assert_ok!(SageAssets::mint(
+		RuntimeOrigin::signed(alice()),
+		asset_id.into(),
+		charlie().into(),
+		1_000 * UNIT
+	));
+	asset_id
+}
+
+#[test]
+fn frozen_payment_assets_do_not_block_removing_mogwais() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 100 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+		let asset_id = create_payment_asset();
+		let payment = WithdrawKind::Payment(NativeOrWithId::WithId(asset_id));
+		run_past_cooldowns([stud, mogwai]);
+		assert_ok!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			BattleMogsAction::Breed { mogwai_1: mogwai, mogwai_2: stud },
+			vec![],
+			Some(payment),
+		));
+		assert_ok!(SageAssets::freeze_asset(RuntimeOrigin::signed(alice()), asset_id.into()));
+
+		let native_funds = native_funds_of(stud);
+		let asset_funds = SageBattleMogsEngine::inspect_asset_funds(&stud, &payment);
+		assert!(native_funds > 0 && asset_funds > 0);
+		let balance_before = free_balance(&bob());
+
+		// Removing the stud paying natively settles its native funds and leaves the frozen ones.
+		transition(&bob(), BattleMogsAction::Remove { mogwai: stud }, vec![]);
+		assert!(mogwais_of(&bob()).is_empty());
+		assert_eq!(free_balance(&bob()), balance_before + native_funds);
+		assert_eq!(SageAssets::balance(asset_id, bob()), 0);
+	});
+}
+
+#[test]
+fn batches_skip_or_reject_mogwais_they_cannot_act_on() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&charlie(), BattleMogsAction::RegisterPlayer, vec![]);
+		for _ in 0..3 {
+			transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		}
+		transition(&charlie(), BattleMogsAction::CreateMogwai, vec![]);
+		run_to_block(1 + HATCH_DELAY);
+		let mogwai_ids: Vec<_> = mogwais_of(&bob()).into_iter().map(|(id, _)| id).collect();
+		let (foreign_id, _) = mogwais_of(&charlie())[0];
+
+		let mogwais = MogwaiBatch::truncate_from(vec![mogwai_ids[0], mogwai_ids[0], foreign_id]);
+		let all_or_nothing =
+			BattleMogsAction::HatchMany { mogwais: mogwais.clone(), mode: BatchMode::AllOrNothing };
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			all_or_nothing,
+			vec![],
+			None
+		)
+		.is_err());
+		assert_eq!(mogwai(mogwai_ids[0]).phase, PhaseType::Bred);
+
+		transition(
+			&bob(),
+			BattleMogsAction::HatchMany { mogwais, mode: BatchMode::SkipInvalid },
+			vec![],
+		);
+		assert_eq!(mogwai(mogwai_ids[0]).phase, PhaseType::Hatched);
+		let skipped: Vec<_> = game_events_of(&bob())
+			.into_iter()
+			.filter(|event| matches!(event, BattleMogsEvent::MogwaiSkipped { .. }))
+			.collect();
+		assert_eq!(
+			skipped,
+			[
+				BattleMogsEvent::MogwaiSkipped { mogwai_id: mogwai_ids[0], code: INVALID_BATCH },
+				BattleMogsEvent::MogwaiSkipped { mogwai_id: foreign_id, code: ASSET_NOT_OWNED },
+			]
+		);
+
+		let mogwais = MogwaiBatch::truncate_from(mogwai_ids.clone());
+		transition(
+			&bob(),
+			BattleMogsAction::RemoveMany { mogwais, mode: BatchMode::AllOrNothing },
+			vec![],
+		);
+		assert!(mogwais_of(&bob()).is_empty());
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(0));
+	});
+}
+
+#[test]
+fn skipped_batch_mogwais_keep_their_funds_and_count() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let others: Vec<_> = mogwais_of(&bob())
+			.into_iter()
+			.map(|(id, _)| id)
+			.filter(|id| *id != stud)
+			.collect();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 100 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+
+		// The stud is paid in an asset frozen afterwards, failing its settlement once its
+		// native funds have been moved.
+		let asset_id = create_payment_asset();
+		let payment = WithdrawKind::Payment(NativeOrWithId::WithId(asset_id));
+		run_past_cooldowns([stud, mogwai]);
+		assert_ok!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			BattleMogsAction::Breed { mogwai_1: mogwai, mogwai_2: stud },
+			vec![],
+			Some(payment),
+		));
+		assert_ok!(SageAssets::freeze_asset(RuntimeOrigin::signed(alice()), asset_id.into()));
+
+		let native_funds = native_funds_of(stud);
+		let asset_funds = SageBattleMogsEngine::inspect_asset_funds(&stud, &payment);
+		assert!(native_funds > 0 && asset_funds > 0);
+		let balance_before = free_balance(&bob());
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(3));
+
+		let mogwais = MogwaiBatch::truncate_from(vec![others[0], stud, others[1]]);
+		assert_ok!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			BattleMogsAction::RemoveMany { mogwais, mode: BatchMode::SkipInvalid },
+			vec![],
+			Some(payment),
+		));
+		assert_eq!(mogwais_of(&bob()).into_iter().map(|(id, _)| id).collect::<Vec<_>>(), [stud]);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(1));
+		assert_eq!(native_funds_of(stud), native_funds);
+		assert_eq!(SageBattleMogsEngine::inspect_asset_funds(&stud, &payment), asset_funds);
+		assert_eq!(free_balance(&bob()), balance_before);
+		assert_eq!(SageAssets::balance(asset_id, bob()), 0);
+		assert!(game_events_of(&bob()).contains(&BattleMogsEvent::MogwaiSkipped {
+			mogwai_id: stud,
+			code: ASSET_COULD_NOT_WITHDRAW_FUNDS
+		}));
+	});
+}
+
+fn mogwai(mogwai_id: BattleMogsAssetId) -> Mogwai<BlockNumber> {
+	match SageBattleMogsEngine::get_asset(&mogwai_id).expect("mogwai exists").variant {
+		BattleMogsVariant::Mogwai(mogwai) => mogwai,
+		_ => panic!("asset is not a mogwai"),
+	}
+}
+
+/// Runs to the first block all of the given mogwais can breed again in.
+fn run_past_cooldowns(mogwai_ids: impl IntoIterator<Item = BattleMogsAssetId>) {
+	let fertility = BattleMogsTransitionConfig::default().fertility;
+	let rested = mogwai_ids
+		.into_iter()
+		.map(mogwai)
+		.filter_map(|mogwai| Some(mogwai.last_bred? + fertility.cooldown_of(&mogwai)))
+		.max();
+	run_to_block(rested.unwrap_or_default());
+}
+
+fn native_funds_of(asset_id: BattleMogsAssetId) -> Balance {
+	let native = <SageBattleMogsEngine as SageApi>::FungiblesAssetId::get_native_id();
+	SageBattleMogsEngine::inspect_asset_funds(&asset_id, &native)
+}
+
+/// Registers Bob and Charlie with a hatched mogwai each, returning Bob's and Charlie's mogwai.
+fn setup_studs() -> (BattleMogsAssetId, BattleMogsAssetId) {
+	for account in [bob(), charlie()] {
+		transition(&account, BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&account, BattleMogsAction::CreateMogwai, vec![]);
+	}
+	run_to_block(1 + HATCH_DELAY);
+	for account in [bob(), charlie()] {
+		let (mogwai, _) = mogwais_of(&account)[0];
+		transition(&account, BattleMogsAction::Hatch { mogwai }, vec![]);
+	}
+
+	(mogwais_of(&bob())[0].0, mogwais_of(&charlie())[0].0)
+}
+
+fn breed(account: &AccountId, mogwai_1: BattleMogsAssetId, mogwai_2: BattleMogsAssetId) -> bool {
+	SageBattleMogs::state_transition(
+		RuntimeOrigin::signed(account.clone()),
+		BattleMogsAction::Breed { mogwai_1, mogwai_2 },
+		vec![],
+		None,
+	)
+	.is_ok()
+}
+
+#[test]
+fn bred_mogwais_record_their_lineage() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let [(parent_1, _), (parent_2, _)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly two mogwais");
+		};
+
+		run_to_block(1 + HATCH_DELAY);
+		for mogwai in [parent_1, parent_2] {
+			transition(&bob(), BattleMogsAction::Hatch { mogwai }, vec![]);
+		}
+		transition(
+			&bob(),
+			BattleMogsAction::Breed { mogwai_1: parent_1, mogwai_2: parent_2 },
+			vec![],
+		);
+
+		let (offspring_id, offspring) = mogwais_of(&bob())
+			.into_iter()
+			.find(|(_, mogwai)| mogwai.parents.is_some())
+			.expect("mogwais have been bred");
+		assert_eq!(offspring.parents, Some([parent_1, parent_2]));
+		assert_eq!(offspring.lineage_depth, 1);
+
+		let lineage = |max_distance| {
+			ancestors::<SageBattleMogsEngine, BlockNumber>(&offspring_id, max_distance)
+				.into_iter()
+				.map(|ancestor| (ancestor.id, ancestor.distance, ancestor.mogwai.is_some()))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(lineage(0), vec![]);
+		assert_eq!(lineage(5), vec![(parent_1, 1, true), (parent_2, 1, true)]);
+
+		// Consumed ancestors are still part of the lineage.
+		transition(&bob(), BattleMogsAction::Remove { mogwai: parent_1 }, vec![]);
+		assert_eq!(lineage(5), vec![(parent_1, 1, false), (parent_2, 1, true)]);
+	});
+}
+
+#[test]
+fn bred_mogwais_rest_until_their_cooldown_passed() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let [(parent_1, _), (parent_2, _)] = mogwais_of(&bob())[..] else {
+			panic!("expected exactly two mogwais");
+		};
+
+		run_to_block(1 + HATCH_DELAY);
+		for mogwai in [parent_1, parent_2] {
+			transition(&bob(), BattleMogsAction::Hatch { mogwai }, vec![]);
+		}
+		assert!(breed(&bob(), parent_1, parent_2));
+		for parent in [parent_1, parent_2] {
+			assert_eq!(mogwai(parent).last_bred, Some(1 + HATCH_DELAY));
+			assert_eq!(mogwai(parent).breed_count, 1);
+		}
+
+		assert!(!breed(&bob(), parent_1, parent_2));
+		assert!(!breed(&bob(), parent_2, parent_1));
+
+		run_past_cooldowns([parent_1, parent_2]);
+		assert!(breed(&bob(), parent_2, parent_1));
+		for parent in [parent_1, parent_2] {
+			assert_eq!(mogwai(parent).breed_count, 2);
+		}
+	});
+}
+
+#[test]
+fn breeding_with_foreign_mogwais_requires_stud_offer() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		assert!(!breed(&charlie(), mogwai, stud));
+
+		let price = 7 * UNIT_PRICE;
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price, duration: 10 },
+			vec![],
+		);
+		assert_eq!(
+			mogwais_of(&bob())[0].1.stud,
+			Some(StudOffer { price, until: 1 + HATCH_DELAY + 10 })
+		);
+		assert_eq!(
+			game_events_of(&bob()).last(),
+			Some(&BattleMogsEvent::StudOffered { mogwai_id: stud, price })
+		);
+
+		let funds_before = native_funds_of(stud);
+		let pairing = Pricing::<Balance>::pairing(
+			mogwais_of(&charlie())[0].1.rarity,
+			mogwais_of(&bob())[0].1.rarity,
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+		assert_eq!(native_funds_of(stud), funds_before + pairing + price);
+
+		run_past_cooldowns([stud, mogwai]);
+		transition(&bob(), BattleMogsAction::RevokeStud { mogwai: stud }, vec![]);
+		assert_eq!(mogwais_of(&bob())[0].1.stud, None);
+		assert!(!breed(&charlie(), mogwai, stud));
+	});
+}
+
+#[test]
+fn foreign_breeding_leaves_the_stud_with_its_owner() {
+	new_test_ext().execute_with(|| {
+		let (stud, partner) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		assert!(breed(&charlie(), partner, stud));
+
+		let (owner, _) =
+			pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::get(stud).expect("stud exists");
+		assert_eq!(owner, bob());
+		assert_eq!(mogwai(stud).breed_count, 1);
+		assert!(SageBattleMogsEngine::ensure_ownership(&charlie(), &stud).is_err());
+
+		// The owner still controls the stud.
+		transition(&bob(), BattleMogsAction::ClaimStudFees { mogwai: stud }, vec![]);
+		transition(&bob(), BattleMogsAction::RevokeStud { mogwai: stud }, vec![]);
+		assert_eq!(mogwai(stud).stud, None);
+	});
+}
+
+/// Opens the trade of mogwais of `rarity` in the running season.
+fn enable_mogwai_trade(rarity: RarityType) {
+	assert_ok!(SageBattleMogs::update_general_config(
+		RuntimeOrigin::signed(alice()),
+		pallet_sage::GeneralConfig { transfer: true, trade: true },
+	));
+	assert_ok!(SageBattleMogs::update_asset_filter(
+		RuntimeOrigin::signed(alice()),
+		SEASON_ID,
+		pallet_sage::AssetFilterOption::Trade(rarity),
+	));
+}
+
+fn sell(
+	seller: &AccountId,
+	buyer: &AccountId,
+	mogwai_id: BattleMogsAssetId,
+	payment: Option<WithdrawKind<NativeOrWithId<u32>>>,
+) {
+	assert_ok!(SageBattleMogs::set_asset_price(
+		RuntimeOrigin::signed(seller.clone()),
+		mogwai_id,
+		UNIT
+	));
+	assert_ok!(SageBattleMogs::buy_asset(RuntimeOrigin::signed(buyer.clone()), mogwai_id, payment));
+}
+
+#[test]
+fn traded_mogwais_move_between_player_counts() {
+	new_test_ext().execute_with(|| {
+		let (stud, partner) = setup_studs();
+		let rarity = mogwai(partner).rarity;
+		let asset = SageBattleMogsEngine::get_asset(&partner).expect("mogwai exists");
+		assert!(GameFilter::<BlockNumber>::can_be_traded_using(&asset, &rarity));
+		assert!(GameFilter::<BlockNumber>::can_be_transferred_using(&asset, &rarity));
+
+		enable_mogwai_trade(rarity);
+		sell(&charlie(), &bob(), partner, None);
+		assert_eq!(mogwais_of(&bob()).len(), 2);
+		assert!(mogwais_of(&charlie()).is_empty());
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(1));
+		assert_eq!(player_of(&charlie()).1.mogwai_count, Some(1));
+
+		// The count follows the mogwai once its new owner uses it, and only once.
+		let stud_offer =
+			BattleMogsAction::OfferStud { mogwai: partner, price: UNIT_PRICE, duration: 10 };
+		transition(&bob(), stud_offer, vec![]);
+		assert_eq!(mogwai(partner).counted_by, Some(player_of(&bob()).0));
+		transition(&bob(), BattleMogsAction::RevokeStud { mogwai: partner }, vec![]);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(2));
+		assert_eq!(player_of(&charlie()).1.mogwai_count, Some(0));
+
+		// Consuming a traded mogwai uncounts it from the player that still counted it.
+		if mogwai(stud).rarity != rarity {
+			enable_mogwai_trade(mogwai(stud).rarity);
+		}
+		sell(&bob(), &charlie(), stud, None);
+		transition(&charlie(), BattleMogsAction::Remove { mogwai: stud }, vec![]);
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(1));
+		assert_eq!(player_of(&charlie()).1.mogwai_count, Some(0));
+		assert_eq!(player_of(&bob()).1.mogwai_count, Some(mogwais_of(&bob()).len() as u16));
+	});
+}
+
+#[test]
+fn trades_pay_their_fee_in_the_payment_asset() {
+	new_test_ext().execute_with(|| {
+		let (stud, _) = setup_studs();
+		enable_mogwai_trade(mogwai(stud).rarity);
+		let asset_id = create_payment_asset();
+		let (seller_before, buyer_before) =
+			(SageAssets::balance(asset_id, bob()), SageAssets::balance(asset_id, charlie()));
+		let native_before = free_balance(&charlie());
+
+		// The price goes through the fee handler in the asset, leaving the native funds alone.
+		let payment = WithdrawKind::Payment(NativeOrWithId::WithId(asset_id));
+		sell(&bob(), &charlie(), stud, Some(payment));
+		assert!(mogwais_of(&charlie()).iter().any(|(id, _)| *id == stud));
+		assert_eq!(SageAssets::balance(asset_id, charlie()), buyer_before - UNIT);
+		assert_eq!(SageAssets::balance(asset_id, bob()), seller_before + UNIT);
+		assert_eq!(free_balance(&charlie()), native_before);
+	});
+}
+
+#[test]
+fn stud_offers_expire() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 5 },
+			vec![],
+		);
+
+		run_to_block(1 + HATCH_DELAY + 6);
+		assert!(!breed(&charlie(), mogwai, stud));
+	});
+}
+
+#[test]
+fn stud_owner_claims_accumulated_fees() {
+	new_test_ext().execute_with(|| {
+		let (stud, mogwai) = setup_studs();
+		let claim = BattleMogsAction::ClaimStudFees { mogwai: stud };
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(bob()),
+			claim.clone(),
+			vec![],
+			None
+		)
+		.is_err());
+
+		transition(
+			&bob(),
+			BattleMogsAction::OfferStud { mogwai: stud, price: UNIT_PRICE, duration: 10 },
+			vec![],
+		);
+		assert!(breed(&charlie(), mogwai, stud));
+		let fees = native_funds_of(stud);
+
+		// Only the owner can claim the fees.
+		assert!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			claim.clone(),
+			vec![],
+			None
+		)
+		.is_err());
+
+		transition(&bob(), claim, vec![]);
+		assert_eq!(native_funds_of(stud), 0);
+		assert_eq!(
+			game_events_of(&bob()).last(),
+			Some(&BattleMogsEvent::StudFeesClaimed { mogwai_id: stud, amount: fees })
+		);
+	});
+}
+
+#[test]
+fn affiliates_require_completed_achievements() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+
+		assert!(SageBattleMogsAffiliates::enable_affiliator(RuntimeOrigin::signed(bob()), None)
+			.is_err());
+	});
+}
+
+#[test]
+fn tournament_ranker_only_ranks_hatched_mogwais() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		let (player_id, _) = player_of(&bob());
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+
+		let ranker = BattleMogsEntityRanker;
+		let bred = SageBattleMogsEngine::get_asset(&mogwai_id).unwrap();
+		assert!(!ranker.can_rank((&mogwai_id, &bred)));
+
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), BattleMogsAction::Hatch { mogwai: mogwai_id }, vec![]);
+
+		let hatched = SageBattleMogsEngine::get_asset(&mogwai_id).unwrap();
+		let player = SageBattleMogsEngine::get_asset(&player_id).unwrap();
+		assert!(ranker.can_rank((&mogwai_id, &hatched)));
+		assert!(!ranker.can_rank((&player_id, &player)));
+	});
+}
+
+fn tournament_events() -> Vec<pallet_ajuna_tournament::Event<Runtime, TournamentBattleMogsInstance>>
+{
+	events()
+		.into_iter()
+		.filter_map(|event| match event {
+			RuntimeEvent::SageBattleMogsTournament(event) => Some(event),
+			_ => None,
+		})
+		.collect()
+}
+
+#[test]
+fn tournaments_reward_their_best_ranked_mogwai() {
+	new_test_ext().execute_with(|| {
+		let reward = 100 * UNIT;
+		let (start, active_end) = (2, 2 + 2 * HATCH_DELAY);
+		let config = TournamentConfig {
+			start,
+			active_end,
+			claim_end: active_end + HATCH_DELAY,
+			initial_reward: Some(reward),
+			max_reward: None,
+			take_fee_percentage: None,
+			reward_distribution: vec![100].try_into().unwrap(),
+			golden_duck_config: Default::default(),
+			max_players: 1,
+			ranker: BattleMogsEntityRanker,
+		};
+		assert_ok!(SageBattleMogsTournament::create_tournament(
+			RuntimeOrigin::signed(alice()),
+			SEASON_ID,
+			config
+		));
+		let tournament_id = tournament_events()
+			.into_iter()
+			.find_map(|event| match event {
+				pallet_ajuna_tournament::Event::TournamentCreated { tournament_id, .. } =>
+					Some(tournament_id),
+				_ => None,
+			})
+			.expect("tournament is created");
+
+		transition(&bob(), BattleMogsAction::RegisterPlayer, vec![]);
+		transition(&bob(), BattleMogsAction::CreateMogwai, vec![]);
+		let (mogwai_id, _) = mogwais_of(&bob())[0];
+		run_to_block(1 + HATCH_DELAY);
+		transition(&bob(), BattleMogsAction::Hatch { mogwai: mogwai_id }, vec![]);
+
+		let hatched = SageBattleMogsEngine::get_asset(&mogwai_id).unwrap();
+		assert_ok!(SageBattleMogsTournament::try_rank_entity_in_tournament_for(
+			&SEASON_ID, &mogwai_id, &hatched
+		));
+		assert!(tournament_events().iter().any(|event| matches!(
+			event,
+			pallet_ajuna_tournament::Event::EntityEnteredRanking { entity_id, .. }
+				if *entity_id == mogwai_id
+		)));
+
+		run_to_block(active_end + 1);
+		let balance_before = free_balance(&bob());
+		assert_ok!(SageBattleMogsTournament::claim_tournament_reward_for(
+			RuntimeOrigin::signed(bob()),
+			SEASON_ID,
+			mogwai_id
+		));
+		assert!(free_balance(&bob()) > balance_before);
+		assert!(tournament_events().contains(
+			&pallet_ajuna_tournament::Event::RankingRewardClaimed {
+				category_id: SEASON_ID,
+				tournament_id,
+				entity_id: mogwai_id,
+				account: bob(),
+			}
+		));
+		assert!(SageBattleMogsTournament::claim_tournament_reward_for(
+			RuntimeOrigin::signed(bob()),
+			SEASON_ID,
+			mogwai_id
+		)
+		.is_err());
+	});
+}
@@ -0,0 +1,366 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use sage_playground_runtime::{
+	configs::{
+		sage::{
+			casino_jam::{
+				CasinoJamAffiliateUnlockRules, CasinoJamAsset, CasinoJamAssetId,
+				CasinoJamEntityRanker, CasinoJamSeatReclaimer, CasinoJamUnlockParameters,
+				SageCasinoJamEngine, SageCasinoJamInstance,
+			},
+			metrics::GameMetrics,
+		},
+		SageGame,
+	},
+	AccountId, Runtime, RuntimeEvent, RuntimeOrigin, SageCasinoJam, SageCasinoJamAffiliates,
+	SageCasinoJamSeatReclaimer, System,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use core::cmp::Ordering;
+use frame_support::{assert_noop, assert_ok};
+use game_casino_jam::prelude::*;
+use pallet_ajuna_affiliates::traits::AffiliateUnlockRules;
+use pallet_ajuna_tournament::EntityRank;
+use pallet_sage_seat_reclaimer::SeatReclaimer;
+use sp_runtime::DispatchError;
+
+fn transition(account: &AccountId, action: CasinoAction, asset_ids: Vec<CasinoJamAssetId>) {
+	assert_ok!(SageCasinoJam::state_transition(
+		RuntimeOrigin::signed(account.clone()),
+		action,
+		asset_ids,
+		None,
+	));
+	assert_eq!(
+		events().last(),
+		Some(&RuntimeEvent::SageCasinoJam(pallet_sage::Event::TransitionExecuted {
+			account: account.clone(),
+			id: action,
+		}))
+	);
+}
+
+fn find_asset(
+	account: &AccountId,
+	variant_type: VariantType,
+) -> Option<(CasinoJamAssetId, CasinoJamAsset)> {
+	SageCasinoJamEngine::iter_assets_from(account)
+		.find(|(_, asset)| asset.variant.is_variant(variant_type))
+}
+
+fn asset_of(account: &AccountId, variant_type: VariantType) -> CasinoJamAssetId {
+	find_asset(account, variant_type)
+		.map(|(asset_id, _)| asset_id)
+		.expect("asset exists")
+}
+
+fn gamble_count_of(account: &AccountId) -> u32 {
+	let (_, asset) = find_asset(account, VariantType::Player(PlayerType::Tracker)).unwrap();
+	match asset.variant {
+		AssetVariant::Player(PlayerVariant::Tracker(tracker)) => tracker.gamble_count,
+		_ => unreachable!("filtered by variant type"),
+	}
+}
+
+/// Sets up a bandit owned by Alice with a rented seat, and a funded player for Bob.
+///
+/// Returns the ids of Bob's human and tracker, the seat and the bandit.
+fn setup_casino() -> [CasinoJamAssetId; 4] {
+	let machine = VariantType::Machine(MachineType::Bandit);
+
+	transition(&alice(), CasinoAction::Create(AssetType::Machine(MachineType::Bandit)), vec![]);
+	let bandit_id = asset_of(&alice(), machine);
+	transition(
+		&alice(),
+		CasinoAction::Deposit(AssetType::Machine(MachineType::Bandit), TokenType::T100000),
+		vec![bandit_id],
+	);
+	transition(&alice(), CasinoAction::Rent(RentDuration::Day1), vec![bandit_id]);
+	let seat_id = asset_of(&alice(), VariantType::Seat);
+
+	transition(&bob(), CasinoAction::Create(AssetType::Player), vec![]);
+	let human_id = asset_of(&bob(), VariantType::Player(PlayerType::Human));
+	let tracker_id = asset_of(&bob(), VariantType::Player(PlayerType::Tracker));
+	transition(&bob(), CasinoAction::Deposit(AssetType::Player, TokenType::T1000), vec![human_id]);
+	transition(&bob(), CasinoAction::Reserve(ReservationDuration::Mins5), vec![human_id, seat_id]);
+
+	[human_id, tracker_id, seat_id, bandit_id]
+}
+
+#[test]
+fn create_player_mints_human_and_tracker() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), CasinoAction::Create(AssetType::Player), vec![]);
+
+		assert!(find_asset(&bob(), VariantType::Player(PlayerType::Human)).is_some());
+		assert_eq!(gamble_count_of(&bob()), 0);
+
+		// Only one player per account is allowed.
+		assert!(SageCasinoJam::state_transition(
+			RuntimeOrigin::signed(bob()),
+			CasinoAction::Create(AssetType::Player),
+			vec![],
+			None,
+		)
+		.is_err());
+	});
+}
+
+#[test]
+fn deposits_move_funds_from_the_owner() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), CasinoAction::Create(AssetType::Player), vec![]);
+		let human_id = asset_of(&bob(), VariantType::Player(PlayerType::Human));
+
+		let balance_before = free_balance(&bob());
+		transition(
+			&bob(),
+			CasinoAction::Deposit(AssetType::Player, TokenType::T1000),
+			vec![human_id],
+		);
+		assert_eq!(free_balance(&bob()), balance_before - 1_000);
+
+		transition(
+			&bob(),
+			CasinoAction::Withdraw(AssetType::Player, TokenType::T100),
+			vec![human_id],
+		);
+		assert_eq!(free_balance(&bob()), balance_before - 900);
+	});
+}
+
+#[test]
+fn full_gamble_flow() {
+	new_test_ext().execute_with(|| {
+		let [human_id, tracker_id, seat_id, bandit_id] = setup_casino();
+
+		let seat = find_asset(&alice(), VariantType::Seat).unwrap().1;
+		match seat.variant {
+			AssetVariant::Seat(seat) => assert_eq!(seat.player_id, Some(human_id)),
+			_ => unreachable!("filtered by variant type"),
+		}
+
+		transition(
+			&bob(),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+		);
+
+		assert_eq!(gamble_count_of(&bob()), 1);
+
+		run_to_block(2);
+		transition(
+			&bob(),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+		);
+		assert_eq!(gamble_count_of(&bob()), 2);
+
+		// Releasing the seat allows Alice to return it to the machine.
+		transition(&bob(), CasinoAction::Release, vec![human_id, seat_id]);
+		transition(&alice(), CasinoAction::Return, vec![bandit_id, seat_id]);
+		assert!(find_asset(&alice(), VariantType::Seat).is_none());
+	});
+}
+
+#[test]
+fn gamble_emits_spin_results() {
+	new_test_ext().execute_with(|| {
+		let [human_id, tracker_id, seat_id, bandit_id] = setup_casino();
+
+		transition(
+			&bob(),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+		);
+
+		let spins = events()
+			.into_iter()
+			.find_map(|event| match event {
+				RuntimeEvent::SageCasinoJamGameEvents(
+					pallet_sage_game_events::Event::GameEvent {
+						account,
+						event: CasinoJamEvent::Gambled { player_id, machine_id, spins, .. },
+					},
+				) => {
+					assert_eq!(account, bob());
+					assert_eq!(player_id, human_id);
+					assert_eq!(machine_id, bandit_id);
+					Some(spins)
+				},
+				_ => None,
+			})
+			.expect("gamble event has been emitted");
+
+		let (_, tracker) = find_asset(&bob(), VariantType::Player(PlayerType::Tracker)).unwrap();
+		match tracker.variant {
+			AssetVariant::Player(PlayerVariant::Tracker(tracker)) =>
+				assert_eq!(spins, vec![tracker.slot_a_result]),
+			_ => unreachable!("filtered by variant type"),
+		}
+	});
+}
+
+#[test]
+fn gamble_requires_asset_ownership() {
+	new_test_ext().execute_with(|| {
+		let [human_id, tracker_id, seat_id, bandit_id] = setup_casino();
+
+		assert!(SageCasinoJam::state_transition(
+			RuntimeOrigin::signed(charlie()),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+			None,
+		)
+		.is_err());
+		assert_eq!(gamble_count_of(&bob()), 0);
+	});
+}
+
+#[test]
+fn assets_survive_season_end() {
+	new_test_ext().execute_with(|| {
+		transition(&bob(), CasinoAction::Create(AssetType::Player), vec![]);
+
+		run_to_block(SEASON_DURATION + 2);
+
+		assert!(find_asset(&bob(), VariantType::Player(PlayerType::Human)).is_some());
+		assert!(find_asset(&bob(), VariantType::Player(PlayerType::Tracker)).is_some());
+	});
+}
+
+#[test]
+fn expired_reservations_are_reclaimed_unsigned() {
+	new_test_ext().execute_with(|| {
+		let [human_id, _, seat_id, _] = setup_casino();
+		assert!(CasinoJamSeatReclaimer::expired_seats(16).is_empty());
+
+		while !CasinoJamSeatReclaimer::is_expired(&seat_id) {
+			assert!(System::block_number() < 1_000, "reservation never expires");
+			run_to_block(System::block_number() + 1);
+		}
+		assert_eq!(CasinoJamSeatReclaimer::expired_seats(16), vec![seat_id]);
+
+		assert_ok!(SageCasinoJamSeatReclaimer::reclaim_seats(
+			RuntimeOrigin::none(),
+			vec![seat_id].try_into().unwrap(),
+		));
+		assert!(events().contains(&RuntimeEvent::SageCasinoJamSeatReclaimer(
+			pallet_sage_seat_reclaimer::Event::SeatReclaimed { seat_id }
+		)));
+
+		let human = SageCasinoJamEngine::get_asset(&human_id).unwrap();
+		assert!(matches!(
+			human.variant,
+			AssetVariant::Player(PlayerVariant::Human(human)) if human.seat_id.is_none()
+		));
+		// The seat stays with the machine until its rent expires.
+		assert!(!CasinoJamSeatReclaimer::is_expired(&seat_id));
+		assert_eq!(find_asset(&alice(), VariantType::Seat).map(|(id, _)| id), Some(seat_id));
+	});
+}
+
+#[test]
+fn game_metrics_count_assets_and_jackpots() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(GameMetrics::collect(), GameMetrics::default());
+
+		let [_, _, _, bandit_id] = setup_casino();
+		let metrics = GameMetrics::collect();
+
+		let variants = |game| {
+			metrics
+				.assets
+				.iter()
+				.filter(|(asset_game, _, _)| *asset_game == game)
+				.map(|(_, variant, count)| (String::from_utf8(variant.clone()).unwrap(), *count))
+				.collect::<Vec<_>>()
+		};
+		assert_eq!(
+			variants(SageGame::CasinoJam),
+			vec![
+				("bandit".to_string(), 1),
+				("human".to_string(), 1),
+				("seat".to_string(), 1),
+				("tracker".to_string(), 1)
+			]
+		);
+		assert!(variants(SageGame::BattleMogs).is_empty());
+		assert_eq!(metrics.jackpots, vec![(bandit_id, 0)]);
+		assert!(metrics.machine_funds > 0);
+	});
+}
+
+#[test]
+fn affiliates_unlock_after_gambles() {
+	new_test_ext().execute_with(|| {
+		let [human_id, tracker_id, seat_id, bandit_id] = setup_casino();
+		let params = CasinoJamUnlockParameters { min_gambles: 1 };
+
+		assert_noop!(
+			CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params),
+			DispatchError::Other("Not enough gambles to become an affiliator")
+		);
+
+		transition(
+			&bob(),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+		);
+		assert_ok!(CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params));
+
+		// The default requirement is not met by a single gamble.
+		assert!(
+			SageCasinoJamAffiliates::enable_affiliator(RuntimeOrigin::signed(bob()), None).is_err()
+		);
+	});
+}
+
+#[test]
+fn tournament_ranker_orders_trackers_by_gambles() {
+	new_test_ext().execute_with(|| {
+		let [human_id, tracker_id, seat_id, bandit_id] = setup_casino();
+		let (_, idle_tracker) =
+			find_asset(&bob(), VariantType::Player(PlayerType::Tracker)).unwrap();
+
+		transition(
+			&bob(),
+			CasinoAction::Gamble(MultiplierType::V1),
+			vec![human_id, tracker_id, seat_id, bandit_id],
+		);
+		let (_, active_tracker) =
+			find_asset(&bob(), VariantType::Player(PlayerType::Tracker)).unwrap();
+		let (_, human) = find_asset(&bob(), VariantType::Player(PlayerType::Human)).unwrap();
+
+		let ranker = CasinoJamEntityRanker;
+		assert!(ranker.can_rank((&tracker_id, &active_tracker)));
+		assert!(!ranker.can_rank((&human_id, &human)));
+		assert_eq!(
+			ranker.rank_against((&tracker_id, &active_tracker), (&tracker_id, &idle_tracker)),
+			Ordering::Greater
+		);
+		assert_eq!(
+			pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::get(tracker_id)
+				.map(|(owner, _)| owner),
+			Some(bob())
+		);
+	});
+}
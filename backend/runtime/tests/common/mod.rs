@@ -0,0 +1,108 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+#![allow(dead_code)]
+
+use sage_playground_runtime::{
+	configs::sage::{
+		battle_mogs::SageBattleMogsId,
+		casino_jam::{SageCasinoJamId, SeatReclaimerId},
+	},
+	genesis_config_presets::{get_preset, GENESIS_SEASON_DURATION, GENESIS_SEASON_ID},
+	migrations::record_genesis_layouts,
+	AccountId, Balance, Balances, BlockNumber, RuntimeEvent, RuntimeGenesisConfig,
+	SageBattleMogsSeasons, SageBattleMogsTournament, SageCasinoJamSeasons, SageCasinoJamTournament,
+	System, UNIT,
+};
+
+use frame_support::{
+	genesis_builder_helper::build_state,
+	traits::{fungible::Mutate, OnFinalize, OnInitialize},
+};
+use sp_keyring::AccountKeyring;
+use sp_runtime::traits::AccountIdConversion;
+
+pub const SEASON_ID: u32 = GENESIS_SEASON_ID;
+pub const SEASON_DURATION: BlockNumber = GENESIS_SEASON_DURATION;
+pub const INITIAL_BALANCE: Balance = 1_000 * UNIT;
+
+pub fn alice() -> AccountId {
+	AccountKeyring::Alice.to_account_id()
+}
+
+pub fn bob() -> AccountId {
+	AccountKeyring::Bob.to_account_id()
+}
+
+pub fn charlie() -> AccountId {
+	AccountKeyring::Charlie.to_account_id()
+}
+
+/// Pallets whose hooks drive the game lifecycle, everything else is left out on purpose
+/// since it expects inherents we don't provide in these tests.
+type GamePallets = (
+	SageCasinoJamSeasons,
+	SageCasinoJamTournament,
+	SageBattleMogsSeasons,
+	SageBattleMogsTournament,
+);
+
+/// Builds the externalities from the development genesis preset, with Alice as organizer
+/// of both games and the first season running.
+///
+/// Like the genesis builder of the runtime, it records the current asset layouts. Charlie and
+/// the game pallet accounts, which the preset doesn't endow, get [`INITIAL_BALANCE`] on top.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let preset = get_preset(&sp_genesis_builder::DEV_RUNTIME_PRESET.into())
+		.expect("the development preset exists");
+
+	let mut ext = sp_io::TestExternalities::new_empty();
+	ext.execute_with(|| {
+		build_state::<RuntimeGenesisConfig>(preset).expect("the development preset builds");
+		record_genesis_layouts();
+
+		for account in [charlie()].into_iter().chain([
+			SageCasinoJamId::get().into_account_truncating(),
+			SageBattleMogsId::get().into_account_truncating(),
+			SeatReclaimerId::get().into_account_truncating(),
+		]) {
+			Balances::mint_into(&account, INITIAL_BALANCE).unwrap();
+		}
+		System::reset_events();
+
+		run_to_block(1);
+	});
+	ext
+}
+
+pub fn run_to_block(n: BlockNumber) {
+	while System::block_number() < n {
+		let current = System::block_number();
+		if current > 0 {
+			GamePallets::on_finalize(current);
+		}
+		System::set_block_number(current + 1);
+		GamePallets::on_initialize(current + 1);
+	}
+}
+
+pub fn free_balance(account: &AccountId) -> Balance {
+	Balances::free_balance(account)
+}
+
+pub fn events() -> Vec<RuntimeEvent> {
+	System::events().into_iter().map(|record| record.event).collect()
+}
@@ -0,0 +1,239 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use sage_playground_runtime::{
+	configs::{
+		sage::{
+			battle_mogs::{BattleMogsAssetFor, BattleMogsAssetLayout, SageBattleMogsInstance},
+			casino_jam::{CasinoJamAsset, CasinoJamAssetLayout, SageCasinoJamInstance},
+		},
+		RuntimeBlockWeights,
+	},
+	migrations::{
+		max_upgraded_assets, upgrade_weight_for, AssetLayoutVersion, GameAssetLayout,
+		MigrateGameAssets,
+	},
+	AccountId, Runtime,
+};
+
+use frame_support::{
+	storage::unhashed,
+	traits::{Get, OnRuntimeUpgrade},
+};
+use game_casino_jam::{migration::v0, prelude::*};
+use parity_scale_codec::{DecodeAll, Encode};
+
+type MigrateCasinoJam = MigrateGameAssets<SageCasinoJamInstance, CasinoJamAssetLayout>;
+type MigrateBattleMogs = MigrateGameAssets<SageBattleMogsInstance, BattleMogsAssetLayout>;
+type CasinoJamAssets = pallet_sage::Assets<Runtime, SageCasinoJamInstance>;
+type BattleMogsAssets = pallet_sage::Assets<Runtime, SageBattleMogsInstance>;
+
+fn insert_raw_asset(asset_id: AssetId, owner: AccountId, asset: impl Encode) {
+	unhashed::put_raw(&CasinoJamAssets::hashed_key_for(asset_id), &(owner, asset).encode());
+}
+
+/// Layout whose version 0 stored a `u32` counter that version 1 splits into a `u16` counter
+/// and a `u16` level, so the same four bytes are valid in both layouts.
+struct CounterLayout;
+
+impl GameAssetLayout for CounterLayout {
+	type Asset = (u16, u16);
+
+	const VERSION: u16 = 1;
+
+	fn upgrade_asset_from(version: u16, encoded: &[u8]) -> Option<Self::Asset> {
+		let input = &mut &encoded[..];
+		match version {
+			0 => u32::decode_all(input).ok().map(|count| (count.min(u16::MAX.into()) as u16, 0)),
+			1 => <(u16, u16)>::decode_all(input).ok(),
+			_ => None,
+		}
+	}
+}
+
+fn v0_tracker(asset_id: AssetId) -> v0::Asset<u32> {
+	v0::Asset {
+		id: asset_id,
+		collection_id: 1,
+		genesis: 1,
+		variant: v0::AssetVariant::Player(v0::PlayerVariant::Tracker(v0::TrackerVariant {
+			slot_a_result: (1, 2),
+			slot_b_result: (3, 4),
+			slot_c_result: (5, 6),
+			slot_d_result: (7, 8),
+			last_reward: 9,
+		})),
+	}
+}
+
+fn run_upgrade<Migration: OnRuntimeUpgrade>() {
+	#[cfg(feature = "try-runtime")]
+	let state = Migration::pre_upgrade().unwrap();
+	Migration::on_runtime_upgrade();
+	#[cfg(feature = "try-runtime")]
+	Migration::post_upgrade(state).unwrap();
+}
+
+#[test]
+fn upgrades_v0_casino_jam_assets() {
+	new_test_ext().execute_with(|| {
+		AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::put(0);
+		let machine = CasinoJamAsset::new_bandit_machine(1, 1);
+		insert_raw_asset(1, alice(), machine);
+		insert_raw_asset(2, bob(), v0_tracker(2));
+		assert!(CasinoJamAssets::get(2).is_none());
+
+		run_upgrade::<MigrateCasinoJam>();
+
+		let (owner, tracker) = CasinoJamAssets::get(2).expect("tracker has been upgraded");
+		assert_eq!(owner, bob());
+		assert_eq!(tracker, v0_tracker(2).into());
+		assert_eq!(CasinoJamAssets::get(1), Some((alice(), machine)));
+		assert_eq!(
+			AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::get(),
+			game_casino_jam::migration::ASSET_LAYOUT_VERSION
+		);
+	});
+}
+
+#[test]
+fn upgrades_fit_a_block_up_to_the_asset_limit() {
+	new_test_ext().execute_with(|| {
+		AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::put(0);
+		for asset_id in 1..=100 {
+			insert_raw_asset(asset_id, bob(), v0_tracker(asset_id));
+		}
+		let asset_count = CasinoJamAssets::iter_keys().count() as u64;
+		let weight = MigrateCasinoJam::on_runtime_upgrade();
+		assert!(weight.all_lte(upgrade_weight_for(asset_count)));
+
+		let max_block = RuntimeBlockWeights::get().max_block;
+		assert!(upgrade_weight_for(max_upgraded_assets()).all_lte(max_block));
+		assert!(!upgrade_weight_for(max_upgraded_assets() + 1).all_lte(max_block));
+	});
+}
+
+#[test]
+fn leaves_current_layout_untouched() {
+	new_test_ext().execute_with(|| {
+		let tracker = CasinoJamAsset::new_tracker(2, 1);
+		insert_raw_asset(2, bob(), tracker);
+
+		run_upgrade::<MigrateCasinoJam>();
+		assert_eq!(CasinoJamAssets::get(2), Some((bob(), tracker)));
+
+		// Once upgraded, the migration does nothing anymore.
+		insert_raw_asset(3, bob(), v0_tracker(3));
+		MigrateCasinoJam::on_runtime_upgrade();
+		assert!(CasinoJamAssets::get(3).is_none());
+	});
+}
+
+#[test]
+fn decodes_assets_with_the_recorded_layout_only() {
+	new_test_ext().execute_with(|| {
+		AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::put(0);
+		// Reads as `(2, 1)` in the current layout, but is a v0 counter of 65538.
+		insert_raw_asset(1, bob(), 0x0001_0002_u32);
+
+		run_upgrade::<MigrateGameAssets<SageCasinoJamInstance, CounterLayout>>();
+
+		assert_eq!(
+			unhashed::get::<(AccountId, (u16, u16))>(&CasinoJamAssets::hashed_key_for(1)),
+			Some((bob(), (u16::MAX, 0)))
+		);
+	});
+}
+
+#[test]
+fn leaves_undecodable_assets_as_they_are() {
+	new_test_ext().execute_with(|| {
+		AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::put(0);
+		let key = CasinoJamAssets::hashed_key_for(1);
+		let raw = (bob(), [0xff_u8; 3]).encode();
+		unhashed::put_raw(&key, &raw);
+
+		MigrateCasinoJam::on_runtime_upgrade();
+
+		assert_eq!(unhashed::get_raw(&key), Some(raw));
+		assert_eq!(
+			AssetLayoutVersion::<Runtime, SageCasinoJamInstance>::get(),
+			game_casino_jam::migration::ASSET_LAYOUT_VERSION
+		);
+	});
+}
+
+#[test]
+fn battle_mogs_is_at_current_layout() {
+	new_test_ext().execute_with(|| {
+		run_upgrade::<MigrateBattleMogs>();
+
+		assert_eq!(
+			AssetLayoutVersion::<Runtime, SageBattleMogsInstance>::get(),
+			game_battle_mogs::migration::ASSET_LAYOUT_VERSION
+		);
+	});
+}
+
+#[test]
+fn upgrades_v0_battle_mogs_assets() {
+	use game_battle_mogs::{migration::v0 as mogs_v0, prelude::*};
+
+	new_test_ext().execute_with(|| {
+		AssetLayoutVersion::<Runtime, SageBattleMogsInstance>::put(0);
+		let old = mogs_v0::BattleMogsAsset::<u32> {
+			id: 1,
+			genesis: 1,
+			variant: mogs_v0::BattleMogsVariant::Mogwai(mogs_v0::Mogwai {
+				dna: [[1; 32], [2; 32]],
+				generation: MogwaiGeneration::Second,
+				rarity: RarityType::Rare,
+				phase: PhaseType::Hatched,
+			}),
+		};
+		unhashed::put_raw(&BattleMogsAssets::hashed_key_for(1), &(bob(), old).encode());
+		assert!(BattleMogsAssets::get(1).is_none());
+
+		run_upgrade::<MigrateBattleMogs>();
+
+		let (owner, mogwai) = BattleMogsAssets::get(1).expect("mogwai has been upgraded");
+		assert_eq!(owner, bob());
+		assert_eq!(
+			mogwai,
+			BattleMogsAssetFor {
+				id: 1,
+				genesis: 1,
+				variant: BattleMogsVariant::Mogwai(Mogwai {
+					dna: [[1; 32], [2; 32]],
+					generation: MogwaiGeneration::Second,
+					rarity: RarityType::Rare,
+					phase: PhaseType::Hatched,
+					stud: None,
+					parents: None,
+					lineage_depth: 0,
+					last_bred: None,
+					breed_count: 0,
+					expedition: None,
+					injured_until: None,
+					counted_by: None,
+				}),
+			}
+		);
+	});
+}
@@ -0,0 +1,350 @@
+// Ajuna Node
+// Copyright (C) 2022 BlogaTech AG
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU Affero General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU Affero General Public License for more details.
+
+// You should have received a copy of the GNU Affero General Public License
+// along with this program.  If not, see <http://www.gnu.org/licenses/>.
+
+mod common;
+
+use common::*;
+use sage_playground_runtime::{
+	configs::sage::{battle_mogs::*, casino_jam::*},
+	AccountId, Runtime, RuntimeOrigin, SageBattleMogs, SageCasinoJam, UNIT,
+};
+
+use ajuna_primitives::sage_api::SageApi;
+use frame_support::{assert_noop, assert_ok};
+use game_battle_mogs::{prelude::*, BattleMogsAction};
+use game_casino_jam::prelude::*;
+use pallet_ajuna_affiliates::traits::AffiliateUnlockRules;
+use sp_runtime::DispatchError;
+
+mod casino_jam {
+	use super::*;
+
+	fn create_player(account: &AccountId) -> CasinoJamAssetId {
+		assert_ok!(SageCasinoJam::state_transition(
+			RuntimeOrigin::signed(account.clone()),
+			CasinoAction::Create(AssetType::Player),
+			vec![],
+			None,
+		));
+
+		SageCasinoJamEngine::iter_assets_from(account)
+			.find_map(|(asset_id, asset)| {
+				matches!(asset.variant, AssetVariant::Player(PlayerVariant::Tracker(_)))
+					.then_some(asset_id)
+			})
+			.expect("tracker has been minted")
+	}
+
+	fn set_gamble_count(tracker_id: CasinoJamAssetId, gamble_count: u32) {
+		pallet_sage::Assets::<Runtime, SageCasinoJamInstance>::mutate(tracker_id, |maybe_entry| {
+			if let Some((_, asset)) = maybe_entry {
+				if let AssetVariant::Player(PlayerVariant::Tracker(tracker)) = &mut asset.variant {
+					tracker.gamble_count = gamble_count;
+				}
+			}
+		});
+	}
+
+	#[test]
+	fn unlock_fails_without_tracker() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(
+					&bob(),
+					CasinoJamUnlockParameters::default(),
+				),
+				DispatchError::Other("Account has no tracker asset")
+			);
+		});
+	}
+
+	#[test]
+	fn unlock_requires_min_gambles() {
+		new_test_ext().execute_with(|| {
+			let tracker_id = create_player(&bob());
+			let params = CasinoJamUnlockParameters::default();
+
+			assert_noop!(
+				CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params),
+				DispatchError::Other("Not enough gambles to become an affiliator")
+			);
+
+			set_gamble_count(tracker_id, params.min_gambles - 1);
+			assert_noop!(
+				CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params),
+				DispatchError::Other("Not enough gambles to become an affiliator")
+			);
+
+			set_gamble_count(tracker_id, params.min_gambles);
+			assert_ok!(CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params));
+		});
+	}
+
+	#[test]
+	fn unlock_respects_custom_parameters() {
+		new_test_ext().execute_with(|| {
+			let tracker_id = create_player(&bob());
+			set_gamble_count(tracker_id, 3);
+
+			assert_ok!(CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(
+				&bob(),
+				CasinoJamUnlockParameters { min_gambles: 3 },
+			));
+			assert_noop!(
+				CasinoJamAffiliateUnlockRules::execute_unlock_rule_for(
+					&bob(),
+					CasinoJamUnlockParameters { min_gambles: 4 },
+				),
+				DispatchError::Other("Not enough gambles to become an affiliator")
+			);
+		});
+	}
+}
+
+mod battle_mogs {
+	use super::*;
+
+	fn register_player(account: &AccountId) -> BattleMogsAssetId {
+		assert_ok!(SageBattleMogs::state_transition(
+			RuntimeOrigin::signed(account.clone()),
+			BattleMogsAction::RegisterPlayer,
+			vec![],
+			None,
+		));
+
+		SageBattleMogsEngine::iter_assets_from(account)
+			.find_map(|(asset_id, asset)| asset.is_player().then_some(asset_id))
+			.expect("player has been minted")
+	}
+
+	fn complete_egg_hatcher(player_id: BattleMogsAssetId) {
+		pallet_sage::Assets::<Runtime, SageBattleMogsInstance>::mutate(player_id, |maybe_entry| {
+			if let Some((_, asset)) = maybe_entry {
+				if let BattleMogsVariant::Player(player) = &mut asset.variant {
+					player.achievements.egg_hatcher = AchievementState::Completed;
+				}
+			}
+		});
+	}
+
+	#[test]
+	fn unlock_fails_for_unregistered_players() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				BattleMogsAffiliateUnlockRules::execute_unlock_rule_for(
+					&bob(),
+					BattleMogsUnlockParameters::default(),
+				),
+				DispatchError::Other("Account is not a registered player")
+			);
+		});
+	}
+
+	#[test]
+	fn unlock_requires_completed_achievement() {
+		new_test_ext().execute_with(|| {
+			let player_id = register_player(&bob());
+			let params = BattleMogsUnlockParameters::default();
+
+			assert_noop!(
+				BattleMogsAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params),
+				DispatchError::Other("Not enough completed achievements to become an affiliator")
+			);
+
+			complete_egg_hatcher(player_id);
+			assert_ok!(BattleMogsAffiliateUnlockRules::execute_unlock_rule_for(&bob(), params));
+			assert_noop!(
+				BattleMogsAffiliateUnlockRules::execute_unlock_rule_for(
+					&bob(),
+					BattleMogsUnlockParameters { min_completed_achievements: 2 },
+				),
+				DispatchError::Other("Not enough completed achievements to become an affiliator")
+			);
+		});
+	}
+}
+
+mod vouchers {
+	use super::*;
+	use ajuna_primitives::payment_handler::WithdrawKind;
+	use sage_playground_runtime::{RuntimeEvent, SageBattleMogsVouchers, SageCasinoJamVouchers};
+
+	#[test]
+	fn only_organizer_can_issue_vouchers() {
+		new_test_ext().execute_with(|| {
+			assert_noop!(
+				SageCasinoJamVouchers::issue_vouchers(
+					RuntimeOrigin::signed(bob()),
+					bob(),
+					UNIT,
+					10
+				),
+				DispatchError::BadOrigin
+			);
+			assert_ok!(SageCasinoJamVouchers::issue_vouchers(
+				RuntimeOrigin::signed(alice()),
+				bob(),
+				UNIT,
+				10
+			));
+		});
+	}
+
+	#[test]
+	fn vouchers_are_scoped_per_game() {
+		new_test_ext().execute_with(|| {
+			assert_ok!(SageCasinoJamVouchers::issue_vouchers(
+				RuntimeOrigin::signed(alice()),
+				bob(),
+				UNIT,
+				10
+			));
+
+			assert!(SageCasinoJamVouchers::vouchers_of(&bob()).is_some());
+			assert!(SageBattleMogsVouchers::vouchers_of(&bob()).is_none());
+		});
+	}
+
+	#[test]
+	fn casino_jam_trades_spend_vouchers_until_they_run_out() {
+		new_test_ext().execute_with(|| {
+			let human = VariantType::Player(PlayerType::Human);
+			assert_ok!(SageCasinoJam::state_transition(
+				RuntimeOrigin::signed(bob()),
+				CasinoAction::Create(AssetType::Player),
+				vec![],
+				None,
+			));
+			let human_id = SageCasinoJamEngine::iter_assets_from(&bob())
+				.find_map(|(asset_id, asset)| asset.variant.is_variant(human).then_some(asset_id))
+				.expect("human has been minted");
+			assert_ok!(SageCasinoJam::update_general_config(
+				RuntimeOrigin::signed(alice()),
+				pallet_sage::GeneralConfig { transfer: true, trade: true },
+			));
+			assert_ok!(SageCasinoJam::update_asset_filter(
+				RuntimeOrigin::signed(alice()),
+				SEASON_ID,
+				pallet_sage::AssetFilterOption::Trade(human),
+			));
+			assert_ok!(SageCasinoJamVouchers::issue_vouchers(
+				RuntimeOrigin::signed(alice()),
+				charlie(),
+				UNIT,
+				10
+			));
+
+			let native_before = free_balance(&charlie());
+			let sell = |seller: AccountId| {
+				assert_ok!(SageCasinoJam::set_asset_price(
+					RuntimeOrigin::signed(seller),
+					human_id,
+					UNIT
+				));
+			};
+			sell(bob());
+			assert_ok!(SageCasinoJam::buy_asset(
+				RuntimeOrigin::signed(charlie()),
+				human_id,
+				Some(WithdrawKind::Voucher)
+			));
+			assert!(events().contains(&RuntimeEvent::SageCasinoJamVouchers(
+				pallet_sage_vouchers::Event::VouchersConsumed { account: charlie(), amount: UNIT }
+			)));
+			assert_eq!(SageCasinoJamVouchers::vouchers_of(&charlie()), None);
+			assert_eq!(free_balance(&charlie()), native_before);
+
+			// Once spent, the vouchers no longer pay for the trade.
+			sell(charlie());
+			assert_ok!(SageCasinoJam::buy_asset(RuntimeOrigin::signed(bob()), human_id, None));
+			sell(bob());
+			assert!(SageCasinoJam::buy_asset(
+				RuntimeOrigin::signed(charlie()),
+				human_id,
+				Some(WithdrawKind::Voucher)
+			)
+			.is_err());
+			assert!(SageCasinoJamEngine::iter_assets_from(&bob()).any(|(id, _)| id == human_id));
+		});
+	}
+
+	#[test]
+	fn battle_mogs_trades_spend_vouchers_until_they_run_out() {
+		new_test_ext().execute_with(|| {
+			for action in [BattleMogsAction::RegisterPlayer, BattleMogsAction::CreateMogwai] {
+				assert_ok!(SageBattleMogs::state_transition(
+					RuntimeOrigin::signed(bob()),
+					action,
+					vec![],
+					None,
+				));
+			}
+			let (mogwai_id, rarity) = SageBattleMogsEngine::iter_assets_from(&bob())
+				.find_map(|(asset_id, asset)| match asset.variant {
+					BattleMogsVariant::Mogwai(mogwai) => Some((asset_id, mogwai.rarity)),
+					_ => None,
+				})
+				.expect("mogwai has been minted");
+			assert_ok!(SageBattleMogs::update_general_config(
+				RuntimeOrigin::signed(alice()),
+				pallet_sage::GeneralConfig { transfer: true, trade: true },
+			));
+			assert_ok!(SageBattleMogs::update_asset_filter(
+				RuntimeOrigin::signed(alice()),
+				SEASON_ID,
+				pallet_sage::AssetFilterOption::Trade(rarity),
+			));
+			assert_ok!(SageBattleMogsVouchers::issue_vouchers(
+				RuntimeOrigin::signed(alice()),
+				charlie(),
+				UNIT,
+				10
+			));
+
+			let native_before = free_balance(&charlie());
+			let sell = |seller: AccountId| {
+				assert_ok!(SageBattleMogs::set_asset_price(
+					RuntimeOrigin::signed(seller),
+					mogwai_id,
+					UNIT
+				));
+			};
+			sell(bob());
+			assert_ok!(SageBattleMogs::buy_asset(
+				RuntimeOrigin::signed(charlie()),
+				mogwai_id,
+				Some(WithdrawKind::Voucher)
+			));
+			assert!(events().contains(&RuntimeEvent::SageBattleMogsVouchers(
+				pallet_sage_vouchers::Event::VouchersConsumed { account: charlie(), amount: UNIT }
+			)));
+			assert_eq!(SageBattleMogsVouchers::vouchers_of(&charlie()), None);
+			assert_eq!(free_balance(&charlie()), native_before);
+
+			// Once spent, the vouchers no longer pay for the trade.
+			sell(charlie());
+			assert_ok!(SageBattleMogs::buy_asset(RuntimeOrigin::signed(bob()), mogwai_id, None));
+			sell(bob());
+			assert!(SageBattleMogs::buy_asset(
+				RuntimeOrigin::signed(charlie()),
+				mogwai_id,
+				Some(WithdrawKind::Voucher)
+			)
+			.is_err());
+			assert!(SageBattleMogsEngine::iter_assets_from(&bob()).any(|(id, _)| id == mogwai_id));
+		});
+	}
+}